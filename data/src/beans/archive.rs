@@ -0,0 +1,176 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    AsianHandicapOdds, BookMaker, League, Matches, Odds, OddsError, OddsSnapshot, OverUnderOdds,
+    Team,
+};
+
+/// version of the archive format written by `Archive::to_json`
+pub const ARCHIVE_VERSION: u32 = 2;
+
+/// the bookmakers, leagues, teams, matches and odds of a database with the price snapshots,
+/// asian handicap and over/under odds of the matches, the ids are the ones of the exported
+/// database and are remapped when the archive is restored
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Archive {
+    pub version: u32,
+    pub exported_at: NaiveDateTime,
+    pub bookmakers: Vec<BookMaker>,
+    pub leagues: Vec<League>,
+    pub teams: Vec<Team>,
+    pub matches: Vec<Matches>,
+    pub odds: Vec<Odds>,
+    /// added in version 2
+    #[serde(default)]
+    pub snapshots: Vec<OddsSnapshot>,
+    /// added in version 2
+    #[serde(default)]
+    pub asian_handicaps: Vec<AsianHandicapOdds>,
+    /// added in version 2
+    #[serde(default)]
+    pub over_unders: Vec<OverUnderOdds>,
+}
+
+impl Archive {
+    pub fn new(exported_at: NaiveDateTime) -> Self {
+        Self {
+            version: ARCHIVE_VERSION,
+            exported_at,
+            bookmakers: vec![],
+            leagues: vec![],
+            teams: vec![],
+            matches: vec![],
+            odds: vec![],
+            snapshots: vec![],
+            asian_handicaps: vec![],
+            over_unders: vec![],
+        }
+    }
+
+    pub fn to_json(&self) -> Result<String, OddsError> {
+        serde_json::to_string_pretty(self).map_err(|err| OddsError::InvalidArchive(err.to_string()))
+    }
+
+    /// read an archive of this or an earlier version, an archive of an earlier
+    /// version is brought up to the current format
+    pub fn from_json(text: &str) -> Result<Self, OddsError> {
+        #[derive(Deserialize)]
+        struct Version {
+            version: u32,
+        }
+
+        let invalid = |err: serde_json::Error| OddsError::InvalidArchive(err.to_string());
+        let Version { version } = serde_json::from_str(text).map_err(invalid)?;
+        match version {
+            ARCHIVE_VERSION => serde_json::from_str(text).map_err(invalid),
+            // version 1 has no snapshots, asian handicap or over/under odds
+            1 => {
+                let archive: Self = serde_json::from_str(text).map_err(invalid)?;
+                Ok(Self {
+                    version: ARCHIVE_VERSION,
+                    ..archive
+                })
+            }
+            _ => Err(OddsError::InvalidArchive(format!(
+                "version {version} is not supported, the latest version is {ARCHIVE_VERSION}"
+            ))),
+        }
+    }
+}
+
+/// what to do with an archived row whose bookmaker, league or team name, match of the
+/// same league, teams and kickoff, odds of the same match and bookmaker, or snapshot of
+/// the same match, bookmaker and recording time is stored
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictPolicy {
+    /// keep the stored row
+    #[default]
+    Skip,
+    /// replace the stored row with the archived one
+    Overwrite,
+    /// restore nothing
+    Fail,
+}
+
+/// rows of a table handled by a restore
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RestoreCount {
+    pub created: usize,
+    pub overwritten: usize,
+    pub skipped: usize,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RestoreReport {
+    pub bookmakers: RestoreCount,
+    pub leagues: RestoreCount,
+    pub teams: RestoreCount,
+    pub matches: RestoreCount,
+    pub odds: RestoreCount,
+    pub snapshots: RestoreCount,
+    pub asian_handicaps: RestoreCount,
+    pub over_unders: RestoreCount,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{LeagueBuilder, OddsBuilder, OverUnderOddsBuilder};
+
+    use super::*;
+
+    #[test]
+    fn archive_should_be_read_back() {
+        let mut archive = Archive::new(NaiveDateTime::default());
+        archive
+            .leagues
+            .push(LeagueBuilder::default().id(3).name("英超").build().unwrap());
+        archive.odds.push(
+            OddsBuilder::default()
+                .home_win_start_setter("2.10")
+                .build()
+                .unwrap(),
+        );
+        archive.over_unders.push(
+            OverUnderOddsBuilder::default()
+                .line_end("2.75".parse().unwrap())
+                .build()
+                .unwrap(),
+        );
+        let json = archive.to_json().unwrap();
+        let archive = Archive::from_json(&json).unwrap();
+        assert_eq!(archive.version, ARCHIVE_VERSION);
+        assert_eq!(archive.leagues[0].id, 3);
+        assert_eq!(archive.odds[0].home_win_start.to_string(), "2.10");
+        assert_eq!(archive.over_unders[0].line_end.to_string(), "2.75");
+    }
+
+    #[test]
+    fn version_1_should_be_read_without_snapshots() {
+        let json = r#"{
+            "version": 1,
+            "exported_at": "2023-04-01T20:00:00",
+            "bookmakers": [],
+            "leagues": [],
+            "teams": [],
+            "matches": [],
+            "odds": []
+        }"#;
+        let archive = Archive::from_json(json).unwrap();
+        assert_eq!(archive.version, ARCHIVE_VERSION);
+        assert!(archive.snapshots.is_empty());
+        assert!(archive.asian_handicaps.is_empty());
+        assert!(archive.over_unders.is_empty());
+    }
+
+    #[test]
+    fn unknown_version_should_be_rejected() {
+        for json in ["{\"version\": 3}", "{}", "[]"] {
+            assert!(matches!(
+                Archive::from_json(json),
+                Err(OddsError::InvalidArchive(_))
+            ));
+        }
+    }
+}
@@ -0,0 +1,284 @@
+use chrono::{DateTime, NaiveDateTime};
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+
+use crate::{Matches, OddsError, Outcome};
+
+/// page size used when the requested one is out of range
+pub const DEFAULT_PAGE_SIZE: i32 = 10;
+
+/// largest page size of a match query
+pub const MAX_PAGE_SIZE: i32 = 100;
+
+/// how the predicted result of a match compares with its actual result
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PredictionFilter {
+    /// the prediction matches the result
+    Hit,
+    /// the prediction differs from the result
+    Miss,
+}
+
+/// which side of the cursor row a page lies on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CursorDirection {
+    After,
+    Before,
+}
+
+/// position of a match in the (game time, id) order of a match query
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MatchCursor {
+    pub game_time: NaiveDateTime,
+    pub id: i32,
+    pub direction: CursorDirection,
+}
+
+impl MatchCursor {
+    pub fn new(matches: &Matches, direction: CursorDirection) -> Self {
+        Self {
+            game_time: matches.game_time.unwrap_or_default(),
+            id: matches.id,
+            direction,
+        }
+    }
+
+    /// the opaque text handed to callers
+    pub fn encode(&self) -> String {
+        let direction = match self.direction {
+            CursorDirection::After => 'a',
+            CursorDirection::Before => 'b',
+        };
+        let micros = self.game_time.and_utc().timestamp_micros();
+        format!("{direction}:{micros}:{}", self.id)
+            .bytes()
+            .map(|byte| format!("{byte:02x}"))
+            .collect()
+    }
+
+    pub fn decode(cursor: &str) -> Result<Self, OddsError> {
+        let invalid = || OddsError::InvalidCursor(cursor.to_string());
+        if !cursor.len().is_multiple_of(2) || !cursor.is_ascii() {
+            return Err(invalid());
+        }
+        let bytes = (0..cursor.len())
+            .step_by(2)
+            .map(|index| u8::from_str_radix(&cursor[index..index + 2], 16))
+            .collect::<Result<Vec<u8>, _>>()
+            .map_err(|_| invalid())?;
+        let text = String::from_utf8(bytes).map_err(|_| invalid())?;
+
+        let mut parts = text.splitn(3, ':');
+        let direction = match parts.next() {
+            Some("a") => CursorDirection::After,
+            Some("b") => CursorDirection::Before,
+            _ => return Err(invalid()),
+        };
+        let game_time = parts
+            .next()
+            .and_then(|micros| micros.parse().ok())
+            .and_then(DateTime::from_timestamp_micros)
+            .ok_or_else(invalid)?
+            .naive_utc();
+        let id = parts
+            .next()
+            .and_then(|id| id.parse().ok())
+            .ok_or_else(invalid)?;
+        Ok(Self {
+            game_time,
+            id,
+            direction,
+        })
+    }
+}
+
+/// conditions of a match query, a condition left as None matches every match
+/// and the given conditions are all required
+#[derive(Debug, Clone, Builder, Serialize, Deserialize)]
+#[builder(default)]
+#[serde(default)]
+pub struct MatchFilter {
+    /// matches with odds of the bookmaker
+    #[builder(setter(strip_option))]
+    pub bookmaker_id: Option<i32>,
+    #[builder(setter(strip_option))]
+    pub league_id: Option<i32>,
+    /// matches of the team at home or away
+    #[builder(setter(strip_option))]
+    pub team_id: Option<i32>,
+    #[builder(setter(into, strip_option))]
+    pub game_year: Option<String>,
+    #[builder(setter(into, strip_option))]
+    pub game_round: Option<String>,
+    /// matches kicking off at or after the time
+    #[builder(setter(strip_option))]
+    pub start_time: Option<NaiveDateTime>,
+    /// matches kicking off before the time
+    #[builder(setter(strip_option))]
+    pub end_time: Option<NaiveDateTime>,
+    /// matches with the full-time outcome
+    #[builder(setter(strip_option))]
+    pub result: Option<Outcome>,
+    /// settled and predicted matches whose prediction hit or missed
+    #[builder(setter(strip_option))]
+    pub prediction: Option<PredictionFilter>,
+    /// latest kickoff first, matches are ordered by game time and id
+    pub is_desc: bool,
+    /// cursor of a page returned before, the first page when None
+    #[builder(setter(into, strip_option))]
+    pub cursor: Option<String>,
+    pub page_size: i32,
+}
+
+impl Default for MatchFilter {
+    fn default() -> Self {
+        Self {
+            bookmaker_id: None,
+            league_id: None,
+            team_id: None,
+            game_year: None,
+            game_round: None,
+            start_time: None,
+            end_time: None,
+            result: None,
+            prediction: None,
+            is_desc: false,
+            cursor: None,
+            page_size: DEFAULT_PAGE_SIZE,
+        }
+    }
+}
+
+impl MatchFilter {
+    /// page size of the query, a size out of 1 to 100 falls back to 10
+    pub fn limit(&self) -> i32 {
+        if (1..=MAX_PAGE_SIZE).contains(&self.page_size) {
+            self.page_size
+        } else {
+            DEFAULT_PAGE_SIZE
+        }
+    }
+
+    /// the decoded cursor of the page to query
+    pub fn keyset(&self) -> Result<Option<MatchCursor>, OddsError> {
+        self.cursor.as_deref().map(MatchCursor::decode).transpose()
+    }
+
+    /// whether the match meets every condition but the bookmaker,
+    /// which needs the odds of the match
+    pub fn matches(&self, matches: &Matches) -> bool {
+        let outcome = matches.outcome();
+        self.league_id.is_none_or(|id| matches.league_id == id)
+            && self
+                .team_id
+                .is_none_or(|id| matches.home_team_id == id || matches.away_team_id == id)
+            && (self.game_year.is_none() || matches.game_year == self.game_year)
+            && (self.game_round.is_none() || matches.game_round == self.game_round)
+            && self
+                .start_time
+                .is_none_or(|time| matches.game_time.is_some_and(|t| t >= time))
+            && self
+                .end_time
+                .is_none_or(|time| matches.game_time.is_some_and(|t| t < time))
+            && self.result.is_none_or(|result| outcome == Some(result))
+            && self.prediction.is_none_or(|prediction| {
+                match (matches.predicted_outcome(), outcome) {
+                    (Some(predicted), Some(actual)) => {
+                        (predicted == actual) == (prediction == PredictionFilter::Hit)
+                    }
+                    _ => false,
+                }
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::MatchesBuilder;
+
+    use super::*;
+
+    fn time(time: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(time, "%Y-%m-%d %H:%M:%S").unwrap()
+    }
+
+    #[test]
+    fn page_size_should_fall_back_to_default() {
+        let filter = MatchFilterBuilder::default().page_size(20).build().unwrap();
+        assert_eq!(filter.limit(), 20);
+        let filter = MatchFilterBuilder::default()
+            .page_size(101)
+            .build()
+            .unwrap();
+        assert_eq!(filter.limit(), 10);
+        assert_eq!(MatchFilter::default().limit(), 10);
+    }
+
+    #[test]
+    fn cursor_should_be_decoded() {
+        let cursor = MatchCursor {
+            game_time: time("2023-04-01 20:00:00"),
+            id: 42,
+            direction: CursorDirection::Before,
+        };
+        let filter = MatchFilterBuilder::default()
+            .cursor(cursor.encode())
+            .build()
+            .unwrap();
+        assert_eq!(filter.keyset().unwrap(), Some(cursor));
+        assert_eq!(MatchFilter::default().keyset().unwrap(), None);
+
+        for cursor in ["", "zz", "613a31", "633a313a32"] {
+            let filter = MatchFilterBuilder::default()
+                .cursor(cursor)
+                .build()
+                .unwrap();
+            assert!(matches!(filter.keyset(), Err(OddsError::InvalidCursor(_))));
+        }
+    }
+
+    #[test]
+    fn conditions_should_be_combined() {
+        let matches = MatchesBuilder::default()
+            .league_id(1)
+            .home_team_id(1)
+            .away_team_id(2)
+            .game_time(time("2023-04-01 20:00:00"))
+            .game_result("3")
+            .home_goals(2)
+            .away_goals(1)
+            .predict_game_result("1")
+            .build()
+            .unwrap();
+
+        assert!(MatchFilter::default().matches(&matches));
+        let filter = MatchFilterBuilder::default()
+            .league_id(1)
+            .team_id(2)
+            .start_time(time("2023-04-01 20:00:00"))
+            .end_time(time("2023-04-02 00:00:00"))
+            .result(Outcome::HomeWin)
+            .prediction(PredictionFilter::Miss)
+            .build()
+            .unwrap();
+        assert!(filter.matches(&matches));
+
+        let filter = MatchFilterBuilder::default()
+            .end_time(time("2023-04-01 20:00:00"))
+            .build()
+            .unwrap();
+        assert!(!filter.matches(&matches));
+        let filter = MatchFilterBuilder::default()
+            .prediction(PredictionFilter::Hit)
+            .build()
+            .unwrap();
+        assert!(!filter.matches(&matches));
+        let filter = MatchFilterBuilder::default()
+            .league_id(1)
+            .result(Outcome::Draw)
+            .build()
+            .unwrap();
+        assert!(!filter.matches(&matches));
+    }
+}
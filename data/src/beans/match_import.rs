@@ -0,0 +1,84 @@
+use bigdecimal::BigDecimal;
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+use crate::Outcome;
+
+/// opening and closing 1X2 prices of a bookmaker given by name
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportedOdds {
+    pub bookmaker: String,
+    pub home_win_start: BigDecimal,
+    pub draw_start: BigDecimal,
+    pub away_win_start: BigDecimal,
+    pub home_win_end: BigDecimal,
+    pub draw_end: BigDecimal,
+    pub away_win_end: BigDecimal,
+}
+
+/// a match read from an import file, the league, the teams and the bookmakers are
+/// given by name, rows of the same league, teams and kickoff are one match
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MatchImportRow {
+    /// line of the row in the file, errors of the row are reported with it
+    pub line: usize,
+    pub league: String,
+    pub home_team: String,
+    pub away_team: String,
+    pub game_time: NaiveDateTime,
+    pub game_year: Option<String>,
+    pub game_round: Option<String>,
+    pub home_goals: Option<i32>,
+    pub away_goals: Option<i32>,
+    pub half_home_goals: Option<i32>,
+    pub half_away_goals: Option<i32>,
+    /// full-time outcome of a row without goals
+    pub result: Option<Outcome>,
+    pub odds: Vec<ImportedOdds>,
+}
+
+/// why a row of an import file was not imported
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImportRowError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl ImportRowError {
+    pub fn new(line: usize, message: impl ToString) -> Self {
+        Self {
+            line,
+            message: message.to_string(),
+        }
+    }
+}
+
+/// outcome of an import, the rows are imported all together or not at all,
+/// so nothing is imported when any row has an error
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ImportReport {
+    /// number of matches created
+    pub matches: usize,
+    /// number of bookmaker odds created
+    pub odds: usize,
+    /// names of the leagues, teams and bookmakers created for the rows
+    pub created_leagues: Vec<String>,
+    pub created_teams: Vec<String>,
+    pub created_bookmakers: Vec<String>,
+    pub errors: Vec<ImportRowError>,
+}
+
+impl ImportReport {
+    /// report of an import rolled back because of the row errors
+    pub fn failed(mut errors: Vec<ImportRowError>) -> Self {
+        errors.sort_by_key(|error| error.line);
+        Self {
+            errors,
+            ..Self::default()
+        }
+    }
+
+    pub fn is_imported(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
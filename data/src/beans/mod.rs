@@ -1,3 +1,31 @@
+mod archive;
+mod match_filter;
+mod match_import;
 mod match_info;
+mod odds_band;
+mod odds_consensus;
+mod odds_info;
+mod odds_kelly;
+mod odds_movement;
+mod odds_probability;
+mod outcome;
+mod over_under_settlement;
+mod page;
+mod prediction_scorecard;
+mod similar_odds;
 
+pub use archive::*;
+pub use match_filter::*;
+pub use match_import::*;
 pub use match_info::*;
+pub use odds_band::*;
+pub use odds_consensus::*;
+pub use odds_info::*;
+pub use odds_kelly::*;
+pub use odds_movement::*;
+pub use odds_probability::*;
+pub use outcome::*;
+pub use over_under_settlement::*;
+pub use page::*;
+pub use prediction_scorecard::*;
+pub use similar_odds::*;
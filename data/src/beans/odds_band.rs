@@ -0,0 +1,36 @@
+use bigdecimal::BigDecimal;
+use serde::{Deserialize, Serialize};
+
+use crate::OutcomeDistribution;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OddsBandQuery {
+    /// width of a band of closing home win prices, 0.10 when not given
+    pub band_width: Option<BigDecimal>,
+    /// 0 for all leagues
+    pub league_id: i32,
+    /// 0 for all bookmakers
+    pub bookmaker_id: i32,
+    /// split every band by league
+    pub per_league: bool,
+    /// split every band by bookmaker
+    pub per_bookmaker: bool,
+}
+
+/// results of the matches whose closing home win price falls in [lower, upper)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OddsBand {
+    pub league_id: Option<i32>,
+    pub league_name: Option<String>,
+    pub bookmaker_id: Option<i32>,
+    pub bookmaker_name: Option<String>,
+    pub lower: BigDecimal,
+    pub upper: BigDecimal,
+    pub distribution: OutcomeDistribution,
+    /// share of the settled matches per outcome
+    pub home_win_rate: BigDecimal,
+    pub draw_rate: BigDecimal,
+    pub away_win_rate: BigDecimal,
+    /// average implied home win probability of the prices in the band
+    pub implied_home_win: BigDecimal,
+}
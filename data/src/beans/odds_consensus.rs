@@ -0,0 +1,37 @@
+use bigdecimal::BigDecimal;
+use serde::{Deserialize, Serialize};
+
+use crate::Outcome;
+
+/// the bookmaker whose price strays furthest from the market mean
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OutlierBookmaker {
+    pub bookmaker_id: i32,
+    pub bookmaker_name: String,
+    pub price: BigDecimal,
+    /// price minus the market mean
+    pub deviation: BigDecimal,
+}
+
+/// statistics of the price of one outcome across bookmakers
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PriceStatistics {
+    pub outcome: Outcome,
+    /// number of bookmakers with a price
+    pub count: usize,
+    pub mean: BigDecimal,
+    pub median: BigDecimal,
+    pub min: BigDecimal,
+    pub max: BigDecimal,
+    /// population standard deviation
+    pub std_dev: BigDecimal,
+    pub outlier: Option<OutlierBookmaker>,
+}
+
+/// cross-bookmaker consensus of the opening and closing prices of a match
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OddsConsensus {
+    pub match_id: i32,
+    pub start: Vec<PriceStatistics>,
+    pub end: Vec<PriceStatistics>,
+}
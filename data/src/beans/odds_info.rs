@@ -0,0 +1,265 @@
+use std::str::FromStr;
+
+use bigdecimal::BigDecimal;
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    AsianHandicapOdds, MatchInfo, Matches, MatchesBuilder, Odds, OddsError, OverUnderOdds,
+};
+
+/// format of the game time of a match form
+pub const GAME_TIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+/// 1X2 odds of a bookmaker as entered in the odds form, a missing price is 0.00,
+/// which is a closing price that is not known yet
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OddsInfo {
+    pub id: i32,
+    pub match_id: i32,
+    pub bookmaker_id: i32,
+    pub bookmaker_name: String,
+    pub home_win_start: Option<String>,
+    pub draw_start: Option<String>,
+    pub away_win_start: Option<String>,
+    pub home_win_end: Option<String>,
+    pub draw_end: Option<String>,
+    pub away_win_end: Option<String>,
+}
+
+/// asian handicap odds of a bookmaker as entered in the odds form
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AsianHandicapInfo {
+    pub id: i32,
+    pub match_id: i32,
+    pub bookmaker_id: i32,
+    pub bookmaker_name: String,
+    pub line_start: Option<BigDecimal>,
+    pub home_price_start: Option<BigDecimal>,
+    pub away_price_start: Option<BigDecimal>,
+    pub line_end: Option<BigDecimal>,
+    pub home_price_end: Option<BigDecimal>,
+    pub away_price_end: Option<BigDecimal>,
+    pub note: Option<String>,
+}
+
+/// over/under odds of a bookmaker as entered in the odds form
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OverUnderInfo {
+    pub id: i32,
+    pub match_id: i32,
+    pub bookmaker_id: i32,
+    pub bookmaker_name: String,
+    pub line_start: Option<BigDecimal>,
+    pub over_price_start: Option<BigDecimal>,
+    pub under_price_start: Option<BigDecimal>,
+    pub line_end: Option<BigDecimal>,
+    pub over_price_end: Option<BigDecimal>,
+    pub under_price_end: Option<BigDecimal>,
+    pub note: Option<String>,
+}
+
+/// a match as entered in the match form, the game time is formatted by `GAME_TIME_FORMAT`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MatchesInfo {
+    pub id: i32,
+    pub league_id: i32,
+    pub league_name: String,
+    pub home_team_id: i32,
+    pub home_team_name: String,
+    pub away_team_id: i32,
+    pub away_team_name: String,
+    pub game_time: Option<String>,
+    pub game_year: Option<String>,
+    pub game_round: Option<String>,
+    pub game_result: Option<String>,
+    pub predict_game_result: Option<String>,
+    pub home_goals: Option<i32>,
+    pub away_goals: Option<i32>,
+    pub half_home_goals: Option<i32>,
+    pub half_away_goals: Option<i32>,
+    pub history_note: Option<String>,
+    pub note: Option<String>,
+}
+
+impl MatchesInfo {
+    /// the match with its odds to save
+    pub fn into_match_info(self, odds_infos: Vec<OddsInfo>) -> Result<MatchInfo, OddsError> {
+        let odds = odds_infos
+            .into_iter()
+            .map(Odds::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(MatchInfo::new(Matches::try_from(self)?, odds))
+    }
+}
+
+impl TryFrom<MatchesInfo> for Matches {
+    type Error = OddsError;
+
+    fn try_from(info: MatchesInfo) -> Result<Self, Self::Error> {
+        let game_time = match info.game_time {
+            Some(time) => NaiveDateTime::parse_from_str(&time, GAME_TIME_FORMAT)
+                .map_err(|_| OddsError::InvalidInput(format!("game time {time}")))?,
+            None => NaiveDateTime::default(),
+        };
+        let mut builder = MatchesBuilder::default();
+        builder
+            .id(info.id)
+            .league_id(info.league_id)
+            .league_name(info.league_name)
+            .home_team_id(info.home_team_id)
+            .away_team_id(info.away_team_id)
+            .home_team(info.home_team_name)
+            .away_team(info.away_team_name)
+            .game_time(game_time)
+            .game_year(info.game_year.unwrap_or_default())
+            .game_round(info.game_round.unwrap_or_default())
+            .game_result(info.game_result.unwrap_or_default())
+            .predict_game_result(info.predict_game_result.unwrap_or_default())
+            .history_note(info.history_note.unwrap_or_default())
+            .note(info.note.unwrap_or_default());
+        if let Some(goals) = info.home_goals {
+            builder.home_goals(goals);
+        }
+        if let Some(goals) = info.away_goals {
+            builder.away_goals(goals);
+        }
+        if let Some(goals) = info.half_home_goals {
+            builder.half_home_goals(goals);
+        }
+        if let Some(goals) = info.half_away_goals {
+            builder.half_away_goals(goals);
+        }
+        builder
+            .build()
+            .map_err(|err| OddsError::InvalidInput(err.to_string()))
+    }
+}
+
+impl TryFrom<OddsInfo> for Odds {
+    type Error = OddsError;
+
+    fn try_from(info: OddsInfo) -> Result<Self, Self::Error> {
+        Ok(Odds {
+            id: info.id,
+            match_id: info.match_id,
+            bookmaker_id: info.bookmaker_id,
+            bookmaker_name: info.bookmaker_name,
+            home_win_start: price(info.home_win_start)?,
+            draw_start: price(info.draw_start)?,
+            away_win_start: price(info.away_win_start)?,
+            home_win_end: price(info.home_win_end)?,
+            draw_end: price(info.draw_end)?,
+            away_win_end: price(info.away_win_end)?,
+            note: None,
+        })
+    }
+}
+
+fn price(value: Option<String>) -> Result<BigDecimal, OddsError> {
+    let value = value.unwrap_or_else(|| "0.00".to_string());
+    BigDecimal::from_str(&value).map_err(|_| OddsError::InvalidInput(format!("price {value}")))
+}
+
+impl From<AsianHandicapInfo> for AsianHandicapOdds {
+    fn from(info: AsianHandicapInfo) -> Self {
+        AsianHandicapOdds {
+            id: info.id,
+            match_id: info.match_id,
+            bookmaker_id: info.bookmaker_id,
+            bookmaker_name: info.bookmaker_name,
+            line_start: info.line_start.unwrap_or_default(),
+            home_price_start: info.home_price_start.unwrap_or_default(),
+            away_price_start: info.away_price_start.unwrap_or_default(),
+            line_end: info.line_end.unwrap_or_default(),
+            home_price_end: info.home_price_end.unwrap_or_default(),
+            away_price_end: info.away_price_end.unwrap_or_default(),
+            note: info.note,
+        }
+    }
+}
+
+impl From<OverUnderInfo> for OverUnderOdds {
+    fn from(info: OverUnderInfo) -> Self {
+        OverUnderOdds {
+            id: info.id,
+            match_id: info.match_id,
+            bookmaker_id: info.bookmaker_id,
+            bookmaker_name: info.bookmaker_name,
+            line_start: info.line_start.unwrap_or_default(),
+            over_price_start: info.over_price_start.unwrap_or_default(),
+            under_price_start: info.under_price_start.unwrap_or_default(),
+            line_end: info.line_end.unwrap_or_default(),
+            over_price_end: info.over_price_end.unwrap_or_default(),
+            under_price_end: info.under_price_end.unwrap_or_default(),
+            note: info.note,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn odds_info(home_win_start: Option<&str>) -> OddsInfo {
+        OddsInfo {
+            id: 0,
+            match_id: 1,
+            bookmaker_id: 2,
+            bookmaker_name: "威廉希尔".to_string(),
+            home_win_start: home_win_start.map(str::to_string),
+            draw_start: Some("3.40".to_string()),
+            away_win_start: Some("3.30".to_string()),
+            home_win_end: None,
+            draw_end: None,
+            away_win_end: None,
+        }
+    }
+
+    fn matches_info(game_time: &str) -> MatchesInfo {
+        MatchesInfo {
+            id: 0,
+            league_id: 1,
+            league_name: "英超".to_string(),
+            home_team_id: 1,
+            home_team_name: "阿森纳".to_string(),
+            away_team_id: 2,
+            away_team_name: "利物浦".to_string(),
+            game_time: Some(game_time.to_string()),
+            game_year: None,
+            game_round: None,
+            game_result: None,
+            predict_game_result: None,
+            home_goals: Some(2),
+            away_goals: Some(1),
+            half_home_goals: None,
+            half_away_goals: None,
+            history_note: None,
+            note: None,
+        }
+    }
+
+    #[test]
+    fn match_form_should_be_converted() {
+        let match_info = matches_info("2023-04-01 20:00:00")
+            .into_match_info(vec![odds_info(Some("2.10"))])
+            .unwrap();
+        assert_eq!(
+            match_info.matches.game_time.unwrap().to_string(),
+            "2023-04-01 20:00:00"
+        );
+        assert_eq!(match_info.matches.home_goals, Some(2));
+        assert_eq!(match_info.odds[0].home_win_start.to_string(), "2.10");
+        assert_eq!(match_info.odds[0].home_win_end.to_string(), "0.00");
+    }
+
+    #[test]
+    fn invalid_match_form_should_be_rejected() {
+        let err = matches_info("2023-04-01")
+            .into_match_info(vec![])
+            .unwrap_err();
+        assert!(matches!(err, OddsError::InvalidInput(_)));
+        let err = Odds::try_from(odds_info(Some("two"))).unwrap_err();
+        assert!(matches!(err, OddsError::InvalidInput(_)));
+    }
+}
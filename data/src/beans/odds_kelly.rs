@@ -0,0 +1,44 @@
+use bigdecimal::BigDecimal;
+use serde::{Deserialize, Serialize};
+
+use crate::MarginRemoval;
+
+/// market average fair probabilities of the 1X2 outcomes
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FairProbability {
+    pub home_win: BigDecimal,
+    pub draw: BigDecimal,
+    pub away_win: BigDecimal,
+}
+
+/// kelly indices of one 1X2 price triple against the market average fair probabilities
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KellyIndex {
+    pub home_win: BigDecimal,
+    pub draw: BigDecimal,
+    pub away_win: BigDecimal,
+    /// population variance of the three kelly indices
+    pub variance: BigDecimal,
+}
+
+/// kelly indices of one bookmaker's odds at the opening and closing prices
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OddsKelly {
+    pub odds_id: i32,
+    pub match_id: i32,
+    pub bookmaker_id: i32,
+    pub bookmaker_name: String,
+    pub start: Option<KellyIndex>,
+    pub end: Option<KellyIndex>,
+}
+
+/// kelly indices of every bookmaker of a match
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KellyReport {
+    pub match_id: i32,
+    /// method used to get the fair probabilities of each bookmaker
+    pub margin_removal: MarginRemoval,
+    pub start_probability: Option<FairProbability>,
+    pub end_probability: Option<FairProbability>,
+    pub odds: Vec<OddsKelly>,
+}
@@ -0,0 +1,43 @@
+use bigdecimal::BigDecimal;
+use serde::{Deserialize, Serialize};
+
+use crate::Outcome;
+
+/// direction of a price between the opening and closing odds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PriceMovement {
+    /// the price shortened, money came in on the outcome
+    Steam,
+    /// the price lengthened, the market moved away from the outcome
+    Drift,
+    Unchanged,
+}
+
+/// movement of the price of one outcome
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OutcomeMovement {
+    pub outcome: Outcome,
+    pub start: BigDecimal,
+    pub end: BigDecimal,
+    /// closing price minus opening price
+    pub change: BigDecimal,
+    /// change relative to the opening price, in percent
+    pub change_percent: BigDecimal,
+    /// closing implied probability minus opening implied probability
+    pub probability_shift: BigDecimal,
+    pub movement: PriceMovement,
+}
+
+/// opening to closing movement of one bookmaker's odds
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OddsMovement {
+    pub odds_id: i32,
+    pub match_id: i32,
+    pub bookmaker_id: i32,
+    pub bookmaker_name: String,
+    /// outcomes with both an opening and a closing price
+    pub outcomes: Vec<OutcomeMovement>,
+    /// the outcome whose implied probability rose the most
+    pub moved_towards: Option<Outcome>,
+}
@@ -0,0 +1,25 @@
+use bigdecimal::BigDecimal;
+use serde::{Deserialize, Serialize};
+
+/// implied probabilities of one 1X2 price triple
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImpliedProbability {
+    pub home_win: BigDecimal,
+    pub draw: BigDecimal,
+    pub away_win: BigDecimal,
+    /// sum of the implied probabilities minus one, the bookmaker margin
+    pub overround: BigDecimal,
+    /// share of the stakes paid back to bettors
+    pub payout_rate: BigDecimal,
+}
+
+/// implied probabilities of one bookmaker's odds at the opening and closing prices
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OddsProbability {
+    pub odds_id: i32,
+    pub match_id: i32,
+    pub bookmaker_id: i32,
+    pub bookmaker_name: String,
+    pub start: Option<ImpliedProbability>,
+    pub end: Option<ImpliedProbability>,
+}
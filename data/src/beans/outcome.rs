@@ -0,0 +1,120 @@
+use serde::{Deserialize, Serialize};
+
+/// 1X2 outcome of a match
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Outcome {
+    HomeWin,
+    Draw,
+    AwayWin,
+}
+
+impl Outcome {
+    /// all outcomes in home/draw/away order
+    pub const ALL: [Outcome; 3] = [Outcome::HomeWin, Outcome::Draw, Outcome::AwayWin];
+
+    /// parse a game result in the 3/1/0 notation of the match form, a score such as 2:1
+    /// is not a result, it is stored as the goals of the match
+    pub fn from_game_result(result: &str) -> Option<Outcome> {
+        match result.trim() {
+            "3" => Some(Outcome::HomeWin),
+            "1" => Some(Outcome::Draw),
+            "0" => Some(Outcome::AwayWin),
+            _ => None,
+        }
+    }
+
+    /// the outcome in the 3/1/0 notation of the match form
+    pub fn game_result(self) -> &'static str {
+        match self {
+            Outcome::HomeWin => "3",
+            Outcome::Draw => "1",
+            Outcome::AwayWin => "0",
+        }
+    }
+
+    /// outcome of a final score
+    pub fn from_goals(home: u32, away: u32) -> Outcome {
+        match home.cmp(&away) {
+            std::cmp::Ordering::Greater => Outcome::HomeWin,
+            std::cmp::Ordering::Equal => Outcome::Draw,
+            std::cmp::Ordering::Less => Outcome::AwayWin,
+        }
+    }
+}
+
+/// number of matches per 1X2 outcome
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OutcomeDistribution {
+    pub home_win: u32,
+    pub draw: u32,
+    pub away_win: u32,
+    /// matches without a recognisable result
+    pub unknown: u32,
+}
+
+impl OutcomeDistribution {
+    pub fn add(&mut self, outcome: Option<Outcome>) {
+        match outcome {
+            Some(Outcome::HomeWin) => self.home_win += 1,
+            Some(Outcome::Draw) => self.draw += 1,
+            Some(Outcome::AwayWin) => self.away_win += 1,
+            None => self.unknown += 1,
+        }
+    }
+
+    /// number of matches with a recognisable result
+    pub fn settled(&self) -> u32 {
+        self.home_win + self.draw + self.away_win
+    }
+}
+
+impl FromIterator<Option<Outcome>> for OutcomeDistribution {
+    fn from_iter<I: IntoIterator<Item = Option<Outcome>>>(iter: I) -> Self {
+        let mut distribution = OutcomeDistribution::default();
+        for outcome in iter {
+            distribution.add(outcome);
+        }
+        distribution
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn game_result_should_be_parsed() {
+        assert_eq!(Outcome::from_game_result("3"), Some(Outcome::HomeWin));
+        assert_eq!(Outcome::from_game_result("1"), Some(Outcome::Draw));
+        assert_eq!(Outcome::from_game_result("0"), Some(Outcome::AwayWin));
+        assert_eq!(Outcome::from_game_result(" 0 "), Some(Outcome::AwayWin));
+        assert_eq!(Outcome::from_game_result("2:1"), None);
+        assert_eq!(Outcome::from_game_result(""), None);
+        assert_eq!(Outcome::from_game_result("2"), None);
+        for outcome in Outcome::ALL {
+            assert_eq!(
+                Outcome::from_game_result(outcome.game_result()),
+                Some(outcome)
+            );
+        }
+    }
+
+    #[test]
+    fn distribution_should_count_outcomes() {
+        let distribution: OutcomeDistribution = ["3", "2:1", "1", "", "0", "3"]
+            .into_iter()
+            .map(Outcome::from_game_result)
+            .collect();
+        assert_eq!(
+            distribution,
+            OutcomeDistribution {
+                home_win: 2,
+                draw: 1,
+                away_win: 1,
+                unknown: 2,
+            }
+        );
+        assert_eq!(distribution.settled(), 4);
+    }
+}
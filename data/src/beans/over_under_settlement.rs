@@ -0,0 +1,25 @@
+use bigdecimal::BigDecimal;
+use serde::{Deserialize, Serialize};
+
+use crate::Settlement;
+
+/// settlement of both sides of one total goals line
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LineSettlement {
+    pub line: BigDecimal,
+    pub over: Settlement,
+    pub under: Settlement,
+}
+
+/// over/under odds of one bookmaker settled against the final score
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OverUnderSettlement {
+    pub odds_id: i32,
+    pub match_id: i32,
+    pub bookmaker_id: i32,
+    pub bookmaker_name: String,
+    /// None until the final score is recorded
+    pub total_goals: Option<i32>,
+    pub start: Option<LineSettlement>,
+    pub end: Option<LineSettlement>,
+}
@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+
+/// one page of a query with the number of rows meeting the query on every page
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub total: i64,
+    /// cursor of the following page, None on the last page
+    pub next_cursor: Option<String>,
+    /// cursor of the preceding page, None on the first page
+    pub prev_cursor: Option<String>,
+}
@@ -0,0 +1,121 @@
+use bigdecimal::BigDecimal;
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+use crate::Outcome;
+
+/// what the predictions of a scorecard are grouped by
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScorecardGroup {
+    #[default]
+    League,
+    /// the game year of the match
+    Season,
+    /// the note of the match, where the reading of the odds is written down
+    Note,
+    /// the month of the game time
+    Month,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ScorecardQuery {
+    /// None for all leagues
+    pub league_id: Option<i32>,
+    #[serde(default)]
+    pub group_by: ScorecardGroup,
+    /// number of the latest settled predictions the rolling hit rate is taken over,
+    /// 20 when not given
+    pub window: Option<u32>,
+}
+
+/// settled predictions counted by predicted and actual outcome
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConfusionMatrix {
+    /// `counts[predicted][actual]`, both in home/draw/away order
+    pub counts: [[u32; 3]; 3],
+}
+
+impl ConfusionMatrix {
+    pub fn add(&mut self, predicted: Outcome, actual: Outcome) {
+        self.counts[index(predicted)][index(actual)] += 1;
+    }
+
+    pub fn count(&self, predicted: Outcome, actual: Outcome) -> u32 {
+        self.counts[index(predicted)][index(actual)]
+    }
+
+    /// number of predictions that hit the result
+    pub fn hits(&self) -> u32 {
+        (0..3).map(|i| self.counts[i][i]).sum()
+    }
+
+    /// number of settled predictions
+    pub fn total(&self) -> u32 {
+        self.counts.iter().flatten().sum()
+    }
+}
+
+fn index(outcome: Outcome) -> usize {
+    match outcome {
+        Outcome::HomeWin => 0,
+        Outcome::Draw => 1,
+        Outcome::AwayWin => 2,
+    }
+}
+
+/// how often the predictions of a group hit the result
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PredictionAccuracy {
+    /// the league name, season, note or yyyy-mm month of the group,
+    /// None for the matches without one and for the whole scorecard
+    pub key: Option<String>,
+    pub settled: u32,
+    pub hits: u32,
+    /// share of the settled predictions that hit
+    pub hit_rate: BigDecimal,
+    /// predictions of matches without a result yet
+    pub pending: u32,
+    pub confusion: ConfusionMatrix,
+}
+
+/// the hit rates after a settled prediction
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccuracyPoint {
+    pub match_id: i32,
+    pub game_time: Option<NaiveDateTime>,
+    pub hit: bool,
+    /// hit rate of the predictions in the window ending with this one
+    pub rolling_hit_rate: BigDecimal,
+    /// hit rate of every prediction up to this one
+    pub cumulative_hit_rate: BigDecimal,
+}
+
+/// predictions of the matches compared with their results
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PredictionScorecard {
+    pub group_by: ScorecardGroup,
+    pub window: u32,
+    /// all predictions together
+    pub overall: PredictionAccuracy,
+    pub groups: Vec<PredictionAccuracy>,
+    /// one point per settled prediction in game time order
+    pub trend: Vec<AccuracyPoint>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn confusion_matrix_should_count_hits() {
+        let mut confusion = ConfusionMatrix::default();
+        confusion.add(Outcome::HomeWin, Outcome::HomeWin);
+        confusion.add(Outcome::HomeWin, Outcome::Draw);
+        confusion.add(Outcome::AwayWin, Outcome::AwayWin);
+        assert_eq!(confusion.count(Outcome::HomeWin, Outcome::Draw), 1);
+        assert_eq!(confusion.counts[1], [0, 0, 0]);
+        assert_eq!(confusion.hits(), 2);
+        assert_eq!(confusion.total(), 3);
+    }
+}
@@ -0,0 +1,39 @@
+use bigdecimal::BigDecimal;
+use serde::{Deserialize, Serialize};
+
+use crate::{Matches, Odds, OutcomeDistribution};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimilarOddsQuery {
+    /// the match whose odds are looked up
+    pub match_id: i32,
+    pub bookmaker_id: i32,
+    /// maximum difference of every opening price, None to ignore the opening prices
+    pub start_tolerance: Option<BigDecimal>,
+    /// maximum difference of every closing price, None to ignore the closing prices
+    pub end_tolerance: Option<BigDecimal>,
+    /// 0 for all leagues
+    pub league_id: i32,
+    pub game_year: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimilarMatch {
+    pub matches: Matches,
+    pub odds: Odds,
+}
+
+impl SimilarMatch {
+    pub fn new(matches: Matches, odds: Odds) -> Self {
+        Self { matches, odds }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimilarOdds {
+    /// the odds being looked up
+    pub odds: Odds,
+    /// earlier matches with similar odds, latest first
+    pub matches: Vec<SimilarMatch>,
+    pub distribution: OutcomeDistribution,
+}
@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 
 use crate::OddsError;
@@ -5,13 +7,31 @@ use crate::OddsError;
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Config {
     pub db: DbConfig,
+    #[serde(default)]
+    pub football_data: FootballDataConfig,
+}
+
+/// database used to persist the odds data
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DbBackend {
+    #[default]
+    Postgres,
+    /// a local database file, `dbname` is the path of the file
+    Sqlite,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct DbConfig {
+    #[serde(default)]
+    pub backend: DbBackend,
+    #[serde(default)]
     pub host: String,
+    #[serde(default)]
     pub port: u16,
+    #[serde(default)]
     pub user: String,
+    #[serde(default)]
     pub password: String,
     pub dbname: String,
     #[serde(default = "default_max_connections")]
@@ -22,6 +42,45 @@ fn default_max_connections() -> u32 {
     5
 }
 
+/// how the columns of a football-data.co.uk season csv map to the stored names
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FootballDataConfig {
+    /// bookmaker of a column prefix, e.g. `B365` for the `B365H`, `B365D`, `B365A` opening
+    /// and the `B365CH`, `B365CD`, `B365CA` closing prices, other prefixes are not imported
+    #[serde(default = "default_football_data_bookmakers")]
+    pub bookmakers: BTreeMap<String, String>,
+    /// league of a `Div` code, a code without league is used as the league name
+    #[serde(default)]
+    pub leagues: BTreeMap<String, String>,
+    /// team of a `HomeTeam` or `AwayTeam` name, a name without team is used as it is
+    #[serde(default)]
+    pub teams: BTreeMap<String, String>,
+}
+
+impl Default for FootballDataConfig {
+    fn default() -> Self {
+        Self {
+            bookmakers: default_football_data_bookmakers(),
+            leagues: BTreeMap::new(),
+            teams: BTreeMap::new(),
+        }
+    }
+}
+
+fn default_football_data_bookmakers() -> BTreeMap<String, String> {
+    [
+        ("B365", "Bet365"),
+        ("BW", "Bet&Win"),
+        ("IW", "Interwetten"),
+        ("PS", "Pinnacle"),
+        ("VC", "VC Bet"),
+        ("WH", "William Hill"),
+    ]
+    .into_iter()
+    .map(|(prefix, bookmaker)| (prefix.to_string(), bookmaker.to_string()))
+    .collect()
+}
+
 impl Config {
     pub fn from_file(path: std::fs::File) -> Result<Self, OddsError> {
         // let content = fs::read_to_string(path).map_err(|_| OddsError::ConfigReadError)?;
@@ -43,7 +102,11 @@ impl DbConfig {
     }
 
     pub fn url(&self) -> String {
-        format!("{}/{}", self.server_url(), self.dbname)
+        match self.backend {
+            DbBackend::Postgres => format!("{}/{}", self.server_url(), self.dbname),
+            // create the database file when it does not exist
+            DbBackend::Sqlite => format!("sqlite://{}?mode=rwc", self.dbname),
+        }
     }
 }
 
@@ -59,6 +122,7 @@ mod tests {
             config,
             Config {
                 db: DbConfig {
+                    backend: DbBackend::Postgres,
                     host: "localhost".to_string(),
                     port: 5432,
                     user: "postgres".to_string(),
@@ -66,7 +130,30 @@ mod tests {
                     dbname: "european_odds".to_string(),
                     max_connections: 5,
                 },
+                football_data: FootballDataConfig::default(),
             }
         );
     }
+
+    #[test]
+    fn sqlite_config_should_be_loaded() {
+        let config: Config =
+            serde_yaml::from_str("db:\n  backend: sqlite\n  dbname: odds.db\n").unwrap();
+        assert_eq!(config.db.backend, DbBackend::Sqlite);
+        assert_eq!(config.db.url(), "sqlite://odds.db?mode=rwc");
+    }
+
+    #[test]
+    fn football_data_config_should_be_loaded() {
+        let config: Config = serde_yaml::from_str(
+            "db:\n  dbname: odds\nfootball_data:\n  bookmakers:\n    PS: 平博\n  leagues:\n    E0: 英超\n",
+        )
+        .unwrap();
+        let football_data = config.football_data;
+        assert_eq!(football_data.bookmakers.len(), 1);
+        assert_eq!(football_data.bookmakers["PS"], "平博");
+        assert_eq!(football_data.leagues["E0"], "英超");
+        assert!(football_data.teams.is_empty());
+        assert_eq!(FootballDataConfig::default().bookmakers["B365"], "Bet365");
+    }
 }
@@ -0,0 +1,32 @@
+use bigdecimal::BigDecimal;
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+/// asian handicap odds of one bookmaker, the line is given from the home team's side,
+/// e.g. -0.75 means the home team gives three quarters of a goal
+#[derive(Debug, Clone, Builder, FromRow, Serialize, Deserialize)]
+pub struct AsianHandicapOdds {
+    #[builder(default)]
+    pub id: i32,
+    #[builder(default)]
+    pub match_id: i32,
+    #[builder(default)]
+    pub bookmaker_id: i32,
+    #[builder(default)]
+    pub bookmaker_name: String,
+    #[builder(default)]
+    pub line_start: BigDecimal,
+    #[builder(default)]
+    pub home_price_start: BigDecimal,
+    #[builder(default)]
+    pub away_price_start: BigDecimal,
+    #[builder(default)]
+    pub line_end: BigDecimal,
+    #[builder(default)]
+    pub home_price_end: BigDecimal,
+    #[builder(default)]
+    pub away_price_end: BigDecimal,
+    #[builder(default, setter(into, strip_option))]
+    pub note: Option<String>,
+}
@@ -1,9 +1,9 @@
 use chrono::NaiveDateTime;
 use derive_builder::Builder;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 
-#[derive(Debug, Builder, FromRow, Serialize)]
+#[derive(Debug, Clone, Builder, FromRow, Serialize, Deserialize)]
 pub struct League {
     #[builder(default)]
     pub id: i32,
@@ -3,6 +3,8 @@ use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 
+use crate::Outcome;
+
 #[derive(Debug, Clone, Builder, FromRow, Serialize, Deserialize)]
 pub struct Matches {
     #[builder(default)]
@@ -29,6 +31,14 @@ pub struct Matches {
     pub game_result: Option<String>,
     #[builder(default, setter(into, strip_option))]
     pub predict_game_result: Option<String>,
+    #[builder(default, setter(strip_option))]
+    pub home_goals: Option<i32>,
+    #[builder(default, setter(strip_option))]
+    pub away_goals: Option<i32>,
+    #[builder(default, setter(strip_option))]
+    pub half_home_goals: Option<i32>,
+    #[builder(default, setter(strip_option))]
+    pub half_away_goals: Option<i32>,
     #[builder(default, setter(into, strip_option))]
     pub history_note: Option<String>,
     #[builder(default, setter(into, strip_option))]
@@ -38,3 +48,121 @@ pub struct Matches {
     #[builder(default)]
     pub updated_at: NaiveDateTime,
 }
+
+impl Matches {
+    /// the full-time goals, read from a game result recorded as a score such as 2:1
+    /// when the match has none, which is how the goals are stored on write
+    pub fn full_time_goals(&self) -> (Option<i32>, Option<i32>) {
+        match (
+            self.home_goals,
+            self.away_goals,
+            self.game_result.as_deref(),
+        ) {
+            (None, None, Some(result)) => score(result)
+                .map(|(home, away)| (Some(home), Some(away)))
+                .unwrap_or_default(),
+            (home, away, _) => (home, away),
+        }
+    }
+
+    /// full-time 1X2 outcome, derived from the goals or from the game result
+    /// recorded in the 3/1/0 notation
+    pub fn outcome(&self) -> Option<Outcome> {
+        match (self.home_goals, self.away_goals) {
+            (Some(home), Some(away)) => Some(Outcome::from_goals(
+                u32::try_from(home).ok()?,
+                u32::try_from(away).ok()?,
+            )),
+            _ => self
+                .game_result
+                .as_deref()
+                .and_then(Outcome::from_game_result),
+        }
+    }
+
+    /// half-time 1X2 outcome
+    pub fn half_time_outcome(&self) -> Option<Outcome> {
+        match (self.half_home_goals, self.half_away_goals) {
+            (Some(home), Some(away)) => Some(Outcome::from_goals(
+                u32::try_from(home).ok()?,
+                u32::try_from(away).ok()?,
+            )),
+            _ => None,
+        }
+    }
+
+    /// predicted 1X2 outcome
+    pub fn predicted_outcome(&self) -> Option<Outcome> {
+        self.predict_game_result
+            .as_deref()
+            .and_then(Outcome::from_game_result)
+    }
+}
+
+/// the goals of a home:away score such as 2:1 or 2-1
+fn score(result: &str) -> Option<(i32, i32)> {
+    let (home, away) = result.trim().split_once([':', '-'])?;
+    let home: u16 = home.trim().parse().ok()?;
+    let away: u16 = away.trim().parse().ok()?;
+    Some((home.into(), away.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn outcome_should_be_derived_from_goals() {
+        let matches = MatchesBuilder::default()
+            .game_result("0")
+            .home_goals(2)
+            .away_goals(1)
+            .half_home_goals(0)
+            .half_away_goals(0)
+            .predict_game_result("1")
+            .build()
+            .unwrap();
+        assert_eq!(matches.outcome(), Some(Outcome::HomeWin));
+        assert_eq!(matches.half_time_outcome(), Some(Outcome::Draw));
+        assert_eq!(matches.predicted_outcome(), Some(Outcome::Draw));
+    }
+
+    #[test]
+    fn outcome_should_fall_back_to_game_result() {
+        let matches = MatchesBuilder::default().game_result("0").build().unwrap();
+        assert_eq!(matches.outcome(), Some(Outcome::AwayWin));
+        assert_eq!(matches.half_time_outcome(), None);
+        assert_eq!(matches.predicted_outcome(), None);
+    }
+
+    #[test]
+    fn score_should_be_read_as_goals() {
+        let matches = MatchesBuilder::default()
+            .game_result(" 2 : 1 ")
+            .build()
+            .unwrap();
+        assert_eq!(matches.full_time_goals(), (Some(2), Some(1)));
+        assert_eq!(matches.outcome(), None);
+        let matches = MatchesBuilder::default()
+            .game_result("1-1")
+            .home_goals(0)
+            .build()
+            .unwrap();
+        assert_eq!(matches.full_time_goals(), (Some(0), None));
+        let matches = MatchesBuilder::default().game_result("3").build().unwrap();
+        assert_eq!(matches.full_time_goals(), (None, None));
+    }
+
+    #[test]
+    fn negative_goals_should_have_no_outcome() {
+        let matches = MatchesBuilder::default()
+            .home_goals(-1)
+            .away_goals(0)
+            .half_home_goals(0)
+            .half_away_goals(-2)
+            .build()
+            .unwrap();
+        assert_eq!(matches.outcome(), None);
+        assert_eq!(matches.half_time_outcome(), None);
+    }
+}
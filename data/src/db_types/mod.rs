@@ -1,11 +1,17 @@
+mod asian_handicap_odds;
 mod bookmaker;
 mod league;
 mod matches;
 mod odds;
+mod odds_snapshot;
+mod over_under_odds;
 mod team;
 
+pub use asian_handicap_odds::*;
 pub use bookmaker::*;
 pub use league::*;
 pub use matches::*;
 pub use odds::*;
+pub use odds_snapshot::*;
+pub use over_under_odds::*;
 pub use team::*;
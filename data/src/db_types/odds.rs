@@ -1,9 +1,11 @@
-use bigdecimal::BigDecimal;
+use bigdecimal::{BigDecimal, ToPrimitive};
 use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use std::str::FromStr;
 
+use crate::Outcome;
+
 #[derive(Debug, Clone, Builder, FromRow, Serialize, Deserialize)]
 pub struct Odds {
     #[builder(default)]
@@ -30,6 +32,40 @@ pub struct Odds {
     pub note: Option<String>,
 }
 
+impl Odds {
+    /// opening home/draw/away prices
+    pub fn start_prices(&self) -> [f64; 3] {
+        to_prices([&self.home_win_start, &self.draw_start, &self.away_win_start])
+    }
+
+    /// closing home/draw/away prices
+    pub fn end_prices(&self) -> [f64; 3] {
+        to_prices([&self.home_win_end, &self.draw_end, &self.away_win_end])
+    }
+
+    /// opening price of the outcome
+    pub fn start_price(&self, outcome: Outcome) -> &BigDecimal {
+        match outcome {
+            Outcome::HomeWin => &self.home_win_start,
+            Outcome::Draw => &self.draw_start,
+            Outcome::AwayWin => &self.away_win_start,
+        }
+    }
+
+    /// closing price of the outcome
+    pub fn end_price(&self, outcome: Outcome) -> &BigDecimal {
+        match outcome {
+            Outcome::HomeWin => &self.home_win_end,
+            Outcome::Draw => &self.draw_end,
+            Outcome::AwayWin => &self.away_win_end,
+        }
+    }
+}
+
+fn to_prices(prices: [&BigDecimal; 3]) -> [f64; 3] {
+    prices.map(|price| price.to_f64().unwrap_or_default())
+}
+
 impl OddsBuilder {
     pub fn home_win_start_setter(&mut self, value: &str) -> Self {
         self.home_win_start = Some(BigDecimal::from_str(value).unwrap());
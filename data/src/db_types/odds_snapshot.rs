@@ -0,0 +1,28 @@
+use bigdecimal::BigDecimal;
+use chrono::NaiveDateTime;
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+/// 1X2 prices of one bookmaker recorded at one moment before kickoff
+#[derive(Debug, Clone, Default, Builder, FromRow, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OddsSnapshot {
+    #[builder(default)]
+    pub id: i32,
+    #[builder(default)]
+    pub match_id: i32,
+    #[builder(default)]
+    pub bookmaker_id: i32,
+    #[builder(default)]
+    pub home_win: BigDecimal,
+    #[builder(default)]
+    pub draw: BigDecimal,
+    #[builder(default)]
+    pub away_win: BigDecimal,
+    /// recorded at the time of saving when left as the default value
+    #[builder(default)]
+    pub recorded_at: NaiveDateTime,
+    #[builder(default)]
+    pub created_at: NaiveDateTime,
+}
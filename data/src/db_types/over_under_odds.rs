@@ -0,0 +1,31 @@
+use bigdecimal::BigDecimal;
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+/// over/under total goals odds of one bookmaker
+#[derive(Debug, Clone, Builder, FromRow, Serialize, Deserialize)]
+pub struct OverUnderOdds {
+    #[builder(default)]
+    pub id: i32,
+    #[builder(default)]
+    pub match_id: i32,
+    #[builder(default)]
+    pub bookmaker_id: i32,
+    #[builder(default)]
+    pub bookmaker_name: String,
+    #[builder(default)]
+    pub line_start: BigDecimal,
+    #[builder(default)]
+    pub over_price_start: BigDecimal,
+    #[builder(default)]
+    pub under_price_start: BigDecimal,
+    #[builder(default)]
+    pub line_end: BigDecimal,
+    #[builder(default)]
+    pub over_price_end: BigDecimal,
+    #[builder(default)]
+    pub under_price_end: BigDecimal,
+    #[builder(default, setter(into, strip_option))]
+    pub note: Option<String>,
+}
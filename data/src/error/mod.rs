@@ -1,7 +1,9 @@
-use serde::Serialize;
+use serde::{ser::SerializeStruct, Serialize, Serializer};
 use thiserror::Error;
 
-#[derive(Debug, Error, Serialize)]
+use crate::FieldError;
+
+#[derive(Debug, Error)]
 pub enum OddsError {
     // #[error("Eframe error")]
     // EframeError(eframe::EframeError),
@@ -11,24 +13,205 @@ pub enum OddsError {
     #[error("Failed to parse configuration file")]
     ConfigParseError,
 
-    #[error("Database error")]
+    #[error("Database error: {0}")]
     DbError(String),
 
+    #[error("Failed to connect to the database: {0}")]
+    ConnectionError(String),
+
     #[error("No result found by the given condition")]
     NotFound,
 
+    #[error("Row already exists by unique constraint {0}")]
+    UniqueViolation(String),
+
+    #[error("Referenced row does not exist by foreign key constraint {0}")]
+    ForeignKeyViolation(String),
+
+    #[error("Cursor is not valid: {0}")]
+    InvalidCursor(String),
+
+    #[error("Archive is not valid: {0}")]
+    InvalidArchive(String),
+
+    #[error("Input is not valid: {0}")]
+    InvalidInput(String),
+
+    #[error("Fields are not valid: {}", join_fields(.0))]
+    Validation(Vec<FieldError>),
+
+    #[error("Row already exists: {0}")]
+    Conflict(String),
+
+    #[error("Failed to access file: {0}")]
+    FileError(String),
+
     #[error("No Error has founded")]
     None,
 }
 
+impl OddsError {
+    /// the stable code of the kind of error for callers to match on
+    pub fn code(&self) -> &'static str {
+        match self {
+            OddsError::ConfigReadError => "config_read",
+            OddsError::ConfigParseError => "config_parse",
+            OddsError::DbError(_) => "database",
+            OddsError::ConnectionError(_) => "connection",
+            OddsError::NotFound => "not_found",
+            OddsError::UniqueViolation(_) => "unique_violation",
+            OddsError::ForeignKeyViolation(_) => "foreign_key_violation",
+            OddsError::InvalidCursor(_) => "invalid_cursor",
+            OddsError::InvalidArchive(_) => "invalid_archive",
+            OddsError::InvalidInput(_) => "invalid_input",
+            OddsError::Validation(_) => "validation",
+            OddsError::Conflict(_) => "conflict",
+            OddsError::FileError(_) => "file",
+            OddsError::None => "none",
+        }
+    }
+
+    /// the constraint, value or message the error is about
+    pub fn detail(&self) -> Option<&str> {
+        match self {
+            OddsError::DbError(detail)
+            | OddsError::ConnectionError(detail)
+            | OddsError::UniqueViolation(detail)
+            | OddsError::ForeignKeyViolation(detail)
+            | OddsError::InvalidCursor(detail)
+            | OddsError::InvalidArchive(detail)
+            | OddsError::InvalidInput(detail)
+            | OddsError::Conflict(detail)
+            | OddsError::FileError(detail) => Some(detail),
+            OddsError::ConfigReadError
+            | OddsError::ConfigParseError
+            | OddsError::NotFound
+            | OddsError::Validation(_)
+            | OddsError::None => None,
+        }
+    }
+
+    /// the field errors of a row failing validation
+    pub fn fields(&self) -> &[FieldError] {
+        match self {
+            OddsError::Validation(fields) => fields,
+            _ => &[],
+        }
+    }
+}
+
+fn join_fields(fields: &[FieldError]) -> String {
+    fields
+        .iter()
+        .map(FieldError::to_string)
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// serialized as `{ "code": ..., "message": ..., "detail": ..., "fields": [...] }`
+impl Serialize for OddsError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("OddsError", 4)?;
+        state.serialize_field("code", self.code())?;
+        state.serialize_field("message", &self.to_string())?;
+        state.serialize_field("detail", &self.detail())?;
+        state.serialize_field("fields", self.fields())?;
+        state.end()
+    }
+}
+
 impl From<sqlx::Error> for OddsError {
     fn from(err: sqlx::Error) -> Self {
-        OddsError::DbError(err.to_string())
+        match err {
+            sqlx::Error::RowNotFound => OddsError::NotFound,
+            sqlx::Error::Database(db) => {
+                let constraint = db
+                    .constraint()
+                    .map(str::to_string)
+                    .unwrap_or_else(|| sqlite_constraint(db.message()));
+                match db.code().as_deref() {
+                    // postgres sqlstates and sqlite extended result codes
+                    Some("23505" | "2067" | "1555") => OddsError::UniqueViolation(constraint),
+                    Some("23503" | "787") => OddsError::ForeignKeyViolation(constraint),
+                    Some("23514" | "23502" | "22001" | "275" | "1299") => {
+                        OddsError::InvalidInput(db.message().to_string())
+                    }
+                    // sqlite reports a foreign key failure of a returning statement
+                    // with the primary result code
+                    _ if db.message() == "FOREIGN KEY constraint failed" => {
+                        OddsError::ForeignKeyViolation(constraint)
+                    }
+                    _ => OddsError::DbError(db.message().to_string()),
+                }
+            }
+            sqlx::Error::Io(_)
+            | sqlx::Error::Tls(_)
+            | sqlx::Error::PoolTimedOut
+            | sqlx::Error::PoolClosed
+            | sqlx::Error::WorkerCrashed => OddsError::ConnectionError(err.to_string()),
+            err => OddsError::DbError(err.to_string()),
+        }
     }
 }
 
+/// sqlite names the columns of a violated constraint in the message only,
+/// e.g. `UNIQUE constraint failed: bookmakers.name`
+fn sqlite_constraint(message: &str) -> String {
+    message
+        .split_once("failed: ")
+        .map_or(message, |(_, columns)| columns)
+        .to_string()
+}
+
 // impl From<eframe::EframeError> for OddsError {
 //     fn from(err: eframe::EframeError) -> Self {
 //         OddsError::DbError(sqlx::Error::Database(Box::new(err)))
 //     }
 // }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn error_should_be_serialized_with_code() {
+        let json =
+            serde_json::to_value(OddsError::UniqueViolation("bookmakers_name_key".into())).unwrap();
+        assert_eq!(json["code"], "unique_violation");
+        assert_eq!(json["detail"], "bookmakers_name_key");
+        assert_eq!(
+            json["message"],
+            "Row already exists by unique constraint bookmakers_name_key"
+        );
+        let json = serde_json::to_value(OddsError::NotFound).unwrap();
+        assert_eq!(json["code"], "not_found");
+        assert!(json["detail"].is_null());
+        let json = serde_json::to_value(OddsError::Validation(vec![
+            FieldError::new("away_team_id", "must differ from home_team_id"),
+            FieldError::new("draw_end", "must be at least 1.01"),
+        ]))
+        .unwrap();
+        assert_eq!(json["code"], "validation");
+        assert_eq!(json["fields"][1]["field"], "draw_end");
+        assert_eq!(
+            json["message"],
+            "Fields are not valid: away_team_id must differ from home_team_id; draw_end must be at least 1.01"
+        );
+    }
+
+    #[test]
+    fn sqlx_error_should_be_mapped() {
+        assert!(matches!(
+            OddsError::from(sqlx::Error::RowNotFound),
+            OddsError::NotFound
+        ));
+        assert!(matches!(
+            OddsError::from(sqlx::Error::PoolTimedOut),
+            OddsError::ConnectionError(_)
+        ));
+        assert_eq!(
+            sqlite_constraint("UNIQUE constraint failed: bookmakers.name"),
+            "bookmakers.name"
+        );
+    }
+}
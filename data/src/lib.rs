@@ -2,8 +2,14 @@ mod beans;
 mod config;
 mod db_types;
 mod error;
+mod line;
+mod margin;
+mod validation;
 
 pub use beans::*;
 pub use config::*;
 pub use db_types::*;
 pub use error::*;
+pub use line::*;
+pub use margin::*;
+pub use validation::*;
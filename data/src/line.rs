@@ -0,0 +1,99 @@
+use bigdecimal::BigDecimal;
+use serde::{Deserialize, Serialize};
+
+/// result of a bet on a line, quarter lines are settled as two half stakes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Settlement {
+    Win,
+    HalfWin,
+    Push,
+    HalfLoss,
+    Loss,
+}
+
+impl Settlement {
+    /// result of the bet on the other side of the line
+    pub fn opposite(&self) -> Settlement {
+        match self {
+            Settlement::Win => Settlement::Loss,
+            Settlement::HalfWin => Settlement::HalfLoss,
+            Settlement::Push => Settlement::Push,
+            Settlement::HalfLoss => Settlement::HalfWin,
+            Settlement::Loss => Settlement::Win,
+        }
+    }
+}
+
+/// whether the line is a whole, half or quarter line, e.g. -1, 0.5 or 2.25
+pub fn is_quarter_line(line: &BigDecimal) -> bool {
+    (line * BigDecimal::from(4)).is_integer()
+}
+
+/// settle a bet that wins when the score is above the line,
+/// e.g. the over side of a total goals line
+pub fn settle_line(score: &BigDecimal, line: &BigDecimal) -> Settlement {
+    let quarter = BigDecimal::from(1) / BigDecimal::from(4);
+    let half_lines = if (line * BigDecimal::from(2)).is_integer() {
+        [line.clone(), line.clone()]
+    } else {
+        [line - &quarter, line + &quarter]
+    };
+    // each half stake scores 2 for a win, 1 for a push and 0 for a loss
+    let points: u8 = half_lines
+        .iter()
+        .map(|half_line| match score.cmp(half_line) {
+            std::cmp::Ordering::Greater => 2,
+            std::cmp::Ordering::Equal => 1,
+            std::cmp::Ordering::Less => 0,
+        })
+        .sum();
+    match points {
+        4 => Settlement::Win,
+        3 => Settlement::HalfWin,
+        2 => Settlement::Push,
+        1 => Settlement::HalfLoss,
+        _ => Settlement::Loss,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bigdecimal::Zero;
+
+    use super::*;
+
+    #[test]
+    fn quarter_lines_should_be_accepted() {
+        for line in ["0", "-0.25", "0.5", "-0.75", "1", "2.25", "-3.5"] {
+            assert!(is_quarter_line(&line.parse().unwrap()), "{line}");
+        }
+        assert!(is_quarter_line(&BigDecimal::zero()));
+    }
+
+    #[test]
+    fn other_lines_should_be_rejected() {
+        for line in ["0.1", "-0.3", "1.125", "2.6"] {
+            assert!(!is_quarter_line(&line.parse().unwrap()), "{line}");
+        }
+    }
+
+    #[test]
+    fn lines_should_be_settled() {
+        let cases = [
+            ("3", "2.5", Settlement::Win),
+            ("2", "2.5", Settlement::Loss),
+            ("2", "2", Settlement::Push),
+            ("3", "2", Settlement::Win),
+            ("2", "2.25", Settlement::HalfLoss),
+            ("3", "2.25", Settlement::Win),
+            ("3", "2.75", Settlement::HalfWin),
+            ("2", "2.75", Settlement::Loss),
+        ];
+        for (score, line, settlement) in cases {
+            let result = settle_line(&score.parse().unwrap(), &line.parse().unwrap());
+            assert_eq!(result, settlement, "{score} over {line}");
+            assert_eq!(result.opposite().opposite(), settlement);
+        }
+    }
+}
@@ -0,0 +1,192 @@
+use serde::{Deserialize, Serialize};
+
+const MAX_ITERATIONS: usize = 1000;
+const CONVERGENCE_THRESHOLD: f64 = 1e-12;
+
+/// method used to remove the bookmaker margin from a 1X2 price triple
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MarginRemoval {
+    /// scale the implied probabilities so that they sum to one
+    #[default]
+    Multiplicative,
+    /// subtract an equal share of the overround from every implied probability
+    Additive,
+    /// raise the implied probabilities to the power that makes them sum to one
+    Power,
+    /// Shin's model of a market with a share of insider money
+    Shin,
+}
+
+/// whether every price of a home/draw/away triple is a finite price above 1.0
+pub fn valid_prices(prices: &[f64; 3]) -> bool {
+    prices.iter().all(|price| price.is_finite() && *price > 1.0)
+}
+
+impl MarginRemoval {
+    /// turn the home/draw/away prices into fair probabilities, returns None when
+    /// any price is not valid or the method leaves an outcome without a positive probability
+    pub fn fair_probabilities(&self, prices: [f64; 3]) -> Option<[f64; 3]> {
+        if !valid_prices(&prices) {
+            return None;
+        }
+        let implied = prices.map(|price| 1.0 / price);
+        let book: f64 = implied.iter().sum();
+
+        let probabilities = match self {
+            MarginRemoval::Multiplicative => implied.map(|p| p / book),
+            MarginRemoval::Additive => {
+                let share = (book - 1.0) / implied.len() as f64;
+                implied.map(|p| p - share)
+            }
+            MarginRemoval::Power => {
+                let k = power_exponent(&implied);
+                implied.map(|p| p.powf(k))
+            }
+            MarginRemoval::Shin => {
+                let z = shin_insider_share(&implied, book);
+                implied.map(|p| {
+                    ((z * z + 4.0 * (1.0 - z) * p * p / book).sqrt() - z) / (2.0 * (1.0 - z))
+                })
+            }
+        };
+        // the additive share of a high margin can exceed the implied probability of a long shot
+        probabilities
+            .iter()
+            .all(|p| *p > 0.0)
+            .then_some(probabilities)
+    }
+}
+
+/// solve k for sum(p ^ k) = 1 with Newton's method
+fn power_exponent(implied: &[f64; 3]) -> f64 {
+    let mut k = 1.0;
+    for _ in 0..MAX_ITERATIONS {
+        let value: f64 = implied.iter().map(|p| p.powf(k)).sum::<f64>() - 1.0;
+        let slope: f64 = implied.iter().map(|p| p.powf(k) * p.ln()).sum();
+        let next = k - value / slope;
+        if (next - k).abs() < CONVERGENCE_THRESHOLD {
+            return next;
+        }
+        k = next;
+    }
+    k
+}
+
+/// solve the share of insider money z of Shin's model by fixed-point iteration
+fn shin_insider_share(implied: &[f64; 3], book: f64) -> f64 {
+    let n = implied.len() as f64;
+    let mut z = 0.0;
+    for _ in 0..MAX_ITERATIONS {
+        let next = (implied
+            .iter()
+            .map(|p| (z * z + 4.0 * (1.0 - z) * p * p / book).sqrt())
+            .sum::<f64>()
+            - 2.0)
+            / (n - 2.0);
+        if (next - z).abs() < CONVERGENCE_THRESHOLD {
+            return next;
+        }
+        z = next;
+    }
+    z
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_probabilities(actual: [f64; 3], expected: [f64; 3]) {
+        for (a, e) in actual.iter().zip(expected.iter()) {
+            assert!((a - e).abs() < 1e-9, "{actual:?} != {expected:?}");
+        }
+    }
+
+    #[test]
+    fn fair_book_should_be_unchanged_by_every_method() {
+        for method in [
+            MarginRemoval::Multiplicative,
+            MarginRemoval::Additive,
+            MarginRemoval::Power,
+            MarginRemoval::Shin,
+        ] {
+            let probabilities = method.fair_probabilities([2.0, 4.0, 4.0]).unwrap();
+            assert_probabilities(probabilities, [0.5, 0.25, 0.25]);
+        }
+    }
+
+    #[test]
+    fn multiplicative_should_normalize_implied_probabilities() {
+        // implied probabilities 0.5, 0.3, 0.3 with a book of 1.1
+        let probabilities = MarginRemoval::Multiplicative
+            .fair_probabilities([2.0, 1.0 / 0.3, 1.0 / 0.3])
+            .unwrap();
+        assert_probabilities(probabilities, [0.5 / 1.1, 0.3 / 1.1, 0.3 / 1.1]);
+    }
+
+    #[test]
+    fn additive_should_subtract_equal_share_of_overround() {
+        // implied probabilities 0.5, 0.3, 0.3 with an overround of 0.1
+        let probabilities = MarginRemoval::Additive
+            .fair_probabilities([2.0, 1.0 / 0.3, 1.0 / 0.3])
+            .unwrap();
+        assert_probabilities(
+            probabilities,
+            [0.5 - 0.1 / 3.0, 0.3 - 0.1 / 3.0, 0.3 - 0.1 / 3.0],
+        );
+    }
+
+    #[test]
+    fn additive_should_reject_negative_probabilities() {
+        // implied probabilities 0.8, 0.4, 0.02 with an overround of 0.22
+        let probabilities = MarginRemoval::Additive.fair_probabilities([1.25, 2.5, 50.0]);
+        assert!(probabilities.is_none());
+        assert!(MarginRemoval::Multiplicative
+            .fair_probabilities([1.25, 2.5, 50.0])
+            .is_some());
+    }
+
+    #[test]
+    fn power_should_use_a_common_exponent() {
+        // 0.6 ^ 2 + 0.6 ^ 2 + 0.529150... ^ 2 = 1
+        let third = 0.28_f64.sqrt();
+        let probabilities = MarginRemoval::Power
+            .fair_probabilities([1.0 / 0.6, 1.0 / 0.6, 1.0 / third])
+            .unwrap();
+        assert_probabilities(probabilities, [0.36, 0.36, 0.28]);
+    }
+
+    #[test]
+    fn shin_should_match_published_example() {
+        // worked example of the `shin` python package (github.com/mberk/shin)
+        let probabilities = MarginRemoval::Shin
+            .fair_probabilities([2.6, 2.4, 4.3])
+            .unwrap();
+        assert_probabilities(
+            probabilities,
+            [0.37299406033208965, 0.4047794109200184, 0.2222265287474275],
+        );
+    }
+
+    #[test]
+    fn shin_should_shorten_favourites_less_than_multiplicative() {
+        let prices = [1.30, 5.50, 9.00];
+        let shin = MarginRemoval::Shin.fair_probabilities(prices).unwrap();
+        let multiplicative = MarginRemoval::Multiplicative
+            .fair_probabilities(prices)
+            .unwrap();
+        assert!(shin[0] > multiplicative[0]);
+        assert!(shin[2] < multiplicative[2]);
+        assert!((shin.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn invalid_prices_should_have_no_fair_probabilities() {
+        assert!(MarginRemoval::Shin
+            .fair_probabilities([0.0, 3.4, 4.2])
+            .is_none());
+        assert!(MarginRemoval::Power
+            .fair_probabilities([1.0, 3.4, 4.2])
+            .is_none());
+    }
+}
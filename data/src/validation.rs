@@ -0,0 +1,472 @@
+use std::fmt;
+
+use bigdecimal::{BigDecimal, Zero};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    is_quarter_line, AsianHandicapOdds, BookMaker, League, Matches, Odds, OddsError, OddsSnapshot,
+    Outcome, OverUnderOdds, Team,
+};
+
+/// lowest decimal price of an outcome, in hundredths
+const MIN_PRICE_CENTS: i64 = 101;
+
+/// a rule broken by a field of a row
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldError {
+    pub field: String,
+    pub message: String,
+}
+
+impl FieldError {
+    pub fn new(field: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            field: field.into(),
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for FieldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.field, self.message)
+    }
+}
+
+/// a row checked before it is written
+pub trait Validate {
+    /// every rule broken by the fields of the row
+    fn field_errors(&self) -> Vec<FieldError>;
+
+    /// the field errors of the row all at once
+    fn validate(&self) -> Result<(), OddsError> {
+        into_result(self.field_errors())
+    }
+}
+
+fn into_result(errors: Vec<FieldError>) -> Result<(), OddsError> {
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(OddsError::Validation(errors))
+    }
+}
+
+/// the field errors of a row being checked
+#[derive(Debug, Default)]
+struct Fields {
+    errors: Vec<FieldError>,
+}
+
+impl Fields {
+    fn check(&mut self, valid: bool, field: &str, message: impl FnOnce() -> String) -> &mut Self {
+        if !valid {
+            self.errors.push(FieldError::new(field, message()));
+        }
+        self
+    }
+
+    fn id(&mut self, field: &str, id: i32) -> &mut Self {
+        self.check(id > 0, field, || "must be set".into())
+    }
+
+    fn required<T>(&mut self, field: &str, value: Option<T>) -> &mut Self {
+        self.check(value.is_some(), field, || "must be set".into())
+    }
+
+    fn name(&mut self, field: &str, name: &str, max: usize) -> &mut Self {
+        self.check(!name.trim().is_empty(), field, || {
+            "must not be empty".into()
+        })
+        .length(field, Some(name), max)
+    }
+
+    fn length(&mut self, field: &str, value: Option<&str>, max: usize) -> &mut Self {
+        let valid = value.is_none_or(|value| value.chars().count() <= max);
+        self.check(valid, field, || format!("must be at most {max} characters"))
+    }
+
+    /// a price of the form, which is 0.00 when it is left blank
+    fn price(&mut self, field: &str, price: &BigDecimal) -> &mut Self {
+        if price.is_zero() {
+            return self.check(false, field, || "must be set".into());
+        }
+        let min = BigDecimal::new(MIN_PRICE_CENTS.into(), 2);
+        self.check(*price >= min, field, || format!("must be at least {min}"))
+    }
+
+    /// a closing price, which stays 0.00 until the market closes
+    fn closing_price(&mut self, field: &str, price: &BigDecimal) -> &mut Self {
+        if price.is_zero() {
+            self
+        } else {
+            self.price(field, price)
+        }
+    }
+
+    fn line(&mut self, field: &str, line: &BigDecimal) -> &mut Self {
+        self.check(is_quarter_line(line), field, || {
+            "must be a whole, half or quarter line".into()
+        })
+    }
+
+    /// a line of total goals, which is never negative
+    fn total_line(&mut self, field: &str, line: &BigDecimal) -> &mut Self {
+        self.check(*line >= BigDecimal::zero(), field, || {
+            "must not be negative".into()
+        })
+        .line(field, line)
+    }
+
+    fn goals(
+        &mut self,
+        field: &str,
+        goals: Option<i32>,
+        full_time: Option<(&str, i32)>,
+    ) -> &mut Self {
+        self.check(goals.is_none_or(|goals| goals >= 0), field, || {
+            "must not be negative".into()
+        });
+        match (goals, full_time) {
+            (Some(goals), Some((full_field, full))) => self.check(goals <= full, field, || {
+                format!("must not exceed {full_field}")
+            }),
+            _ => self,
+        }
+    }
+
+    fn finish(&mut self) -> Vec<FieldError> {
+        std::mem::take(&mut self.errors)
+    }
+}
+
+impl Validate for BookMaker {
+    fn field_errors(&self) -> Vec<FieldError> {
+        Fields::default()
+            .name("name", &self.name, 30)
+            .length("url", self.url.as_deref(), 100)
+            .length("note", self.note.as_deref(), 50)
+            .finish()
+    }
+}
+
+impl Validate for League {
+    fn field_errors(&self) -> Vec<FieldError> {
+        Fields::default()
+            .name("name", &self.name, 30)
+            .length("note", self.note.as_deref(), 50)
+            .finish()
+    }
+}
+
+impl Validate for Team {
+    fn field_errors(&self) -> Vec<FieldError> {
+        Fields::default()
+            .id("league_id", self.league_id)
+            .name("name", &self.name, 30)
+            .length("note", self.note.as_deref(), 50)
+            .finish()
+    }
+}
+
+impl Validate for Matches {
+    fn field_errors(&self) -> Vec<FieldError> {
+        let distinct = self.home_team_id <= 0 || self.home_team_id != self.away_team_id;
+        // the goals decide the outcome, a game result telling another one is a typo
+        let recorded = self
+            .game_result
+            .as_deref()
+            .and_then(Outcome::from_game_result);
+        let agrees = recorded
+            .zip(self.outcome())
+            .is_none_or(|(recorded, outcome)| recorded == outcome);
+        Fields::default()
+            .id("league_id", self.league_id)
+            .id("home_team_id", self.home_team_id)
+            .id("away_team_id", self.away_team_id)
+            .check(distinct, "away_team_id", || {
+                "must differ from home_team_id".into()
+            })
+            .required("game_time", self.game_time)
+            .required("league_name", self.league_name.as_ref())
+            .required("home_team", self.home_team.as_ref())
+            .required("away_team", self.away_team.as_ref())
+            .length("league_name", self.league_name.as_deref(), 30)
+            .length("home_team", self.home_team.as_deref(), 15)
+            .length("away_team", self.away_team.as_deref(), 15)
+            .length("game_year", self.game_year.as_deref(), 5)
+            .length("game_round", self.game_round.as_deref(), 5)
+            .length("game_result", self.game_result.as_deref(), 5)
+            .check(agrees, "game_result", || "must agree with the goals".into())
+            .length(
+                "predict_game_result",
+                self.predict_game_result.as_deref(),
+                5,
+            )
+            .length("history_note", self.history_note.as_deref(), 255)
+            .length("note", self.note.as_deref(), 100)
+            .goals("home_goals", self.home_goals, None)
+            .goals("away_goals", self.away_goals, None)
+            .goals(
+                "half_home_goals",
+                self.half_home_goals,
+                self.home_goals.map(|goals| ("home_goals", goals)),
+            )
+            .goals(
+                "half_away_goals",
+                self.half_away_goals,
+                self.away_goals.map(|goals| ("away_goals", goals)),
+            )
+            .finish()
+    }
+}
+
+impl Matches {
+    /// the field errors of the match with those of its stored teams, a team missing
+    /// from `teams` or of another league than the match is an error
+    pub fn validate_with(&self, teams: &[Team]) -> Result<(), OddsError> {
+        let mut fields = Fields {
+            errors: self.field_errors(),
+        };
+        for (field, id) in [
+            ("home_team_id", self.home_team_id),
+            ("away_team_id", self.away_team_id),
+        ] {
+            match teams.iter().find(|team| team.id == id) {
+                Some(team) => fields.check(team.league_id == self.league_id, field, || {
+                    format!("must be a team of league {}", self.league_id)
+                }),
+                None => fields.check(false, field, || "no such team".into()),
+            };
+        }
+        into_result(fields.finish())
+    }
+}
+
+impl Validate for Odds {
+    fn field_errors(&self) -> Vec<FieldError> {
+        Fields::default()
+            .id("bookmaker_id", self.bookmaker_id)
+            .name("bookmaker_name", &self.bookmaker_name, 30)
+            .price("home_win_start", &self.home_win_start)
+            .price("draw_start", &self.draw_start)
+            .price("away_win_start", &self.away_win_start)
+            .closing_price("home_win_end", &self.home_win_end)
+            .closing_price("draw_end", &self.draw_end)
+            .closing_price("away_win_end", &self.away_win_end)
+            .length("note", self.note.as_deref(), 50)
+            .finish()
+    }
+}
+
+impl Validate for OddsSnapshot {
+    fn field_errors(&self) -> Vec<FieldError> {
+        Fields::default()
+            .id("bookmaker_id", self.bookmaker_id)
+            .price("home_win", &self.home_win)
+            .price("draw", &self.draw)
+            .price("away_win", &self.away_win)
+            .finish()
+    }
+}
+
+impl Validate for AsianHandicapOdds {
+    fn field_errors(&self) -> Vec<FieldError> {
+        Fields::default()
+            .id("bookmaker_id", self.bookmaker_id)
+            .name("bookmaker_name", &self.bookmaker_name, 30)
+            .line("line_start", &self.line_start)
+            .price("home_price_start", &self.home_price_start)
+            .price("away_price_start", &self.away_price_start)
+            .line("line_end", &self.line_end)
+            .closing_price("home_price_end", &self.home_price_end)
+            .closing_price("away_price_end", &self.away_price_end)
+            .length("note", self.note.as_deref(), 50)
+            .finish()
+    }
+}
+
+impl Validate for OverUnderOdds {
+    fn field_errors(&self) -> Vec<FieldError> {
+        Fields::default()
+            .id("bookmaker_id", self.bookmaker_id)
+            .name("bookmaker_name", &self.bookmaker_name, 30)
+            .total_line("line_start", &self.line_start)
+            .price("over_price_start", &self.over_price_start)
+            .price("under_price_start", &self.under_price_start)
+            .total_line("line_end", &self.line_end)
+            .closing_price("over_price_end", &self.over_price_end)
+            .closing_price("under_price_end", &self.under_price_end)
+            .length("note", self.note.as_deref(), 50)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        AsianHandicapOddsBuilder, MatchesBuilder, OddsBuilder, OverUnderOddsBuilder, TeamBuilder,
+    };
+
+    use super::*;
+
+    fn matches() -> MatchesBuilder {
+        MatchesBuilder::default()
+            .league_id(1)
+            .league_name("英超")
+            .home_team_id(1)
+            .home_team("阿森纳")
+            .away_team_id(2)
+            .away_team("利物浦")
+            .game_time(Default::default())
+            .to_owned()
+    }
+
+    fn fields(err: OddsError) -> Vec<String> {
+        match err {
+            OddsError::Validation(errors) => errors.into_iter().map(|err| err.field).collect(),
+            err => panic!("not a validation error: {err}"),
+        }
+    }
+
+    #[test]
+    fn valid_rows_should_pass() {
+        let odd = OddsBuilder::default()
+            .bookmaker_id(1)
+            .bookmaker_name("威廉希尔".into())
+            .home_win_start_setter("2.10")
+            .draw_start_setter("3.40")
+            .away_win_start_setter("3.30")
+            .home_win_end_setter("2.05")
+            .draw_end_setter("3.50")
+            .away_win_end_setter("1.01")
+            .build()
+            .unwrap();
+        assert!(odd.validate().is_ok());
+        assert!(matches().build().unwrap().validate().is_ok());
+    }
+
+    #[test]
+    fn every_field_error_should_be_returned() {
+        let mut matches = matches().away_team_id(1).build().unwrap();
+        matches.game_time = None;
+        matches.home_goals = Some(1);
+        matches.half_home_goals = Some(2);
+        matches.away_goals = Some(-1);
+        assert_eq!(
+            fields(matches.validate().unwrap_err()),
+            ["away_team_id", "game_time", "away_goals", "half_home_goals"]
+        );
+
+        let odd = OddsBuilder::default()
+            .bookmaker_id(1)
+            .bookmaker_name("威廉希尔".into())
+            .home_win_start_setter("1.00")
+            .draw_start_setter("3.40")
+            .away_win_start_setter("3.30")
+            .home_win_end_setter("2.05")
+            .draw_end_setter("3.50")
+            .build()
+            .unwrap();
+        assert_eq!(fields(odd.validate().unwrap_err()), ["home_win_start"]);
+
+        let team = TeamBuilder::default().name(" ").build().unwrap();
+        assert_eq!(fields(team.validate().unwrap_err()), ["league_id", "name"]);
+    }
+
+    #[test]
+    fn lines_should_be_quarter_lines() {
+        let handicap = |line_start: &str, line_end: &str| {
+            AsianHandicapOddsBuilder::default()
+                .bookmaker_id(1)
+                .bookmaker_name("威廉希尔".into())
+                .line_start(line_start.parse().unwrap())
+                .home_price_start("1.90".parse().unwrap())
+                .away_price_start("1.95".parse().unwrap())
+                .line_end(line_end.parse().unwrap())
+                .home_price_end("2.02".parse().unwrap())
+                .away_price_end("1.84".parse().unwrap())
+                .build()
+                .unwrap()
+        };
+        assert!(handicap("-0.25", "-0.5").validate().is_ok());
+        assert_eq!(
+            fields(handicap("-0.25", "-0.6").validate().unwrap_err()),
+            ["line_end"]
+        );
+
+        let total = OverUnderOddsBuilder::default()
+            .bookmaker_id(1)
+            .bookmaker_name("威廉希尔".into())
+            .line_start("-0.5".parse().unwrap())
+            .over_price_start("1.90".parse().unwrap())
+            .under_price_start("1.95".parse().unwrap())
+            .line_end("2.5".parse().unwrap())
+            .over_price_end("1.85".parse().unwrap())
+            .under_price_end("1.00".parse().unwrap())
+            .build()
+            .unwrap();
+        assert_eq!(
+            fields(total.validate().unwrap_err()),
+            ["line_start", "under_price_end"]
+        );
+    }
+
+    #[test]
+    fn closing_prices_should_be_optional() {
+        let odd = |away_win_start: &str, away_win_end: &str| {
+            OddsBuilder::default()
+                .bookmaker_id(1)
+                .bookmaker_name("威廉希尔".into())
+                .home_win_start_setter("2.10")
+                .draw_start_setter("3.40")
+                .away_win_start_setter(away_win_start)
+                .away_win_end_setter(away_win_end)
+                .build()
+                .unwrap()
+        };
+        assert!(odd("3.30", "0.00").validate().is_ok());
+        let err = odd("0.00", "1.00").validate().unwrap_err();
+        assert_eq!(
+            err.fields(),
+            [
+                FieldError::new("away_win_start", "must be set"),
+                FieldError::new("away_win_end", "must be at least 1.01"),
+            ]
+        );
+    }
+
+    #[test]
+    fn game_result_should_agree_with_goals() {
+        let scored = || matches().home_goals(2).away_goals(0).to_owned();
+        assert!(scored()
+            .game_result("3")
+            .build()
+            .unwrap()
+            .validate()
+            .is_ok());
+        assert!(scored().build().unwrap().validate().is_ok());
+        let err = scored().game_result("0").build().unwrap().validate();
+        assert_eq!(fields(err.unwrap_err()), ["game_result"]);
+    }
+
+    #[test]
+    fn missing_team_or_team_of_another_league_should_be_rejected() {
+        let matches = matches().build().unwrap();
+        let team = |id, league_id| {
+            TeamBuilder::default()
+                .id(id)
+                .league_id(league_id)
+                .name("阿森纳")
+                .build()
+                .unwrap()
+        };
+        assert!(matches.validate_with(&[team(1, 1), team(2, 1)]).is_ok());
+        let err = matches.validate_with(&[team(2, 1)]).unwrap_err();
+        assert_eq!(fields(err), ["home_team_id"]);
+        let err = matches
+            .validate_with(&[team(1, 1), team(2, 2)])
+            .unwrap_err();
+        assert_eq!(fields(err), ["away_team_id"]);
+    }
+}
@@ -0,0 +1,9 @@
+-- Add down migration script here
+DROP TABLE over_under_odds;
+DROP TABLE asian_handicap_odds;
+DROP TABLE odds_snapshots;
+DROP TABLE odds;
+DROP TABLE matches;
+DROP TABLE teams;
+DROP TABLE leagues;
+DROP TABLE bookmakers;
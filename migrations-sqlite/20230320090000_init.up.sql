@@ -0,0 +1,127 @@
+-- Add up migration script here
+-- the sqlite schema mirrors the euro schema of postgres, varchar lengths are
+-- checked explicitly and prices are stored as text to keep their exact decimal value
+CREATE TABLE bookmakers (
+    id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+    name VARCHAR(30) NOT NULL UNIQUE CHECK (length(name) <= 30),
+    url VARCHAR(100) DEFAULT NULL CHECK (length(url) <= 100),
+    note VARCHAR(50) DEFAULT NULL CHECK (length(note) <= 50),
+    created_at TIMESTAMP NOT NULL DEFAULT (strftime('%Y-%m-%d %H:%M:%f', 'now')),
+    updated_at TIMESTAMP NOT NULL DEFAULT (strftime('%Y-%m-%d %H:%M:%f', 'now'))
+);
+
+CREATE TABLE leagues (
+    id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+    name VARCHAR(30) NOT NULL UNIQUE CHECK (length(name) <= 30),
+    note VARCHAR(50) DEFAULT NULL CHECK (length(note) <= 50),
+    created_at TIMESTAMP NOT NULL DEFAULT (strftime('%Y-%m-%d %H:%M:%f', 'now')),
+    updated_at TIMESTAMP NOT NULL DEFAULT (strftime('%Y-%m-%d %H:%M:%f', 'now'))
+);
+
+CREATE TABLE teams (
+    id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+    league_id INTEGER NOT NULL,
+    name VARCHAR(30) NOT NULL UNIQUE CHECK (length(name) <= 30),
+    note VARCHAR(50) DEFAULT NULL CHECK (length(note) <= 50),
+    created_at TIMESTAMP NOT NULL DEFAULT (strftime('%Y-%m-%d %H:%M:%f', 'now')),
+    updated_at TIMESTAMP NOT NULL DEFAULT (strftime('%Y-%m-%d %H:%M:%f', 'now'))
+);
+
+CREATE INDEX teams_league_id_idx ON teams (league_id);
+
+CREATE TABLE matches (
+    id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+    league_id INTEGER NOT NULL,
+    league_name VARCHAR(30) NOT NULL CHECK (length(league_name) <= 30),
+    home_team_id INTEGER NOT NULL,
+    home_team VARCHAR(15) NOT NULL CHECK (length(home_team) <= 15),
+    away_team_id INTEGER NOT NULL,
+    away_team VARCHAR(15) NOT NULL CHECK (length(away_team) <= 15),
+    game_time TIMESTAMP NOT NULL,
+    game_year VARCHAR(5) DEFAULT NULL CHECK (length(game_year) <= 5),
+    game_round VARCHAR(5) DEFAULT NULL CHECK (length(game_round) <= 5),
+    game_result VARCHAR(5) DEFAULT NULL CHECK (length(game_result) <= 5),
+    predict_game_result VARCHAR(5) DEFAULT NULL CHECK (length(predict_game_result) <= 5),
+    home_goals INTEGER DEFAULT NULL CHECK (home_goals >= 0),
+    away_goals INTEGER DEFAULT NULL CHECK (away_goals >= 0),
+    half_home_goals INTEGER DEFAULT NULL CHECK (half_home_goals >= 0),
+    half_away_goals INTEGER DEFAULT NULL CHECK (half_away_goals >= 0),
+    history_note VARCHAR(255) DEFAULT NULL CHECK (length(history_note) <= 255),
+    note VARCHAR(100) DEFAULT NULL CHECK (length(note) <= 100),
+    created_at TIMESTAMP NOT NULL DEFAULT (strftime('%Y-%m-%d %H:%M:%f', 'now')),
+    updated_at TIMESTAMP NOT NULL DEFAULT (strftime('%Y-%m-%d %H:%M:%f', 'now'))
+);
+
+CREATE INDEX matches_league_id_idx ON matches (league_id);
+
+CREATE TABLE odds (
+    id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+    match_id INTEGER NOT NULL REFERENCES matches(id) ON DELETE CASCADE ON UPDATE CASCADE,
+    bookmaker_id INTEGER NOT NULL,
+    bookmaker_name VARCHAR(30) NOT NULL CHECK (length(bookmaker_name) <= 30),
+    home_win_start TEXT NOT NULL DEFAULT '0.00',
+    draw_start TEXT NOT NULL DEFAULT '0.00',
+    away_win_start TEXT NOT NULL DEFAULT '0.00',
+    home_win_end TEXT NOT NULL DEFAULT '0.00',
+    draw_end TEXT NOT NULL DEFAULT '0.00',
+    away_win_end TEXT NOT NULL DEFAULT '0.00',
+    note VARCHAR(50) DEFAULT NULL CHECK (length(note) <= 50)
+);
+
+CREATE INDEX odds_match_id_idx ON odds (match_id);
+CREATE INDEX odds_bookmaker_id_idx ON odds (bookmaker_id);
+
+CREATE TABLE odds_snapshots (
+    id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+    match_id INTEGER NOT NULL REFERENCES matches(id) ON DELETE CASCADE ON UPDATE CASCADE,
+    bookmaker_id INTEGER NOT NULL,
+    home_win TEXT NOT NULL,
+    draw TEXT NOT NULL,
+    away_win TEXT NOT NULL,
+    recorded_at TIMESTAMP NOT NULL DEFAULT (strftime('%Y-%m-%d %H:%M:%f', 'now')),
+    created_at TIMESTAMP NOT NULL DEFAULT (strftime('%Y-%m-%d %H:%M:%f', 'now'))
+);
+
+CREATE INDEX odds_snapshots_match_id_bookmaker_id_idx ON odds_snapshots (match_id, bookmaker_id, recorded_at);
+
+CREATE TABLE asian_handicap_odds (
+    id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+    match_id INTEGER NOT NULL REFERENCES matches(id) ON DELETE CASCADE ON UPDATE CASCADE,
+    bookmaker_id INTEGER NOT NULL,
+    bookmaker_name VARCHAR(30) NOT NULL CHECK (length(bookmaker_name) <= 30),
+    line_start TEXT NOT NULL DEFAULT '0.00',
+    home_price_start TEXT NOT NULL DEFAULT '0.00',
+    away_price_start TEXT NOT NULL DEFAULT '0.00',
+    line_end TEXT NOT NULL DEFAULT '0.00',
+    home_price_end TEXT NOT NULL DEFAULT '0.00',
+    away_price_end TEXT NOT NULL DEFAULT '0.00',
+    note VARCHAR(50) DEFAULT NULL CHECK (length(note) <= 50),
+    -- quarter lines times four are whole numbers, which are exact as reals
+    CONSTRAINT asian_handicap_odds_line_check CHECK (
+        CAST(line_start AS REAL) * 4 = ROUND(CAST(line_start AS REAL) * 4)
+        AND CAST(line_end AS REAL) * 4 = ROUND(CAST(line_end AS REAL) * 4)
+    )
+);
+
+CREATE INDEX asian_handicap_odds_match_id_idx ON asian_handicap_odds (match_id);
+
+CREATE TABLE over_under_odds (
+    id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+    match_id INTEGER NOT NULL REFERENCES matches(id) ON DELETE CASCADE ON UPDATE CASCADE,
+    bookmaker_id INTEGER NOT NULL,
+    bookmaker_name VARCHAR(30) NOT NULL CHECK (length(bookmaker_name) <= 30),
+    line_start TEXT NOT NULL DEFAULT '0.00',
+    over_price_start TEXT NOT NULL DEFAULT '0.00',
+    under_price_start TEXT NOT NULL DEFAULT '0.00',
+    line_end TEXT NOT NULL DEFAULT '0.00',
+    over_price_end TEXT NOT NULL DEFAULT '0.00',
+    under_price_end TEXT NOT NULL DEFAULT '0.00',
+    note VARCHAR(50) DEFAULT NULL CHECK (length(note) <= 50),
+    CONSTRAINT over_under_odds_line_check CHECK (
+        CAST(line_start AS REAL) >= 0 AND CAST(line_end AS REAL) >= 0
+        AND CAST(line_start AS REAL) * 4 = ROUND(CAST(line_start AS REAL) * 4)
+        AND CAST(line_end AS REAL) * 4 = ROUND(CAST(line_end AS REAL) * 4)
+    )
+);
+
+CREATE INDEX over_under_odds_match_id_idx ON over_under_odds (match_id);
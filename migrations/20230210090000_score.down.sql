@@ -0,0 +1,7 @@
+-- Add down migration script here
+ALTER TABLE euro.matches
+    DROP CONSTRAINT matches_goals_check,
+    DROP home_goals,
+    DROP away_goals,
+    DROP half_home_goals,
+    DROP half_away_goals;
@@ -0,0 +1,16 @@
+-- Add up migration script here
+ALTER TABLE euro.matches
+    ADD home_goals INTEGER DEFAULT NULL,
+    ADD away_goals INTEGER DEFAULT NULL,
+    ADD half_home_goals INTEGER DEFAULT NULL,
+    ADD half_away_goals INTEGER DEFAULT NULL,
+    ADD CONSTRAINT matches_goals_check CHECK (
+        home_goals >= 0 AND away_goals >= 0 AND half_home_goals >= 0 AND half_away_goals >= 0
+    );
+
+-- parse the results recorded as a score such as 2:1 or 2-1,
+-- results recorded in the 3/1/0 notation have no score and are kept as they are
+UPDATE euro.matches SET
+    home_goals = (regexp_match(game_result, '^\s*(\d+)\s*[:-]\s*(\d+)\s*$'))[1]::INTEGER,
+    away_goals = (regexp_match(game_result, '^\s*(\d+)\s*[:-]\s*(\d+)\s*$'))[2]::INTEGER
+WHERE game_result ~ '^\s*\d+\s*[:-]\s*\d+\s*$';
@@ -0,0 +1,16 @@
+-- Add up migration script here
+CREATE TABLE euro.odds_snapshots (
+    id SERIAL NOT NULL,
+    match_id INTEGER NOT NULL,
+    bookmaker_id INTEGER NOT NULL,
+    home_win NUMERIC(5,2) NOT NULL,
+    draw NUMERIC(5,2) NOT NULL,
+    away_win NUMERIC(5,2) NOT NULL,
+    recorded_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT odds_snapshots_pkey PRIMARY KEY (id),
+    CONSTRAINT odds_snapshots_match_id_fkey FOREIGN KEY (match_id) REFERENCES euro.matches(id) ON DELETE CASCADE ON UPDATE CASCADE
+);
+
+CREATE INDEX odds_snapshots_match_id_bookmaker_id_idx ON euro.odds_snapshots (match_id, bookmaker_id, recorded_at);
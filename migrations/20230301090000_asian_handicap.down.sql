@@ -0,0 +1,2 @@
+-- Add down migration script here
+DROP TABLE euro.asian_handicap_odds CASCADE;
@@ -0,0 +1,20 @@
+-- Add up migration script here
+CREATE TABLE euro.asian_handicap_odds (
+    id SERIAL NOT NULL,
+    match_id INTEGER NOT NULL,
+    bookmaker_id INTEGER NOT NULL,
+    bookmaker_name VARCHAR(30) NOT NULL,
+    line_start NUMERIC(4,2) DEFAULT 0.0,
+    home_price_start NUMERIC(5,2) DEFAULT 0.0,
+    away_price_start NUMERIC(5,2) DEFAULT 0.0,
+    line_end NUMERIC(4,2) DEFAULT 0.0,
+    home_price_end NUMERIC(5,2) DEFAULT 0.0,
+    away_price_end NUMERIC(5,2) DEFAULT 0.0,
+    note VARCHAR(50) DEFAULT NULL,
+
+    CONSTRAINT asian_handicap_odds_pkey PRIMARY KEY (id),
+    CONSTRAINT asian_handicap_odds_match_id_fkey FOREIGN KEY (match_id) REFERENCES euro.matches(id) ON DELETE CASCADE ON UPDATE CASCADE,
+    CONSTRAINT asian_handicap_odds_line_check CHECK (MOD(line_start * 4, 1) = 0 AND MOD(line_end * 4, 1) = 0)
+);
+
+CREATE INDEX asian_handicap_odds_match_id_idx ON euro.asian_handicap_odds (match_id);
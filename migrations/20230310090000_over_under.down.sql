@@ -0,0 +1,2 @@
+-- Add down migration script here
+DROP TABLE euro.over_under_odds CASCADE;
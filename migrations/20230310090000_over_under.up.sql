@@ -0,0 +1,21 @@
+-- Add up migration script here
+CREATE TABLE euro.over_under_odds (
+    id SERIAL NOT NULL,
+    match_id INTEGER NOT NULL,
+    bookmaker_id INTEGER NOT NULL,
+    bookmaker_name VARCHAR(30) NOT NULL,
+    line_start NUMERIC(4,2) DEFAULT 0.0,
+    over_price_start NUMERIC(5,2) DEFAULT 0.0,
+    under_price_start NUMERIC(5,2) DEFAULT 0.0,
+    line_end NUMERIC(4,2) DEFAULT 0.0,
+    over_price_end NUMERIC(5,2) DEFAULT 0.0,
+    under_price_end NUMERIC(5,2) DEFAULT 0.0,
+    note VARCHAR(50) DEFAULT NULL,
+
+    CONSTRAINT over_under_odds_pkey PRIMARY KEY (id),
+    CONSTRAINT over_under_odds_match_id_fkey FOREIGN KEY (match_id) REFERENCES euro.matches(id) ON DELETE CASCADE ON UPDATE CASCADE,
+    CONSTRAINT over_under_odds_line_check CHECK (line_start >= 0 AND line_end >= 0
+        AND MOD(line_start * 4, 1) = 0 AND MOD(line_end * 4, 1) = 0)
+);
+
+CREATE INDEX over_under_odds_match_id_idx ON euro.over_under_odds (match_id);
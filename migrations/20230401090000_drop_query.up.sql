@@ -0,0 +1,3 @@
+-- Add up migration script here
+-- matches are filtered by the typed query of the odds crate, the dynamic sql function is dropped
+DROP FUNCTION IF EXISTS euro.query;
@@ -0,0 +1,486 @@
+use std::{fs::File, path::PathBuf};
+
+use chrono::{NaiveDate, NaiveDateTime};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use data::{
+    BookMaker, BookMakerBuilder, Config, League, LeagueBuilder, MatchFilter, MatchInfo, OddsError,
+    Outcome, PredictionFilter, ScorecardGroup, ScorecardQuery, Team, TeamBuilder,
+    DEFAULT_PAGE_SIZE,
+};
+use odds::OddsStore;
+use serde::Serialize;
+
+use crate::table;
+
+const TIME_FORMATS: [&str; 2] = ["%Y-%m-%d %H:%M:%S", "%Y-%m-%d %H:%M"];
+
+/// manage the odds data of the database in the config from a terminal
+#[derive(Debug, Parser)]
+#[command(name = "odds-cli", version)]
+pub struct Cli {
+    /// config file of the database, the config.yml of the app works as well
+    #[arg(short, long, env = "ODDS_CONFIG", default_value = "config.yml")]
+    pub config: PathBuf,
+    /// print json instead of tables
+    #[arg(long, global = true)]
+    pub json: bool,
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// list, create, update and delete bookmakers
+    #[command(subcommand)]
+    Bookmaker(BookmakerCommand),
+    /// list, create, update and delete leagues
+    #[command(subcommand)]
+    League(LeagueCommand),
+    /// list, create, update and delete teams
+    #[command(subcommand)]
+    Team(TeamCommand),
+    /// query matches and show a match with its odds
+    #[command(subcommand)]
+    Match(MatchCommand),
+}
+
+#[derive(Debug, Subcommand)]
+pub enum BookmakerCommand {
+    /// list all bookmakers
+    List,
+    /// create a bookmaker
+    Create {
+        name: String,
+        #[arg(long)]
+        url: Option<String>,
+        #[arg(long)]
+        note: Option<String>,
+    },
+    /// update the given fields of a bookmaker
+    Update {
+        id: i32,
+        #[arg(long)]
+        name: Option<String>,
+        #[arg(long)]
+        url: Option<String>,
+        #[arg(long)]
+        note: Option<String>,
+    },
+    /// delete a bookmaker
+    Delete { id: i32 },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum LeagueCommand {
+    /// list all leagues
+    List,
+    /// create a league
+    Create {
+        name: String,
+        #[arg(long)]
+        note: Option<String>,
+    },
+    /// update the given fields of a league
+    Update {
+        id: i32,
+        #[arg(long)]
+        name: Option<String>,
+        #[arg(long)]
+        note: Option<String>,
+    },
+    /// delete a league
+    Delete { id: i32 },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum TeamCommand {
+    /// list all teams or the teams of a league
+    List {
+        #[arg(long)]
+        league: Option<i32>,
+    },
+    /// create a team in a league
+    Create {
+        name: String,
+        #[arg(long)]
+        league: i32,
+        #[arg(long)]
+        note: Option<String>,
+    },
+    /// update the given fields of a team
+    Update {
+        id: i32,
+        #[arg(long)]
+        name: Option<String>,
+        #[arg(long)]
+        league: Option<i32>,
+        #[arg(long)]
+        note: Option<String>,
+    },
+    /// delete a team
+    Delete { id: i32 },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum MatchCommand {
+    /// list a page of the matches meeting the filter
+    List(MatchArgs),
+    /// show a match with its odds
+    Show { id: i32 },
+    /// score the predicted outcomes of the matches against their results
+    Scorecard(ScorecardArgs),
+}
+
+/// the conditions of `match list`, see `MatchFilter`
+#[derive(Debug, Args)]
+pub struct MatchArgs {
+    /// matches with odds of the bookmaker
+    #[arg(long)]
+    pub bookmaker: Option<i32>,
+    #[arg(long)]
+    pub league: Option<i32>,
+    /// matches of the team at home or away
+    #[arg(long)]
+    pub team: Option<i32>,
+    /// season of the matches, e.g. 22/23
+    #[arg(long)]
+    pub year: Option<String>,
+    #[arg(long)]
+    pub round: Option<String>,
+    /// matches kicking off at or after the time, e.g. 2023-04-01 or "2023-04-01 20:00"
+    #[arg(long, value_parser = parse_time)]
+    pub from: Option<NaiveDateTime>,
+    /// matches kicking off before the time
+    #[arg(long, value_parser = parse_time)]
+    pub to: Option<NaiveDateTime>,
+    /// matches with the full-time outcome
+    #[arg(long)]
+    pub result: Option<ResultArg>,
+    /// settled and predicted matches whose prediction hit or missed
+    #[arg(long)]
+    pub prediction: Option<PredictionArg>,
+    /// latest kickoff first
+    #[arg(long)]
+    pub desc: bool,
+    /// cursor of a page printed before
+    #[arg(long)]
+    pub cursor: Option<String>,
+    #[arg(long, default_value_t = DEFAULT_PAGE_SIZE)]
+    pub page_size: i32,
+}
+
+/// the conditions of `match scorecard`, see `ScorecardQuery`
+#[derive(Debug, Args)]
+pub struct ScorecardArgs {
+    #[arg(long)]
+    pub league: Option<i32>,
+    /// what the predictions are grouped by
+    #[arg(long, default_value = "league")]
+    pub by: GroupArg,
+    /// number of the latest settled predictions the rolling hit rate is taken over
+    #[arg(long)]
+    pub window: Option<u32>,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum GroupArg {
+    League,
+    Season,
+    Note,
+    Month,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum ResultArg {
+    HomeWin,
+    Draw,
+    AwayWin,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum PredictionArg {
+    Hit,
+    Miss,
+}
+
+impl Cli {
+    pub async fn run(self) -> Result<(), OddsError> {
+        let file = File::open(&self.config)
+            .map_err(|err| OddsError::FileError(format!("{}: {err}", self.config.display())))?;
+        let config = Config::from_file(file)?;
+        let store = odds::connect(&config.db).await?;
+        let output = Output { json: self.json };
+
+        match self.command {
+            Command::Bookmaker(command) => command.run(&store, output).await,
+            Command::League(command) => command.run(&store, output).await,
+            Command::Team(command) => command.run(&store, output).await,
+            Command::Match(command) => command.run(&store, output).await,
+        }
+    }
+}
+
+impl BookmakerCommand {
+    async fn run(self, store: &OddsStore, output: Output) -> Result<(), OddsError> {
+        let bookmakers = match self {
+            BookmakerCommand::List => store.list_bookermaker().await?,
+            BookmakerCommand::Create { name, url, note } => {
+                let bookmaker = BookMaker {
+                    url,
+                    note,
+                    ..BookMakerBuilder::default()
+                        .name(name)
+                        .build()
+                        .expect("the name is given")
+                };
+                store.create_bookermaker(bookmaker).await?
+            }
+            BookmakerCommand::Update {
+                id,
+                name,
+                url,
+                note,
+            } => {
+                let mut bookmaker = store.query_bookermaker_with_id(id).await?;
+                bookmaker.name = name.unwrap_or(bookmaker.name);
+                bookmaker.url = url.or(bookmaker.url);
+                bookmaker.note = note.or(bookmaker.note);
+                store.update_bookermaker(bookmaker).await?
+            }
+            BookmakerCommand::Delete { id } => store.delete_bookermaker(id).await?,
+        };
+        output.print(&bookmakers, |bookmakers| table::bookmakers(bookmakers))
+    }
+}
+
+impl LeagueCommand {
+    async fn run(self, store: &OddsStore, output: Output) -> Result<(), OddsError> {
+        let leagues = match self {
+            LeagueCommand::List => store.list_leagues().await?,
+            LeagueCommand::Create { name, note } => {
+                let league = League {
+                    note,
+                    ..LeagueBuilder::default()
+                        .name(name)
+                        .build()
+                        .expect("the name is given")
+                };
+                store.create_league(league).await?
+            }
+            LeagueCommand::Update { id, name, note } => {
+                let mut league = store.query_league_with_id(id).await?;
+                league.name = name.unwrap_or(league.name);
+                league.note = note.or(league.note);
+                store.update_league(league).await?
+            }
+            LeagueCommand::Delete { id } => store.delete_league(id).await?,
+        };
+        output.print(&leagues, |leagues| table::leagues(leagues))
+    }
+}
+
+impl TeamCommand {
+    async fn run(self, store: &OddsStore, output: Output) -> Result<(), OddsError> {
+        let teams = match self {
+            TeamCommand::List { league: None } => store.list_teams().await?,
+            TeamCommand::List { league: Some(id) } => store.query_teams_with_condition(id).await?,
+            TeamCommand::Create { name, league, note } => {
+                let team = Team {
+                    note,
+                    ..TeamBuilder::default()
+                        .name(name)
+                        .league_id(league)
+                        .build()
+                        .expect("the name is given")
+                };
+                store.create_team(team).await?
+            }
+            TeamCommand::Update {
+                id,
+                name,
+                league,
+                note,
+            } => {
+                let mut team = store.query_team_with_id(id).await?;
+                team.name = name.unwrap_or(team.name);
+                team.league_id = league.unwrap_or(team.league_id);
+                team.note = note.or(team.note);
+                store.update_team(team).await?
+            }
+            TeamCommand::Delete { id } => store.delete_team(id).await?,
+        };
+        output.print(&teams, |teams| table::teams(teams))
+    }
+}
+
+impl MatchCommand {
+    async fn run(self, store: &OddsStore, output: Output) -> Result<(), OddsError> {
+        match self {
+            MatchCommand::List(args) => {
+                let page = store.query_match_info(args.filter()).await?;
+                output.print(&page, table::match_page)
+            }
+            MatchCommand::Show { id } => {
+                let matches = store.query_match_info_by_id(id).await?;
+                let odds = store.query_odds_info_by_id(id).await?;
+                output.print(&MatchInfo::new(matches, odds), table::match_info)
+            }
+            MatchCommand::Scorecard(args) => {
+                let scorecard = store.query_prediction_scorecard(args.query()).await?;
+                output.print(&scorecard, table::scorecard)
+            }
+        }
+    }
+}
+
+impl MatchArgs {
+    pub fn filter(self) -> MatchFilter {
+        MatchFilter {
+            bookmaker_id: self.bookmaker,
+            league_id: self.league,
+            team_id: self.team,
+            game_year: self.year,
+            game_round: self.round,
+            start_time: self.from,
+            end_time: self.to,
+            result: self.result.map(|result| match result {
+                ResultArg::HomeWin => Outcome::HomeWin,
+                ResultArg::Draw => Outcome::Draw,
+                ResultArg::AwayWin => Outcome::AwayWin,
+            }),
+            prediction: self.prediction.map(|prediction| match prediction {
+                PredictionArg::Hit => PredictionFilter::Hit,
+                PredictionArg::Miss => PredictionFilter::Miss,
+            }),
+            is_desc: self.desc,
+            cursor: self.cursor,
+            page_size: self.page_size,
+        }
+    }
+}
+
+impl ScorecardArgs {
+    pub fn query(self) -> ScorecardQuery {
+        ScorecardQuery {
+            league_id: self.league,
+            group_by: match self.by {
+                GroupArg::League => ScorecardGroup::League,
+                GroupArg::Season => ScorecardGroup::Season,
+                GroupArg::Note => ScorecardGroup::Note,
+                GroupArg::Month => ScorecardGroup::Month,
+            },
+            window: self.window,
+        }
+    }
+}
+
+/// how the results of a command are printed
+#[derive(Debug, Clone, Copy)]
+struct Output {
+    json: bool,
+}
+
+impl Output {
+    fn print<T: Serialize>(
+        self,
+        value: &T,
+        table: impl FnOnce(&T) -> String,
+    ) -> Result<(), OddsError> {
+        if self.json {
+            let json = serde_json::to_string_pretty(value).expect("the results are serializable");
+            println!("{json}");
+        } else {
+            println!("{}", table(value));
+        }
+        Ok(())
+    }
+}
+
+/// a kickoff time or the start of a day
+fn parse_time(value: &str) -> Result<NaiveDateTime, String> {
+    TIME_FORMATS
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(value, format).ok())
+        .or_else(|| {
+            NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                .ok()
+                .map(|date| date.and_time(Default::default()))
+        })
+        .ok_or_else(|| format!("{value} is not a time like 2023-04-01 20:00"))
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::CommandFactory;
+
+    use super::*;
+
+    #[test]
+    fn cli_should_be_valid() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn match_list_should_be_parsed_to_filter() {
+        let cli = Cli::try_parse_from([
+            "odds-cli",
+            "match",
+            "list",
+            "--league",
+            "3",
+            "--from",
+            "2023-04-01",
+            "--to",
+            "2023-05-01 20:00",
+            "--result",
+            "away-win",
+            "--prediction",
+            "miss",
+            "--desc",
+            "--json",
+        ])
+        .unwrap();
+        assert!(cli.json);
+        let Command::Match(MatchCommand::List(args)) = cli.command else {
+            panic!("not a match list");
+        };
+        let filter = args.filter();
+        assert_eq!(filter.league_id, Some(3));
+        assert_eq!(
+            filter.start_time.unwrap().to_string(),
+            "2023-04-01 00:00:00"
+        );
+        assert_eq!(filter.end_time.unwrap().to_string(), "2023-05-01 20:00:00");
+        assert_eq!(filter.result, Some(Outcome::AwayWin));
+        assert_eq!(filter.prediction, Some(PredictionFilter::Miss));
+        assert!(filter.is_desc);
+        assert_eq!(filter.page_size, DEFAULT_PAGE_SIZE);
+    }
+
+    #[test]
+    fn match_scorecard_should_be_parsed_to_query() {
+        let cli = Cli::try_parse_from([
+            "odds-cli",
+            "match",
+            "scorecard",
+            "--by",
+            "month",
+            "--window",
+            "10",
+        ])
+        .unwrap();
+        let Command::Match(MatchCommand::Scorecard(args)) = cli.command else {
+            panic!("not a match scorecard");
+        };
+        let query = args.query();
+        assert_eq!(query.league_id, None);
+        assert_eq!(query.group_by, ScorecardGroup::Month);
+        assert_eq!(query.window, Some(10));
+    }
+
+    #[test]
+    fn bad_time_should_be_rejected() {
+        let result = Cli::try_parse_from(["odds-cli", "match", "list", "--from", "01/04/2023"]);
+        assert!(result.is_err());
+    }
+}
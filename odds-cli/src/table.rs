@@ -0,0 +1,296 @@
+use comfy_table::{presets::UTF8_FULL_CONDENSED, Cell, Table};
+use data::{
+    BookMaker, League, MatchInfo, Matches, Outcome, Page, PredictionAccuracy, PredictionScorecard,
+    ScorecardGroup, Team,
+};
+
+fn table(header: &[&str]) -> Table {
+    let mut table = Table::new();
+    table.load_preset(UTF8_FULL_CONDENSED).set_header(header);
+    table
+}
+
+fn text(value: &Option<String>) -> &str {
+    value.as_deref().unwrap_or_default()
+}
+
+/// home:away goals, empty before the goals are stored
+fn score(home: Option<i32>, away: Option<i32>) -> String {
+    match (home, away) {
+        (Some(home), Some(away)) => format!("{home}:{away}"),
+        _ => String::new(),
+    }
+}
+
+fn outcome(outcome: Option<Outcome>) -> &'static str {
+    match outcome {
+        Some(Outcome::HomeWin) => "home win",
+        Some(Outcome::Draw) => "draw",
+        Some(Outcome::AwayWin) => "away win",
+        None => "",
+    }
+}
+
+fn game_time(matches: &Matches) -> String {
+    matches
+        .game_time
+        .map(|time| time.format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_default()
+}
+
+pub fn bookmakers(bookmakers: &[BookMaker]) -> String {
+    let mut table = table(&["id", "name", "url", "note"]);
+    for bookmaker in bookmakers {
+        table.add_row([
+            Cell::new(bookmaker.id),
+            Cell::new(&bookmaker.name),
+            Cell::new(text(&bookmaker.url)),
+            Cell::new(text(&bookmaker.note)),
+        ]);
+    }
+    table.to_string()
+}
+
+pub fn leagues(leagues: &[League]) -> String {
+    let mut table = table(&["id", "name", "note"]);
+    for league in leagues {
+        table.add_row([
+            Cell::new(league.id),
+            Cell::new(&league.name),
+            Cell::new(text(&league.note)),
+        ]);
+    }
+    table.to_string()
+}
+
+pub fn teams(teams: &[Team]) -> String {
+    let mut table = table(&["id", "name", "league", "note"]);
+    for team in teams {
+        table.add_row([
+            Cell::new(team.id),
+            Cell::new(&team.name),
+            Cell::new(text(&team.league_name)),
+            Cell::new(text(&team.note)),
+        ]);
+    }
+    table.to_string()
+}
+
+/// the matches of a page with the total and the cursors of the neighbouring pages
+pub fn match_page(page: &Page<Matches>) -> String {
+    let mut table = table(&[
+        "id",
+        "game time",
+        "league",
+        "home",
+        "away",
+        "score",
+        "half",
+        "result",
+        "predicted",
+        "season",
+        "round",
+    ]);
+    for matches in &page.items {
+        table.add_row([
+            Cell::new(matches.id),
+            Cell::new(game_time(matches)),
+            Cell::new(text(&matches.league_name)),
+            Cell::new(text(&matches.home_team)),
+            Cell::new(text(&matches.away_team)),
+            Cell::new(score(matches.home_goals, matches.away_goals)),
+            Cell::new(score(matches.half_home_goals, matches.half_away_goals)),
+            Cell::new(outcome(matches.outcome())),
+            Cell::new(outcome(matches.predicted_outcome())),
+            Cell::new(text(&matches.game_year)),
+            Cell::new(text(&matches.game_round)),
+        ]);
+    }
+
+    let mut lines = vec![table.to_string(), format!("total: {}", page.total)];
+    if let Some(cursor) = &page.prev_cursor {
+        lines.push(format!("previous page: --cursor {cursor}"));
+    }
+    if let Some(cursor) = &page.next_cursor {
+        lines.push(format!("next page: --cursor {cursor}"));
+    }
+    lines.join("\n")
+}
+
+/// the fields of a match followed by its odds
+pub fn match_info(match_info: &MatchInfo) -> String {
+    let matches = &match_info.matches;
+    let mut fields = table(&["field", "value"]);
+    let rows = [
+        ("id", matches.id.to_string()),
+        ("league", text(&matches.league_name).to_string()),
+        ("home", text(&matches.home_team).to_string()),
+        ("away", text(&matches.away_team).to_string()),
+        ("game time", game_time(matches)),
+        ("season", text(&matches.game_year).to_string()),
+        ("round", text(&matches.game_round).to_string()),
+        ("score", score(matches.home_goals, matches.away_goals)),
+        (
+            "half",
+            score(matches.half_home_goals, matches.half_away_goals),
+        ),
+        ("result", outcome(matches.outcome()).to_string()),
+        (
+            "predicted",
+            outcome(matches.predicted_outcome()).to_string(),
+        ),
+        ("note", text(&matches.note).to_string()),
+    ];
+    for (field, value) in rows {
+        fields.add_row([field.to_string(), value]);
+    }
+
+    let mut odds = table(&[
+        "id",
+        "bookmaker",
+        "home start",
+        "draw start",
+        "away start",
+        "home end",
+        "draw end",
+        "away end",
+    ]);
+    for odd in &match_info.odds {
+        odds.add_row([
+            Cell::new(odd.id),
+            Cell::new(&odd.bookmaker_name),
+            Cell::new(&odd.home_win_start),
+            Cell::new(&odd.draw_start),
+            Cell::new(&odd.away_win_start),
+            Cell::new(&odd.home_win_end),
+            Cell::new(&odd.draw_end),
+            Cell::new(&odd.away_win_end),
+        ]);
+    }
+    format!("{fields}\n{odds}")
+}
+
+fn accuracy_row(group: &str, accuracy: &PredictionAccuracy) -> [Cell; 5] {
+    [
+        Cell::new(group),
+        Cell::new(accuracy.settled),
+        Cell::new(accuracy.hits),
+        Cell::new(&accuracy.hit_rate),
+        Cell::new(accuracy.pending),
+    ]
+}
+
+/// hit rates per group, the confusion matrix of all predictions and the rolling trend
+pub fn scorecard(scorecard: &PredictionScorecard) -> String {
+    let group_by = match scorecard.group_by {
+        ScorecardGroup::League => "league",
+        ScorecardGroup::Season => "season",
+        ScorecardGroup::Note => "note",
+        ScorecardGroup::Month => "month",
+    };
+    let mut groups = table(&[group_by, "settled", "hits", "hit rate", "pending"]);
+    for group in &scorecard.groups {
+        groups.add_row(accuracy_row(text(&group.key), group));
+    }
+    groups.add_row(accuracy_row("all", &scorecard.overall));
+
+    let mut confusion = table(&["predicted \\ actual", "home win", "draw", "away win"]);
+    for predicted in Outcome::ALL {
+        let mut row = vec![Cell::new(outcome(Some(predicted)))];
+        for actual in Outcome::ALL {
+            row.push(Cell::new(
+                scorecard.overall.confusion.count(predicted, actual),
+            ));
+        }
+        confusion.add_row(row);
+    }
+
+    let rolling = format!("last {}", scorecard.window);
+    let mut trend = table(&["match", "game time", "hit", &rolling, "cumulative"]);
+    for point in &scorecard.trend {
+        trend.add_row([
+            Cell::new(point.match_id),
+            Cell::new(
+                point
+                    .game_time
+                    .map(|time| time.format("%Y-%m-%d %H:%M").to_string())
+                    .unwrap_or_default(),
+            ),
+            Cell::new(if point.hit { "hit" } else { "miss" }),
+            Cell::new(&point.rolling_hit_rate),
+            Cell::new(&point.cumulative_hit_rate),
+        ]);
+    }
+    format!("{groups}\n{confusion}\n{trend}")
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDateTime;
+    use data::{MatchesBuilder, OddsBuilder, ScorecardQuery};
+
+    use super::*;
+
+    #[test]
+    fn match_info_should_be_printed() {
+        let matches = MatchesBuilder::default()
+            .id(7)
+            .league_name("英超")
+            .home_team("阿森纳")
+            .away_team("利物浦")
+            .game_time(NaiveDateTime::parse_from_str("2023-04-01 20:00", "%Y-%m-%d %H:%M").unwrap())
+            .home_goals(2)
+            .away_goals(1)
+            .build()
+            .unwrap();
+        let odds = OddsBuilder::default()
+            .bookmaker_name("威廉希尔".to_string())
+            .home_win_start_setter("2.10")
+            .build()
+            .unwrap();
+        let printed = match_info(&MatchInfo::new(matches.clone(), vec![odds]));
+        for value in [
+            "阿森纳",
+            "2023-04-01 20:00",
+            "2:1",
+            "home win",
+            "威廉希尔",
+            "2.10",
+        ] {
+            assert!(printed.contains(value), "{value} is not in\n{printed}");
+        }
+
+        let page = Page {
+            items: vec![matches],
+            total: 11,
+            next_cursor: Some("6100".to_string()),
+            prev_cursor: None,
+        };
+        let printed = match_page(&page);
+        assert!(printed.contains("total: 11"));
+        assert!(printed.contains("next page: --cursor 6100"));
+        assert!(!printed.contains("previous page"));
+    }
+
+    #[test]
+    fn scorecard_should_be_printed() {
+        let game = |id, predicted: &str, game_result: &str| {
+            MatchesBuilder::default()
+                .id(id)
+                .league_name("英超")
+                .predict_game_result(predicted)
+                .game_result(game_result)
+                .build()
+                .unwrap()
+        };
+        let query = ScorecardQuery {
+            window: Some(5),
+            ..Default::default()
+        };
+        let card = odds::prediction_scorecard(&[game(1, "3", "3"), game(2, "1", "0")], &query);
+        let printed = scorecard(&card);
+        for value in ["英超", "all", "0.5", "last 5", "hit", "miss"] {
+            assert!(printed.contains(value), "{value} is not in\n{printed}");
+        }
+    }
+}
@@ -7,6 +7,8 @@ import {
   BankOutlined,
   OrderedListOutlined,
   PlusOutlined,
+  ImportOutlined,
+  SaveOutlined,
 } from '@ant-design/icons'
 import { Link, matchRoutes, Outlet, useLocation } from 'react-router-dom'
 import { useEffect, useState } from 'react'
@@ -64,6 +66,9 @@ function AppLayout() {
                   <Menu.Item key="6" icon={<PlusOutlined />}>
                     <Link to="/add_match_info">新增</Link>
                   </Menu.Item>
+                  <Menu.Item key="7" icon={<ImportOutlined />}>
+                    <Link to="/import_match_info">导入</Link>
+                  </Menu.Item>
                 </SubMenu>
                 <Menu.Item key="2" icon={<ToolOutlined />}>
                   <Link to="/league">联赛</Link>
@@ -74,6 +79,9 @@ function AppLayout() {
                 <Menu.Item key="4" icon={<BankOutlined />}>
                   <Link to="/bookmaker">博彩公司</Link>
                 </Menu.Item>
+                <Menu.Item key="8" icon={<SaveOutlined />}>
+                  <Link to="/backup">备份</Link>
+                </Menu.Item>
               </SubMenu>
             </Menu>
           </Sider>
@@ -1,19 +1,22 @@
-import { Button, Col, DatePicker, Form, Input, Row, Select, Space } from 'antd'
+import { Button, Col, DatePicker, Form, Input, InputNumber, Row, Select, Space } from 'antd'
 import { PlusOutlined } from '@ant-design/icons'
 import { useEffect, useState } from 'react'
 import { invoke } from '@tauri-apps/api'
 import {
   DataType,
   MatchInfoDataType,
+  MatchFilterType,
   MatchInfoFormType,
+  MatchOddsDataType,
   OddsDataType,
   OddsFormType,
+  PageType,
   SelectType,
 } from '../types/data'
 import TextArea from 'antd/es/input/TextArea'
 import { MessageInstance } from 'antd/es/message/interface'
 import dayjs from 'dayjs'
-import { error, success } from '../utils'
+import { error, errorMessage, success } from '../utils'
 import Odds from './odds'
 
 const formItemLayout = {
@@ -111,6 +114,10 @@ function MatchInfo({ match_id, is_add, is_update, messageApi, handleValue }: Mat
       away_team: updateData.away_team,
       game_result: updateData.game_result,
       predict_game_result: updateData.predict_game_result,
+      home_goals: updateData.home_goals,
+      away_goals: updateData.away_goals,
+      half_home_goals: updateData.half_home_goals,
+      half_away_goals: updateData.half_away_goals,
       game_time: updateData.game_time ? dayjs(updateData.game_time, 'YYYY/MM/DD HH:mm:ss') : '',
       note: updateData.note,
     })
@@ -120,29 +127,31 @@ function MatchInfo({ match_id, is_add, is_update, messageApi, handleValue }: Mat
   const get_match_infos = async () => {
     try {
       const values = await form.validateFields()
-      let query = {
-        book_maker_id: values.bookmaker_id ? values.bookmaker_id : 0,
-        league_id: values.leagueInfo ? values.leagueInfo.value : 0,
-        team_id: values.home_team ? values.home_team : 0,
-        game_year: values.game_year,
-        game_round: values.game_round,
+      let [start_time, end_time] = values.game_time_range ? values.game_time_range : []
+      let query: MatchFilterType = {
+        bookmaker_id: values.bookmaker_id,
+        league_id: values.leagueInfo ? values.leagueInfo.value : undefined,
+        team_id: values.home_team ? values.home_team.value : undefined,
+        game_year: values.game_year ? values.game_year : undefined,
+        game_round: values.game_round ? values.game_round : undefined,
+        start_time: start_time ? dayjs(start_time).format('YYYY-MM-DDTHH:mm:ss') : undefined,
+        end_time: end_time ? dayjs(end_time).format('YYYY-MM-DDTHH:mm:ss') : undefined,
+        result: values.result,
+        prediction: values.prediction,
         is_desc: true,
-        cursor: 1,
         page_size: 10,
       }
-      let matchInfos = await invoke<MatchInfoDataType[]>('query_match_info', { query })
-      // set table data with query mode
-      if (!is_add && handleValue) {
-        handleValue(matchInfos)
-      } else if (is_update && match_id) {
-        // update mode
-        let matchInfo = matchInfos.find((item) => item.id === parseInt(match_id))
-        // query odds info by match id
+      if (is_update && match_id) {
+        // update mode, query the matches with their odds at once
+        let matchOdds = await invoke<MatchOddsDataType[]>('query_match_odds', { query })
+        let matchInfo = matchOdds.find((item) => item.matches.id === parseInt(match_id))
         if (matchInfo) {
-          let odds = await invoke<OddsDataType[]>('query_odds_by_id', { id: matchInfo.id })
-          matchInfo.oddsInfo = odds
-          setUpdateData(matchInfo)
+          setUpdateData({ ...matchInfo.matches, oddsInfo: matchInfo.odds })
         }
+      } else if (!is_add && handleValue) {
+        // set table data with query mode
+        let matchInfos = await invoke<PageType<MatchInfoDataType>>('query_match_info', { query })
+        handleValue(matchInfos, query)
       }
     } catch (err) {
       console.log('err is', err)
@@ -177,7 +186,7 @@ function MatchInfo({ match_id, is_add, is_update, messageApi, handleValue }: Mat
       success(messageApi, 'Successful: 更新成功')
     } catch (err) {
       console.log('handleUpdateInfo error is:', err)
-      error(messageApi, 'Failed: 更新失败, 请检查数据')
+      error(messageApi, errorMessage(err, 'Failed: 更新失败, 请检查数据'))
     }
   }
 
@@ -199,7 +208,7 @@ function MatchInfo({ match_id, is_add, is_update, messageApi, handleValue }: Mat
       success(messageApi, 'Successful: 保存成功')
     } catch (errorInfo) {
       console.log('Failed:', errorInfo)
-      error(messageApi, 'Failed: 保存失败, 请检查数据')
+      error(messageApi, errorMessage(errorInfo, 'Failed: 保存失败, 请检查数据'))
     }
   }
 
@@ -219,6 +228,10 @@ function MatchInfo({ match_id, is_add, is_update, messageApi, handleValue }: Mat
       game_round: values.game_round,
       game_result: values.game_result,
       predict_game_result: values.predict_game_result,
+      home_goals: values.home_goals,
+      away_goals: values.away_goals,
+      half_home_goals: values.half_home_goals,
+      half_away_goals: values.half_away_goals,
       history_note: values.history_note,
       note: values.note,
     }
@@ -241,6 +254,10 @@ function MatchInfo({ match_id, is_add, is_update, messageApi, handleValue }: Mat
       game_round: values.game_round,
       game_result: values.game_result,
       predict_game_result: values.predict_game_result,
+      home_goals: values.home_goals,
+      away_goals: values.away_goals,
+      half_home_goals: values.half_home_goals,
+      half_away_goals: values.half_away_goals,
       history_note: values.history_note,
       note: values.note,
     }
@@ -414,6 +431,68 @@ function MatchInfo({ match_id, is_add, is_update, messageApi, handleValue }: Mat
             </Col>
           )}
         </Row>
+        {!is_add && !is_update && (
+          <Row gutter={1}>
+            <Col span={12}>
+              <Form.Item {...formItemLayout} name="game_time_range" label="比赛时间">
+                <DatePicker.RangePicker showTime format="YYYY-MM-DD HH:mm:ss" />
+              </Form.Item>
+            </Col>
+            <Col span={6}>
+              <Form.Item name="result" label="比赛结果">
+                <Select
+                  allowClear
+                  placeholder="选择比赛结果"
+                  options={[
+                    { value: 'home_win', label: '主胜' },
+                    { value: 'draw', label: '平' },
+                    { value: 'away_win', label: '主负' },
+                  ]}
+                />
+              </Form.Item>
+            </Col>
+            <Col span={6}>
+              <Form.Item name="prediction" label="预测">
+                <Select
+                  allowClear
+                  placeholder="预测对错"
+                  options={[
+                    { value: 'hit', label: '命中' },
+                    { value: 'miss', label: '未中' },
+                  ]}
+                />
+              </Form.Item>
+            </Col>
+          </Row>
+        )}
+        {(is_add || is_update) && (
+          <Row gutter={1}>
+            <Col span={12}>
+              <Form.Item {...formItemLayout} label="全场比分">
+                <Space>
+                  <Form.Item name="home_goals" noStyle>
+                    <InputNumber min={0} placeholder="主队" />
+                  </Form.Item>
+                  <Form.Item name="away_goals" noStyle>
+                    <InputNumber min={0} placeholder="客队" />
+                  </Form.Item>
+                </Space>
+              </Form.Item>
+            </Col>
+            <Col span={12}>
+              <Form.Item {...formItemLayout} label="半场比分">
+                <Space>
+                  <Form.Item name="half_home_goals" noStyle>
+                    <InputNumber min={0} placeholder="主队" />
+                  </Form.Item>
+                  <Form.Item name="half_away_goals" noStyle>
+                    <InputNumber min={0} placeholder="客队" />
+                  </Form.Item>
+                </Space>
+              </Form.Item>
+            </Col>
+          </Row>
+        )}
         {(is_add || is_update) && (
           <Row>
             <Col span={12}>
@@ -0,0 +1,134 @@
+import { Alert, Button, Form, Input, message, Radio, Space, Table, Upload } from 'antd'
+import { UploadOutlined } from '@ant-design/icons'
+import { useState } from 'react'
+import type { ColumnsType } from 'antd/es/table'
+import type { RcFile } from 'antd/es/upload'
+import { invoke } from '@tauri-apps/api'
+import { RestoreCountType, RestoreReportType } from '../types/data'
+import { error, success } from '../utils'
+
+type PolicyType = 'skip' | 'overwrite' | 'fail'
+
+// the tables of an archive in the order they are restored
+const TABLES: [keyof RestoreReportType, string][] = [
+  ['bookmakers', '博彩公司'],
+  ['leagues', '联赛'],
+  ['teams', '球队'],
+  ['matches', '比赛'],
+  ['odds', '赔率'],
+]
+
+function Backup() {
+  const columns: ColumnsType<RestoreCountType & { table: string }> = [
+    {
+      title: '数据',
+      dataIndex: 'table',
+      key: 'table',
+    },
+    {
+      title: '新建',
+      dataIndex: 'created',
+      key: 'created',
+    },
+    {
+      title: '覆盖',
+      dataIndex: 'overwritten',
+      key: 'overwritten',
+    },
+    {
+      title: '跳过',
+      dataIndex: 'skipped',
+      key: 'skipped',
+    },
+  ]
+
+  const [messageApi, contextHolder] = message.useMessage()
+  const [path, setPath] = useState<string>('')
+  const [fileName, setFileName] = useState<string>('')
+  const [archive, setArchive] = useState<string>('')
+  const [policy, setPolicy] = useState<PolicyType>('skip')
+  const [report, setReport] = useState<RestoreReportType>()
+
+  // export the whole database to the archive file
+  const handleExport = async () => {
+    try {
+      await invoke('export_archive', { path })
+      success(messageApi, `Successful: 已导出到${path}`)
+    } catch (err) {
+      console.log('handleExport error is:', err)
+      error(messageApi, 'Failed: 导出失败, 请检查文件路径')
+    }
+  }
+
+  // read the chosen archive file instead of uploading it
+  const handleChooseFile = async (file: RcFile) => {
+    setFileName(file.name)
+    setArchive(await file.text())
+    setReport(undefined)
+    return false
+  }
+
+  // restore all rows of the archive at once
+  const handleRestore = async () => {
+    try {
+      let report = await invoke<RestoreReportType>('restore_archive', { archive, policy })
+      setReport(report)
+      success(messageApi, 'Successful: 恢复成功')
+    } catch (err) {
+      console.log('handleRestore error is:', err)
+      error(messageApi, 'Failed: 恢复失败, 没有数据被恢复')
+    }
+  }
+
+  return (
+    <>
+      {contextHolder}
+      <Form labelCol={{ span: 4 }} wrapperCol={{ span: 14 }} layout="horizontal">
+        <Form.Item label="导出到" extra="导出所有博彩公司, 联赛, 球队, 比赛和赔率到一个JSON文件">
+          <Space.Compact style={{ width: '100%' }}>
+            <Input
+              placeholder="/home/user/odds-backup.json"
+              value={path}
+              onChange={(e) => setPath(e.target.value)}
+            />
+            <Button type="primary" disabled={path === ''} onClick={handleExport}>
+              导出
+            </Button>
+          </Space.Compact>
+        </Form.Item>
+        <Form.Item label="备份文件">
+          <Space>
+            <Upload accept=".json" showUploadList={false} beforeUpload={handleChooseFile}>
+              <Button icon={<UploadOutlined />}>选择文件</Button>
+            </Upload>
+            <span>{fileName}</span>
+          </Space>
+        </Form.Item>
+        <Form.Item label="已存在的数据" extra="名称相同的博彩公司, 联赛和球队, 联赛, 球队和开赛时间相同的比赛, 比赛和博彩公司相同的赔率">
+          <Radio.Group value={policy} onChange={(e) => setPolicy(e.target.value)}>
+            <Radio value="skip">跳过</Radio>
+            <Radio value="overwrite">覆盖</Radio>
+            <Radio value="fail">停止恢复</Radio>
+          </Radio.Group>
+        </Form.Item>
+        <Form.Item wrapperCol={{ span: 14, offset: 4 }}>
+          <Button type="primary" disabled={archive === ''} onClick={handleRestore}>
+            恢复
+          </Button>
+        </Form.Item>
+      </Form>
+      {report && (
+        <>
+          <Alert type="success" message="恢复完成" />
+          <Table
+            columns={columns}
+            dataSource={TABLES.map(([key, table]) => ({ ...report[key], table, key }))}
+            pagination={false}
+          />
+        </>
+      )}
+    </>
+  )
+}
+
+export default Backup
@@ -3,7 +3,7 @@ import { useEffect, useState } from 'react'
 import { Link } from 'react-router-dom'
 import type { ColumnsType } from 'antd/es/table'
 import { invoke } from '@tauri-apps/api'
-import { error, success } from '../utils'
+import { error, errorMessage, success } from '../utils'
 
 function League() {
   const formItemLayout = {
@@ -98,7 +98,7 @@ function League() {
       render_list(lists)
       success(messageApi, 'Successful: 保存成功')
     } catch (errorInfo) {
-      error(messageApi, 'Failed: 保存失败, 请检查数据')
+      error(messageApi, errorMessage(errorInfo, 'Failed: 保存失败, 请检查数据'))
     }
   }
 
@@ -2,7 +2,7 @@ import { Button, Form, Input, message, Space } from 'antd'
 import { useEffect } from 'react'
 import type { ColumnsType } from 'antd/es/table'
 import { invoke } from '@tauri-apps/api'
-import { error, success } from '../utils'
+import { error, errorMessage, success } from '../utils'
 import { useParams } from 'react-router-dom'
 
 function LeagueUpdate() {
@@ -60,7 +60,7 @@ function LeagueUpdate() {
       success(messageApi, 'Successful: 更新成功')
     } catch (errorInfo) {
       console.log(errorInfo)
-      error(messageApi, 'Failed: 更新失败, 请检查数据')
+      error(messageApi, errorMessage(errorInfo, 'Failed: 更新失败, 请检查数据'))
     }
   }
 
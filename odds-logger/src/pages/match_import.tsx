@@ -0,0 +1,140 @@
+import { Alert, Button, Checkbox, Form, message, Radio, Space, Table, Upload } from 'antd'
+import { UploadOutlined } from '@ant-design/icons'
+import { useState } from 'react'
+import type { ColumnsType } from 'antd/es/table'
+import type { RcFile } from 'antd/es/upload'
+import { invoke } from '@tauri-apps/api'
+import { ImportReportType, ImportRowErrorType } from '../types/data'
+import { error, success } from '../utils'
+
+// the columns of the csv in each supported format
+const FORMATS = {
+  match: {
+    command: 'import_match_csv',
+    columns:
+      '列: league, game_time, home_team, away_team, game_year, game_round, home_goals, away_goals, half_home_goals, half_away_goals, bookmaker, home_win_start, draw_start, away_win_start, home_win_end, draw_end, away_win_end',
+  },
+  football_data: {
+    command: 'import_football_data_csv',
+    columns:
+      'football-data.co.uk 赛季文件, 列: Div, Date, Time, HomeTeam, AwayTeam, FTHG, FTAG, FTR, HTHG, HTAG 和配置中博彩公司前缀的赔率, 如 B365H, PSH, PSCH',
+  },
+}
+
+type FormatType = keyof typeof FORMATS
+
+function MatchImport() {
+  const columns: ColumnsType<ImportRowErrorType> = [
+    {
+      title: '行号',
+      dataIndex: 'line',
+      key: 'line',
+      width: 100,
+    },
+    {
+      title: '错误',
+      dataIndex: 'message',
+      key: 'message',
+    },
+  ]
+
+  const [messageApi, contextHolder] = message.useMessage()
+  const [fileName, setFileName] = useState<string>('')
+  const [csv, setCsv] = useState<string>('')
+  const [format, setFormat] = useState<FormatType>('match')
+  const [createMissing, setCreateMissing] = useState<boolean>(false)
+  const [report, setReport] = useState<ImportReportType>()
+
+  // read the chosen csv file instead of uploading it
+  const handleChooseFile = async (file: RcFile) => {
+    setFileName(file.name)
+    setCsv(await file.text())
+    setReport(undefined)
+    return false
+  }
+
+  // import all lines of the csv at once
+  const handleImport = async () => {
+    try {
+      let report = await invoke<ImportReportType>(FORMATS[format].command, { csv, createMissing })
+      setReport(report)
+      if (report.errors.length === 0) {
+        success(messageApi, `Successful: 导入${report.matches}场比赛, ${report.odds}条赔率`)
+      } else {
+        error(messageApi, 'Failed: 导入失败, 请检查错误的行')
+      }
+    } catch (err) {
+      console.log('handleImport error is:', err)
+      error(messageApi, 'Failed: 导入失败, 请检查数据')
+    }
+  }
+
+  // the leagues, teams and bookmakers created by the import
+  const createdNames = (report: ImportReportType) => {
+    const created: [string, string[]][] = [
+      ['联赛', report.created_leagues],
+      ['球队', report.created_teams],
+      ['博彩公司', report.created_bookmakers],
+    ]
+    return created
+      .filter(([_, names]) => names.length > 0)
+      .map(([title, names]) => `${title}: ${names.join(', ')}`)
+      .join('; ')
+  }
+
+  return (
+    <>
+      {contextHolder}
+      <Form labelCol={{ span: 4 }} wrapperCol={{ span: 14 }} layout="horizontal">
+        <Form.Item label="格式">
+          <Radio.Group
+            value={format}
+            onChange={(e) => {
+              setFormat(e.target.value)
+              setReport(undefined)
+            }}>
+            <Radio value="match">比赛赔率</Radio>
+            <Radio value="football_data">football-data.co.uk</Radio>
+          </Radio.Group>
+        </Form.Item>
+        <Form.Item label="CSV文件" extra={FORMATS[format].columns}>
+          <Space>
+            <Upload accept=".csv" showUploadList={false} beforeUpload={handleChooseFile}>
+              <Button icon={<UploadOutlined />}>选择文件</Button>
+            </Upload>
+            <span>{fileName}</span>
+          </Space>
+        </Form.Item>
+        <Form.Item wrapperCol={{ span: 14, offset: 4 }}>
+          <Checkbox checked={createMissing} onChange={(e) => setCreateMissing(e.target.checked)}>
+            自动创建不存在的联赛, 球队和博彩公司
+          </Checkbox>
+        </Form.Item>
+        <Form.Item wrapperCol={{ span: 14, offset: 4 }}>
+          <Button type="primary" disabled={csv === ''} onClick={handleImport}>
+            导入
+          </Button>
+        </Form.Item>
+      </Form>
+      {report && report.errors.length === 0 && (
+        <Alert
+          type="success"
+          message={`导入${report.matches}场比赛, ${report.odds}条赔率`}
+          description={createdNames(report) ? `新建 ${createdNames(report)}` : undefined}
+        />
+      )}
+      {report && report.errors.length > 0 && (
+        <>
+          <Alert type="error" message="所有行都没有导入, 请修改以下行后重新导入" />
+          <Table
+            columns={columns}
+            dataSource={report.errors.map((item, index) => ({ ...item, key: index }))}
+            pagination={false}
+          />
+        </>
+      )}
+    </>
+  )
+}
+
+export default MatchImport
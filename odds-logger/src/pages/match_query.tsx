@@ -1,9 +1,9 @@
 import { useState } from 'react'
 import { Link } from 'react-router-dom'
 import { invoke } from '@tauri-apps/api'
-import { message, Popconfirm, Space } from 'antd'
+import { Button, message, Popconfirm, Space } from 'antd'
 import Table, { ColumnsType } from 'antd/es/table'
-import { MatchInfoDataType, MatchInfoTableType } from '../types/data'
+import { MatchFilterType, MatchInfoDataType, MatchInfoTableType, PageType } from '../types/data'
 import { error, success } from '../utils'
 import MatchInfo from '../components/match_info'
 import dayjs from 'dayjs'
@@ -11,6 +11,9 @@ import dayjs from 'dayjs'
 function MatchQuery() {
   const [messageApi, contextHolder] = message.useMessage()
   const [tableData, setTableData] = useState<MatchInfoTableType[]>([])
+  const [query, setQuery] = useState<MatchFilterType>()
+  const [page, setPage] = useState<PageType<MatchInfoDataType>>()
+  const [pageNumber, setPageNumber] = useState(1)
 
   const columns: ColumnsType<MatchInfoTableType> = [
     {
@@ -109,14 +112,21 @@ function MatchQuery() {
     setTableData(new_data)
   }
 
-  // init the table info list data
-  const getMatchInfoTableData = (data: MatchInfoDataType[]) => {
+  // init the table info list data with the first page of a new query
+  const getMatchInfoTableData = (data: PageType<MatchInfoDataType>, query: MatchFilterType) => {
+    setQuery(query)
+    setPageNumber(1)
+    renderPage(data, 1, query.page_size)
+  }
+
+  // render a page of match infos in the table
+  const renderPage = (data: PageType<MatchInfoDataType>, number: number, pageSize: number) => {
     let result: MatchInfoTableType[] = []
-    data.map((item, index) => {
+    data.items.map((item, index) => {
       result.push({
         key: item.id.toString(),
         id: item.id,
-        index: index + 1,
+        index: (number - 1) * pageSize + index + 1,
         league_name: item.league_name,
         vs: item.home_team + ' vs ' + item.away_team,
         year: item.game_year,
@@ -127,6 +137,7 @@ function MatchQuery() {
         note: item.note,
       })
     })
+    setPage(data)
     setTableData(result)
   }
 
@@ -143,11 +154,24 @@ function MatchQuery() {
     return className
   }
 
-  // get page content with click page number
-  const getPageContent = (page: number, pageSize: number) => {
-    console.log(page, pageSize)
+  // get the neighbouring page with its cursor
+  const getPageContent = async (cursor: string | undefined, number: number) => {
+    if (!query || !cursor) {
+      return
+    }
+    try {
+      let data = await invoke<PageType<MatchInfoDataType>>('query_match_info', {
+        query: { ...query, cursor },
+      })
+      setPageNumber(number)
+      renderPage(data, number, query.page_size)
+    } catch (errorInfo) {
+      error(messageApi, 'Failed: 查询失败, 请检查数据')
+    }
   }
 
+  const pageCount = page && query ? Math.max(Math.ceil(page.total / query.page_size), 1) : 1
+
   return (
     <>
       {contextHolder}
@@ -161,8 +185,25 @@ function MatchQuery() {
         columns={columns}
         dataSource={tableData}
         rowClassName={getRowClassName}
-        pagination={{ pageSize: 5, onChange: getPageContent }}
+        pagination={false}
       />
+      {page && (
+        <Space style={{ marginTop: 16 }}>
+          <Button
+            disabled={!page.prev_cursor}
+            onClick={() => getPageContent(page.prev_cursor, pageNumber - 1)}>
+            上一页
+          </Button>
+          <span>
+            第 {pageNumber} / {pageCount} 页, 共 {page.total} 场
+          </span>
+          <Button
+            disabled={!page.next_cursor}
+            onClick={() => getPageContent(page.next_cursor, pageNumber + 1)}>
+            下一页
+          </Button>
+        </Space>
+      )}
     </>
   )
 }
@@ -3,7 +3,7 @@ import { useEffect, useState } from 'react'
 import type { ColumnsType } from 'antd/es/table'
 import { Link } from 'react-router-dom'
 import { invoke } from '@tauri-apps/api'
-import { error, success } from '../utils'
+import { error, errorMessage, success } from '../utils'
 
 function Team() {
   const formItemLayout = {
@@ -137,7 +137,7 @@ function Team() {
       render_list(lists)
       success(messageApi, 'Successful: 保存成功')
     } catch (errorInfo) {
-      error(messageApi, 'Failed: 保存失败, 请检查数据')
+      error(messageApi, errorMessage(errorInfo, 'Failed: 保存失败, 请检查数据'))
     }
   }
 
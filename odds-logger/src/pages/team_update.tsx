@@ -1,7 +1,7 @@
 import { Button, Form, Input, message, Select, Space } from 'antd'
 import { useEffect, useState } from 'react'
 import { invoke } from '@tauri-apps/api'
-import { error, success } from '../utils'
+import { error, errorMessage, success } from '../utils'
 import { useParams } from 'react-router-dom'
 import { DataType, TeamDataType } from '../types/data'
 
@@ -70,7 +70,7 @@ function TeamUpdate() {
       success(messageApi, 'Successful: 更新成功')
     } catch (errorInfo) {
       console.log(errorInfo)
-      error(messageApi, 'Failed: 更新失败, 请检查数据')
+      error(messageApi, errorMessage(errorInfo, 'Failed: 更新失败, 请检查数据'))
     }
   }
 
@@ -2,6 +2,7 @@ import { lazy, ReactNode, Suspense } from 'react'
 import { RouteObject } from 'react-router-dom'
 import AppLayout from '../components/app_layout'
 // lazy initialization
+const Backup = lazy(() => import('../pages/backup'))
 const BookMaker = lazy(() => import('../pages/bookmaker'))
 const BookMakerUpdate = lazy(() => import('../pages/bookmaker_update'))
 const League = lazy(() => import('../pages/league'))
@@ -9,6 +10,7 @@ const LeagueUpdate = lazy(() => import('../pages/league_update'))
 const Team = lazy(() => import('../pages/team'))
 const TeamUpdate = lazy(() => import('../pages/team_update'))
 const Match = lazy(() => import('../pages/match'))
+const MatchImport = lazy(() => import('../pages/match_import'))
 const MatchQuery = lazy(() => import('../pages/match_query'))
 const MatchUpdate = lazy(() => import('../pages/match_update'))
 
@@ -33,6 +35,10 @@ export const routes: RouteObject[] = [
         path: '/add_match_info',
         element: lazyLoad(<Match />),
       },
+      {
+        path: '/import_match_info',
+        element: lazyLoad(<MatchImport />),
+      },
       {
         path: '/league',
         element: lazyLoad(<League />),
@@ -53,6 +59,10 @@ export const routes: RouteObject[] = [
         path: '/team',
         element: lazyLoad(<Team />),
       },
+      {
+        path: '/backup',
+        element: lazyLoad(<Backup />),
+      },
       {
         path: '/team/:id',
         element: lazyLoad(<TeamUpdate />),
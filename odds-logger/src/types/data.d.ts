@@ -40,10 +40,38 @@ type CommonMatchInfo = {
   game_round: string
   game_result: string
   predict_game_result: string
+  home_goals?: number
+  away_goals?: number
+  half_home_goals?: number
+  half_away_goals?: number
   history_note: string
   note: string
 }
 
+// define match query filter, a condition left undefined matches all matches
+export interface MatchFilterType {
+  bookmaker_id?: number
+  league_id?: number
+  team_id?: number
+  game_year?: string
+  game_round?: string
+  start_time?: string
+  end_time?: string
+  result?: 'home_win' | 'draw' | 'away_win'
+  prediction?: 'hit' | 'miss'
+  is_desc: boolean
+  cursor?: string
+  page_size: number
+}
+
+// define a page of query results from backend, the cursors fetch the neighbouring pages
+export interface PageType<T> {
+  items: T[]
+  total: number
+  next_cursor?: string
+  prev_cursor?: string
+}
+
 // define match info data of form
 export interface MatchInfoFormType extends CommonMatchInfo {
   leagueInfo: SelectType
@@ -68,6 +96,12 @@ export interface MatchInfoDataType extends CommonMatchInfo {
   oddsInfo: OddsDataType[]
 }
 
+// define match with its odds from backend
+export interface MatchOddsDataType {
+  matches: MatchInfoDataType
+  odds: OddsDataType[]
+}
+
 type CommonOddsInfo = {
   id: number
   bookmaker_id: number
@@ -90,3 +124,87 @@ export interface OddsDataType extends BasicDataType, CommonOddsInfo {
 export interface OddsFormType extends CommonOddsInfo {
   bookmaker: SelectType
 }
+
+// define odds snapshot data type from backend
+export interface OddsSnapshotDataType extends BasicDataType {
+  match_id: number
+  bookmaker_id: number
+  home_win: string
+  draw: string
+  away_win: string
+  recorded_at: string
+}
+
+// define asian handicap odds data type from backend
+export interface AsianHandicapDataType extends BasicDataType {
+  match_id: number
+  bookmaker_id: number
+  bookmaker_name: string
+  line_start: string
+  home_price_start: string
+  away_price_start: string
+  line_end: string
+  home_price_end: string
+  away_price_end: string
+  note?: string
+}
+
+// define over/under odds data type from backend
+export interface OverUnderDataType extends BasicDataType {
+  match_id: number
+  bookmaker_id: number
+  bookmaker_name: string
+  line_start: string
+  over_price_start: string
+  under_price_start: string
+  line_end: string
+  over_price_end: string
+  under_price_end: string
+  note?: string
+}
+
+// define the error of a command from backend, the code is stable
+export interface OddsErrorType {
+  code: string
+  message: string
+  detail?: string
+  fields: FieldErrorType[]
+}
+
+// define a field of a row failing validation
+export interface FieldErrorType {
+  field: string
+  message: string
+}
+
+// define the error of an imported csv line from backend
+export interface ImportRowErrorType {
+  line: number
+  message: string
+}
+
+// define the report of a match import from backend
+export interface ImportReportType {
+  matches: number
+  odds: number
+  created_leagues: string[]
+  created_teams: string[]
+  created_bookmakers: string[]
+  errors: ImportRowErrorType[]
+}
+
+// define the rows of a table handled by a restore from backend
+export interface RestoreCountType {
+  created: number
+  overwritten: number
+  skipped: number
+}
+
+// define the report of an archive restore from backend
+export interface RestoreReportType {
+  bookmakers: RestoreCountType
+  leagues: RestoreCountType
+  teams: RestoreCountType
+  matches: RestoreCountType
+  odds: RestoreCountType
+}
@@ -1,4 +1,5 @@
 import { MessageInstance } from 'antd/es/message/interface'
+import { OddsErrorType } from '../types/data'
 
 const success = (messageApi: MessageInstance, content: string, duration: number = 3) => {
   messageApi.open({
@@ -16,4 +17,25 @@ const error = (messageApi: MessageInstance, content: string, duration: number =
   })
 }
 
-export { success, error }
+// message of the code of an error from backend, other errors show the fallback
+const errorMessage = (err: unknown, fallback: string) => {
+  const { code, detail, fields } = (err ?? {}) as Partial<OddsErrorType>
+  switch (code) {
+    case 'validation':
+      return `Failed: ${(fields ?? []).map((f) => `${f.field} ${f.message}`).join('; ')}`
+    case 'unique_violation':
+      return `Failed: 名称已存在 (${detail})`
+    case 'foreign_key_violation':
+      return 'Failed: 关联的数据不存在'
+    case 'not_found':
+      return 'Failed: 数据不存在'
+    case 'invalid_input':
+      return `Failed: 数据格式不正确 (${detail})`
+    case 'connection':
+      return 'Failed: 无法连接数据库'
+    default:
+      return fallback
+  }
+}
+
+export { success, error, errorMessage }
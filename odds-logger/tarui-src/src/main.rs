@@ -4,24 +4,37 @@
 )]
 
 use app::{
-    __cmd__delete_book_maker_info, __cmd__delete_league_info, __cmd__delete_match_info,
-    __cmd__delete_team_info, __cmd__get_book_maker_lists, __cmd__get_book_maker_with_id,
-    __cmd__get_league_lists, __cmd__get_league_with_id, __cmd__get_team_lists,
-    __cmd__get_team_with_id, __cmd__query_match_info, __cmd__query_odds_by_id,
-    __cmd__query_team_info_by_league, __cmd__save_book_maker_info, __cmd__save_league_info,
-    __cmd__save_match_odds, __cmd__save_team_info, __cmd__update_book_maker,
-    __cmd__update_league_info, __cmd__update_match_odds, __cmd__update_team_info,
-    delete_book_maker_info, delete_league_info, delete_match_info, delete_team_info,
-    get_book_maker_lists, get_book_maker_with_id, get_league_lists, get_league_with_id,
-    get_team_lists, get_team_with_id, query_match_info, query_odds_by_id,
-    query_team_info_by_league, save_book_maker_info, save_league_info, save_match_odds,
-    save_team_info, update_book_maker, update_league_info, update_match_odds, update_team_info,
+    __cmd__append_odds_snapshot, __cmd__delete_book_maker_info, __cmd__delete_league_info,
+    __cmd__delete_match_info, __cmd__delete_team_info, __cmd__export_archive,
+    __cmd__get_book_maker_lists, __cmd__get_book_maker_with_id, __cmd__get_league_lists,
+    __cmd__get_league_with_id, __cmd__get_team_lists, __cmd__get_team_with_id,
+    __cmd__import_football_data_csv, __cmd__import_match_csv, __cmd__query_asian_handicap_by_id,
+    __cmd__query_match_info, __cmd__query_match_odds, __cmd__query_odds_bands,
+    __cmd__query_odds_by_id, __cmd__query_odds_consensus_by_id, __cmd__query_odds_kelly_by_id,
+    __cmd__query_odds_movement_by_id, __cmd__query_odds_probability_by_id,
+    __cmd__query_odds_timeline, __cmd__query_over_under_by_id,
+    __cmd__query_over_under_settlement_by_id, __cmd__query_prediction_scorecard,
+    __cmd__query_similar_odds, __cmd__query_team_info_by_league, __cmd__restore_archive,
+    __cmd__save_asian_handicap_odds, __cmd__save_book_maker_info, __cmd__save_league_info,
+    __cmd__save_match_odds, __cmd__save_over_under_odds, __cmd__save_team_info,
+    __cmd__update_asian_handicap_odds, __cmd__update_book_maker, __cmd__update_league_info,
+    __cmd__update_match_odds, __cmd__update_over_under_odds, __cmd__update_team_info,
+    append_odds_snapshot, delete_book_maker_info, delete_league_info, delete_match_info,
+    delete_team_info, export_archive, get_book_maker_lists, get_book_maker_with_id,
+    get_league_lists, get_league_with_id, get_team_lists, get_team_with_id,
+    import_football_data_csv, import_match_csv, query_asian_handicap_by_id, query_match_info,
+    query_match_odds, query_odds_bands, query_odds_by_id, query_odds_consensus_by_id,
+    query_odds_kelly_by_id, query_odds_movement_by_id, query_odds_probability_by_id,
+    query_odds_timeline, query_over_under_by_id, query_over_under_settlement_by_id,
+    query_prediction_scorecard, query_similar_odds, query_team_info_by_league, restore_archive,
+    save_asian_handicap_odds, save_book_maker_info, save_league_info, save_match_odds,
+    save_over_under_odds, save_team_info, update_asian_handicap_odds, update_book_maker,
+    update_league_info, update_match_odds, update_over_under_odds, update_team_info,
 };
 use tauri::async_runtime::block_on;
 use tauri::Manager;
 
 use data::Config;
-use odds::OddsManager;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     tracing_subscriber::fmt::init();
@@ -49,8 +62,29 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             // odds
             save_match_odds,
             query_match_info,
+            query_match_odds,
             delete_match_info,
+            import_match_csv,
+            import_football_data_csv,
+            export_archive,
+            restore_archive,
             query_odds_by_id,
+            query_odds_probability_by_id,
+            query_odds_movement_by_id,
+            query_odds_consensus_by_id,
+            query_odds_kelly_by_id,
+            query_similar_odds,
+            query_odds_bands,
+            query_prediction_scorecard,
+            append_odds_snapshot,
+            query_odds_timeline,
+            query_asian_handicap_by_id,
+            save_asian_handicap_odds,
+            update_asian_handicap_odds,
+            query_over_under_by_id,
+            query_over_under_settlement_by_id,
+            save_over_under_odds,
+            update_over_under_odds,
             update_match_odds,
         ])
         .setup(|app| {
@@ -61,8 +95,11 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 .expect("failed to resolve resource");
             let file = std::fs::File::open(resource_path).unwrap();
             let config = Config::from_file(file)?;
-            let odds_manager = block_on(OddsManager::from_config(&config.db))?;
-            app.manage(odds_manager);
+            // postgres or a local sqlite file, as chosen by the configuration
+            let odds_store = block_on(odds::connect(&config.db))?;
+            app.manage(odds_store);
+            // bookmaker prefixes and names of football-data.co.uk csv files
+            app.manage(config.football_data);
             Ok(())
         })
         .run(tauri::generate_context!())
@@ -1,10 +1,10 @@
 use data::{BookMaker, BookMakerBuilder, OddsError};
-use odds::{EuropeOdds, OddsManager};
+use odds::OddsStore;
 use tauri::State;
 
 #[tauri::command]
 pub async fn get_book_maker_lists(
-    manager: State<'_, OddsManager>,
+    manager: State<'_, OddsStore>,
 ) -> Result<Vec<BookMaker>, OddsError> {
     let manager = &*manager;
     let bms = manager.list_bookermaker().await?;
@@ -13,7 +13,7 @@ pub async fn get_book_maker_lists(
 
 #[tauri::command]
 pub async fn save_book_maker_info(
-    manager: State<'_, OddsManager>,
+    manager: State<'_, OddsStore>,
     name: String,
     url: String,
     note: String,
@@ -31,7 +31,7 @@ pub async fn save_book_maker_info(
 
 #[tauri::command]
 pub async fn delete_book_maker_info(
-    manager: State<'_, OddsManager>,
+    manager: State<'_, OddsStore>,
     id: i32,
 ) -> Result<Vec<BookMaker>, OddsError> {
     let manager = &*manager;
@@ -41,7 +41,7 @@ pub async fn delete_book_maker_info(
 
 #[tauri::command]
 pub async fn get_book_maker_with_id(
-    manager: State<'_, OddsManager>,
+    manager: State<'_, OddsStore>,
     id: i32,
 ) -> Result<BookMaker, OddsError> {
     let manager = &*manager;
@@ -51,7 +51,7 @@ pub async fn get_book_maker_with_id(
 
 #[tauri::command]
 pub async fn update_book_maker(
-    manager: State<'_, OddsManager>,
+    manager: State<'_, OddsStore>,
     id: i32,
     name: String,
     url: String,
@@ -1,9 +1,9 @@
 use data::{League, LeagueBuilder, OddsError};
-use odds::{EuropeOdds, OddsManager};
+use odds::OddsStore;
 use tauri::State;
 
 #[tauri::command]
-pub async fn get_league_lists(manager: State<'_, OddsManager>) -> Result<Vec<League>, OddsError> {
+pub async fn get_league_lists(manager: State<'_, OddsStore>) -> Result<Vec<League>, OddsError> {
     let manager = &*manager;
     let leagues = manager.list_leagues().await?;
     Ok(leagues)
@@ -11,7 +11,7 @@ pub async fn get_league_lists(manager: State<'_, OddsManager>) -> Result<Vec<Lea
 
 #[tauri::command]
 pub async fn save_league_info(
-    manager: State<'_, OddsManager>,
+    manager: State<'_, OddsStore>,
     name: String,
     note: String,
 ) -> Result<Vec<League>, OddsError> {
@@ -27,7 +27,7 @@ pub async fn save_league_info(
 
 #[tauri::command]
 pub async fn delete_league_info(
-    manager: State<'_, OddsManager>,
+    manager: State<'_, OddsStore>,
     id: i32,
 ) -> Result<Vec<League>, OddsError> {
     let manager = &*manager;
@@ -37,7 +37,7 @@ pub async fn delete_league_info(
 
 #[tauri::command]
 pub async fn get_league_with_id(
-    manager: State<'_, OddsManager>,
+    manager: State<'_, OddsStore>,
     id: i32,
 ) -> Result<League, OddsError> {
     let manager = &*manager;
@@ -47,7 +47,7 @@ pub async fn get_league_with_id(
 
 #[tauri::command]
 pub async fn update_league_info(
-    manager: State<'_, OddsManager>,
+    manager: State<'_, OddsStore>,
     id: i32,
     name: String,
     note: String,
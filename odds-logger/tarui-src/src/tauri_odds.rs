@@ -1,74 +1,87 @@
-use chrono::NaiveDateTime;
-use data::{MatchInfo, MatchInfoQuery, Matches, MatchesBuilder, Odds, OddsBuilder, OddsError};
-use odds::{EuropeOdds, OddsManager};
-use serde::Deserialize;
+use data::{
+    Archive, AsianHandicapInfo, AsianHandicapOdds, ConflictPolicy, FootballDataConfig,
+    ImportReport, KellyReport, MarginRemoval, MatchFilter, MatchInfo, Matches, MatchesInfo, Odds,
+    OddsBand, OddsBandQuery, OddsConsensus, OddsError, OddsInfo, OddsMovement, OddsProbability,
+    OddsSnapshot, OverUnderInfo, OverUnderOdds, OverUnderSettlement, Page, PredictionScorecard,
+    RestoreReport, ScorecardQuery, SimilarOdds, SimilarOddsQuery,
+};
+use odds::OddsStore;
 use tauri::State;
 
-#[derive(Debug, Deserialize)]
-pub struct OddsInfo {
-    pub id: i32,
-    pub match_id: i32,
-    pub bookmaker_id: i32,
-    pub bookmaker_name: String,
-    pub home_win_start: Option<String>,
-    pub draw_start: Option<String>,
-    pub away_win_start: Option<String>,
-    pub home_win_end: Option<String>,
-    pub draw_end: Option<String>,
-    pub away_win_end: Option<String>,
-}
-
-#[derive(Debug, Deserialize)]
-pub struct MatchesInfo {
-    pub id: i32,
-    pub league_id: i32,
-    pub league_name: String,
-    pub home_team_id: i32,
-    pub home_team_name: String,
-    pub away_team_id: i32,
-    pub away_team_name: String,
-    pub game_time: Option<String>,
-    pub game_year: Option<String>,
-    pub game_round: Option<String>,
-    pub game_result: Option<String>,
-    pub predict_game_result: Option<String>,
-    pub history_note: Option<String>,
-    pub note: Option<String>,
-}
-
 // Result<Vec<MatchInfo>, OddsError>
 #[tauri::command]
 pub async fn save_match_odds(
-    manager: State<'_, OddsManager>,
+    manager: State<'_, OddsStore>,
     match_info: MatchesInfo,
     odds_infos: Vec<OddsInfo>,
 ) -> Result<MatchInfo, OddsError> {
     let manager = &*manager;
-    let (m_info, o_infos) = builde_match_odds_info(match_info, odds_infos);
-    // insert match info
-    let match_info = manager.create_match_info(m_info).await?;
-    // insert odds info
-    let mut result_odds_info = vec![];
-    for info in o_infos {
-        let odds_info = manager.create_odd_info(match_info.id, info).await?;
-        result_odds_info.push(odds_info)
-    }
-
-    Ok(MatchInfo::new(match_info, result_odds_info))
+    let mut match_info = match_info.into_match_info(odds_infos)?;
+    // always insert a new match with new odds
+    match_info.matches.id = 0;
+    match_info.odds.iter_mut().for_each(|info| info.id = 0);
+    let match_info = manager.save_match_info(match_info).await?;
+    Ok(match_info)
 }
 
 #[tauri::command]
 pub async fn query_match_info(
-    manager: State<'_, OddsManager>,
-    query: MatchInfoQuery,
-) -> Result<Vec<Matches>, OddsError> {
+    manager: State<'_, OddsStore>,
+    query: MatchFilter,
+) -> Result<Page<Matches>, OddsError> {
     let manager = &*manager;
     let match_info = manager.query_match_info(query).await?;
     Ok(match_info)
 }
 
 #[tauri::command]
-pub async fn delete_match_info(manager: State<'_, OddsManager>, id: i32) -> Result<i32, OddsError> {
+pub async fn import_match_csv(
+    manager: State<'_, OddsStore>,
+    csv: String,
+    create_missing: bool,
+) -> Result<ImportReport, OddsError> {
+    let manager = &*manager;
+    let report = manager.import_match_csv(&csv, create_missing).await?;
+    Ok(report)
+}
+
+#[tauri::command]
+pub async fn import_football_data_csv(
+    manager: State<'_, OddsStore>,
+    config: State<'_, FootballDataConfig>,
+    csv: String,
+    create_missing: bool,
+) -> Result<ImportReport, OddsError> {
+    let manager = &*manager;
+    let report = manager
+        .import_football_data_csv(&csv, &config, create_missing)
+        .await?;
+    Ok(report)
+}
+
+#[tauri::command]
+pub async fn export_archive(manager: State<'_, OddsStore>, path: String) -> Result<(), OddsError> {
+    let manager = &*manager;
+    let archive = manager.export_archive().await?;
+    std::fs::write(&path, archive.to_json()?)
+        .map_err(|err| OddsError::FileError(format!("{path}: {err}")))?;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn restore_archive(
+    manager: State<'_, OddsStore>,
+    archive: String,
+    policy: ConflictPolicy,
+) -> Result<RestoreReport, OddsError> {
+    let manager = &*manager;
+    let archive = Archive::from_json(&archive)?;
+    let report = manager.restore_archive(archive, policy).await?;
+    Ok(report)
+}
+
+#[tauri::command]
+pub async fn delete_match_info(manager: State<'_, OddsStore>, id: i32) -> Result<i32, OddsError> {
     let manager = &*manager;
     let count = manager.delete_match_info(id).await?;
     Ok(count)
@@ -76,133 +89,222 @@ pub async fn delete_match_info(manager: State<'_, OddsManager>, id: i32) -> Resu
 
 #[tauri::command]
 pub async fn query_odds_by_id(
-    manager: State<'_, OddsManager>,
+    manager: State<'_, OddsStore>,
     id: i32,
 ) -> Result<Vec<Odds>, OddsError> {
     let manager = &*manager;
     let mut odds = manager.query_odds_info_by_id(id).await?;
-    let mut new_result = vec![];
-    for odd in odds.iter_mut() {
-        odd.home_win_start = odd.home_win_start.with_prec(3);
-        odd.home_win_end = odd.home_win_end.with_prec(3);
-        odd.draw_start = odd.draw_start.with_prec(3);
-        odd.draw_end = odd.draw_end.with_prec(3);
-        odd.away_win_start = odd.away_win_start.with_prec(3);
-        odd.away_win_end = odd.away_win_end.with_prec(3);
-        new_result.push(odd.clone());
+    odds.iter_mut().for_each(with_display_precision);
+    Ok(odds)
+}
+
+#[tauri::command]
+pub async fn query_match_odds(
+    manager: State<'_, OddsStore>,
+    query: MatchFilter,
+) -> Result<Vec<MatchInfo>, OddsError> {
+    let manager = &*manager;
+    let mut match_infos = manager.query_match_info_with_odds(query).await?;
+    for match_info in match_infos.iter_mut() {
+        match_info.odds.iter_mut().for_each(with_display_precision);
     }
-    Ok(new_result)
+    Ok(match_infos)
+}
+
+/// keep 3 significant digits of the prices for display
+fn with_display_precision(odd: &mut Odds) {
+    odd.home_win_start = odd.home_win_start.with_prec(3);
+    odd.home_win_end = odd.home_win_end.with_prec(3);
+    odd.draw_start = odd.draw_start.with_prec(3);
+    odd.draw_end = odd.draw_end.with_prec(3);
+    odd.away_win_start = odd.away_win_start.with_prec(3);
+    odd.away_win_end = odd.away_win_end.with_prec(3);
 }
 
-// Result<Vec<MatchInfo>, OddsError>
 #[tauri::command]
-pub async fn update_match_odds(
-    manager: State<'_, OddsManager>,
-    match_info: MatchesInfo,
-    odds_infos: Vec<OddsInfo>,
+pub async fn query_odds_probability_by_id(
+    manager: State<'_, OddsStore>,
+    id: i32,
+) -> Result<Vec<OddsProbability>, OddsError> {
+    let manager = &*manager;
+    let probabilities = manager.query_odds_probability_by_id(id).await?;
+    Ok(probabilities)
+}
+
+#[tauri::command]
+pub async fn query_odds_movement_by_id(
+    manager: State<'_, OddsStore>,
+    id: i32,
+) -> Result<Vec<OddsMovement>, OddsError> {
+    let manager = &*manager;
+    let movements = manager.query_odds_movement_by_id(id).await?;
+    Ok(movements)
+}
+
+#[tauri::command]
+pub async fn query_odds_consensus_by_id(
+    manager: State<'_, OddsStore>,
+    id: i32,
+) -> Result<OddsConsensus, OddsError> {
+    let manager = &*manager;
+    let consensus = manager.query_odds_consensus_by_id(id).await?;
+    Ok(consensus)
+}
+
+#[tauri::command]
+pub async fn query_odds_kelly_by_id(
+    manager: State<'_, OddsStore>,
+    id: i32,
+    method: Option<MarginRemoval>,
+) -> Result<KellyReport, OddsError> {
+    let manager = &*manager;
+    let report = manager
+        .query_odds_kelly_by_id(id, method.unwrap_or_default())
+        .await?;
+    Ok(report)
+}
+
+#[tauri::command]
+pub async fn query_similar_odds(
+    manager: State<'_, OddsStore>,
+    query: SimilarOddsQuery,
+) -> Result<SimilarOdds, OddsError> {
+    let manager = &*manager;
+    let similar = manager.query_similar_odds(query).await?;
+    Ok(similar)
+}
+
+#[tauri::command]
+pub async fn query_odds_bands(
+    manager: State<'_, OddsStore>,
+    query: OddsBandQuery,
+) -> Result<Vec<OddsBand>, OddsError> {
+    let manager = &*manager;
+    let bands = manager.query_odds_bands(query).await?;
+    Ok(bands)
+}
+
+#[tauri::command]
+pub async fn query_prediction_scorecard(
+    manager: State<'_, OddsStore>,
+    query: ScorecardQuery,
+) -> Result<PredictionScorecard, OddsError> {
+    let manager = &*manager;
+    let scorecard = manager.query_prediction_scorecard(query).await?;
+    Ok(scorecard)
+}
+
+#[tauri::command]
+pub async fn append_odds_snapshot(
+    manager: State<'_, OddsStore>,
+    snapshot: OddsSnapshot,
+) -> Result<OddsSnapshot, OddsError> {
+    let manager = &*manager;
+    let snapshot = manager.append_odds_snapshot(snapshot).await?;
+    Ok(snapshot)
+}
+
+#[tauri::command]
+pub async fn query_odds_timeline(
+    manager: State<'_, OddsStore>,
+    match_id: i32,
+    bookmaker_id: i32,
+) -> Result<Vec<OddsSnapshot>, OddsError> {
+    let manager = &*manager;
+    let timeline = manager.query_odds_timeline(match_id, bookmaker_id).await?;
+    Ok(timeline)
+}
+
+#[tauri::command]
+pub async fn query_asian_handicap_by_id(
+    manager: State<'_, OddsStore>,
+    id: i32,
+) -> Result<Vec<AsianHandicapOdds>, OddsError> {
+    let manager = &*manager;
+    let odds = manager.query_asian_handicap_by_id(id).await?;
+    Ok(odds)
+}
+
+#[tauri::command]
+pub async fn save_asian_handicap_odds(
+    manager: State<'_, OddsStore>,
+    match_id: i32,
+    handicap_infos: Vec<AsianHandicapInfo>,
+) -> Result<Vec<AsianHandicapOdds>, OddsError> {
+    let manager = &*manager;
+    let odds = handicap_infos.into_iter().map(Into::into).collect();
+    let odds = manager.save_asian_handicaps(match_id, odds).await?;
+    Ok(odds)
+}
+
+#[tauri::command]
+pub async fn update_asian_handicap_odds(
+    manager: State<'_, OddsStore>,
+    match_id: i32,
+    handicap_infos: Vec<AsianHandicapInfo>,
 ) -> Result<(), OddsError> {
     let manager = &*manager;
-    let (m_info, o_infos) = builde_match_odds_info(match_info, odds_infos);
-    // update match info
-    let match_info = manager.update_match_info(m_info).await?;
-    // query odds info with now database
-    let mut odds = manager.query_odds_info_by_id(match_info.id).await?;
-    // odds info insert or update or delete with the odds id in the odds info data
-    for info in o_infos.into_iter() {
-        let id = info.id;
-        match odds.clone().into_iter().position(|odd| odd.id == id) {
-            Some(index) => {
-                // update the info
-                manager.update_odd_info(info).await?;
-                odds.remove(index);
-            }
-            None => {
-                if id == 0 {
-                    // insert into odds table
-                    manager.create_odd_info(match_info.id, info).await?;
-                }
-            }
-        }
-    }
-    for old_item in odds.iter() {
-        // delete the old data
-        manager.delete_odds_info(old_item.id).await?;
-    }
+    // insert or update or delete with the odds id in the handicap info data
+    let odds = handicap_infos.into_iter().map(Into::into).collect();
+    manager.save_asian_handicaps(match_id, odds).await?;
     Ok(())
 }
 
-fn builde_match_odds_info(
+#[tauri::command]
+pub async fn query_over_under_by_id(
+    manager: State<'_, OddsStore>,
+    id: i32,
+) -> Result<Vec<OverUnderOdds>, OddsError> {
+    let manager = &*manager;
+    let odds = manager.query_over_under_by_id(id).await?;
+    Ok(odds)
+}
+
+#[tauri::command]
+pub async fn query_over_under_settlement_by_id(
+    manager: State<'_, OddsStore>,
+    id: i32,
+) -> Result<Vec<OverUnderSettlement>, OddsError> {
+    let manager = &*manager;
+    let settlements = manager.query_over_under_settlement_by_id(id).await?;
+    Ok(settlements)
+}
+
+#[tauri::command]
+pub async fn save_over_under_odds(
+    manager: State<'_, OddsStore>,
+    match_id: i32,
+    over_under_infos: Vec<OverUnderInfo>,
+) -> Result<Vec<OverUnderOdds>, OddsError> {
+    let manager = &*manager;
+    let odds = over_under_infos.into_iter().map(Into::into).collect();
+    let odds = manager.save_over_unders(match_id, odds).await?;
+    Ok(odds)
+}
+
+#[tauri::command]
+pub async fn update_over_under_odds(
+    manager: State<'_, OddsStore>,
+    match_id: i32,
+    over_under_infos: Vec<OverUnderInfo>,
+) -> Result<(), OddsError> {
+    let manager = &*manager;
+    // insert or update or delete with the odds id in the over/under info data
+    let odds = over_under_infos.into_iter().map(Into::into).collect();
+    manager.save_over_unders(match_id, odds).await?;
+    Ok(())
+}
+
+// Result<Vec<MatchInfo>, OddsError>
+#[tauri::command]
+pub async fn update_match_odds(
+    manager: State<'_, OddsStore>,
     match_info: MatchesInfo,
     odds_infos: Vec<OddsInfo>,
-) -> (Matches, Vec<Odds>) {
-    let game_time = match match_info.game_time {
-        Some(time) => NaiveDateTime::parse_from_str(time.as_str(), "%Y-%m-%d %H:%M:%S").unwrap(),
-        None => NaiveDateTime::default(),
-    };
-    let m_info = MatchesBuilder::default()
-        .id(match_info.id)
-        .league_id(match_info.league_id)
-        .league_name(match_info.league_name)
-        .home_team_id(match_info.home_team_id)
-        .away_team_id(match_info.away_team_id)
-        .home_team(match_info.home_team_name)
-        .away_team(match_info.away_team_name)
-        .game_time(game_time)
-        .game_year(match_info.game_year.unwrap_or_default())
-        .game_round(match_info.game_round.unwrap_or_default())
-        .game_result(match_info.game_result.unwrap_or_default())
-        .predict_game_result(match_info.predict_game_result.unwrap_or_default())
-        .history_note(match_info.history_note.unwrap_or_default())
-        .note(match_info.note.unwrap_or_default())
-        .build()
-        .unwrap();
-    let odds_infos: Vec<Odds> = odds_infos
-        .iter()
-        .map(|info| {
-            OddsBuilder::default()
-                .id(info.id)
-                .match_id(info.match_id)
-                .bookmaker_id(info.bookmaker_id)
-                .bookmaker_name(info.bookmaker_name.clone())
-                .home_win_start_setter(
-                    info.home_win_start
-                        .clone()
-                        .unwrap_or_else(|| "0.00".to_string())
-                        .as_str(),
-                )
-                .draw_start_setter(
-                    info.draw_start
-                        .clone()
-                        .unwrap_or_else(|| "0.00".to_string())
-                        .as_str(),
-                )
-                .away_win_start_setter(
-                    info.away_win_start
-                        .clone()
-                        .unwrap_or_else(|| "0.00".to_string())
-                        .as_str(),
-                )
-                .home_win_end_setter(
-                    info.home_win_end
-                        .clone()
-                        .unwrap_or_else(|| "0.00".to_string())
-                        .as_str(),
-                )
-                .draw_end_setter(
-                    info.draw_end
-                        .clone()
-                        .unwrap_or_else(|| "0.00".to_string())
-                        .as_str(),
-                )
-                .away_win_end_setter(
-                    info.away_win_end
-                        .clone()
-                        .unwrap_or_else(|| "0.00".to_string())
-                        .as_str(),
-                )
-                .build()
-                .unwrap()
-        })
-        .collect();
-    (m_info, odds_infos)
+) -> Result<(), OddsError> {
+    let manager = &*manager;
+    let match_info = match_info.into_match_info(odds_infos)?;
+    // update the match and insert or update or delete its odds in one transaction
+    manager.save_match_info(match_info).await?;
+    Ok(())
 }
@@ -1,9 +1,9 @@
 use data::{OddsError, Team, TeamBuilder};
-use odds::{EuropeOdds, OddsManager};
+use odds::OddsStore;
 use tauri::State;
 
 #[tauri::command]
-pub async fn get_team_lists(manager: State<'_, OddsManager>) -> Result<Vec<Team>, OddsError> {
+pub async fn get_team_lists(manager: State<'_, OddsStore>) -> Result<Vec<Team>, OddsError> {
     let manager = &*manager;
     let leagues = manager.list_teams().await?;
     Ok(leagues)
@@ -11,7 +11,7 @@ pub async fn get_team_lists(manager: State<'_, OddsManager>) -> Result<Vec<Team>
 
 #[tauri::command]
 pub async fn save_team_info(
-    manager: State<'_, OddsManager>,
+    manager: State<'_, OddsStore>,
     id: i32,
     name: String,
     note: String,
@@ -29,7 +29,7 @@ pub async fn save_team_info(
 
 #[tauri::command]
 pub async fn delete_team_info(
-    manager: State<'_, OddsManager>,
+    manager: State<'_, OddsStore>,
     id: i32,
 ) -> Result<Vec<Team>, OddsError> {
     let manager = &*manager;
@@ -38,7 +38,7 @@ pub async fn delete_team_info(
 }
 
 #[tauri::command]
-pub async fn get_team_with_id(manager: State<'_, OddsManager>, id: i32) -> Result<Team, OddsError> {
+pub async fn get_team_with_id(manager: State<'_, OddsStore>, id: i32) -> Result<Team, OddsError> {
     let manager = &*manager;
     let team = manager.query_team_with_id(id).await?;
     Ok(team)
@@ -46,7 +46,7 @@ pub async fn get_team_with_id(manager: State<'_, OddsManager>, id: i32) -> Resul
 
 #[tauri::command]
 pub async fn update_team_info(
-    manager: State<'_, OddsManager>,
+    manager: State<'_, OddsStore>,
     id: i32,
     lid: i32,
     name: String,
@@ -66,7 +66,7 @@ pub async fn update_team_info(
 
 #[tauri::command]
 pub async fn query_team_info_by_league(
-    manager: State<'_, OddsManager>,
+    manager: State<'_, OddsStore>,
     id: i32,
 ) -> Result<Vec<Team>, OddsError> {
     let manager = &*manager;
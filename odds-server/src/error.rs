@@ -0,0 +1,74 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use data::OddsError;
+
+/// an `OddsError` answered with the status code of its kind, the body is the
+/// serialized error with its code
+#[derive(Debug)]
+pub struct ApiError(pub OddsError);
+
+impl From<OddsError> for ApiError {
+    fn from(err: OddsError) -> Self {
+        Self(err)
+    }
+}
+
+impl ApiError {
+    pub fn status(&self) -> StatusCode {
+        match &self.0 {
+            OddsError::NotFound => StatusCode::NOT_FOUND,
+            OddsError::UniqueViolation(_) | OddsError::Conflict(_) => StatusCode::CONFLICT,
+            OddsError::ForeignKeyViolation(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            OddsError::InvalidCursor(_)
+            | OddsError::InvalidArchive(_)
+            | OddsError::InvalidInput(_)
+            | OddsError::Validation(_) => StatusCode::BAD_REQUEST,
+            OddsError::ConnectionError(_) => StatusCode::SERVICE_UNAVAILABLE,
+            OddsError::ConfigReadError
+            | OddsError::ConfigParseError
+            | OddsError::DbError(_)
+            | OddsError::FileError(_)
+            | OddsError::None => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.status(), Json(self.0)).into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn error_should_be_mapped_to_status() {
+        let status = |err| ApiError(err).status();
+        assert_eq!(status(OddsError::NotFound), StatusCode::NOT_FOUND);
+        assert_eq!(
+            status(OddsError::InvalidCursor("x".to_string())),
+            StatusCode::BAD_REQUEST
+        );
+        assert_eq!(
+            status(OddsError::Conflict("league 英超".to_string())),
+            StatusCode::CONFLICT
+        );
+        assert_eq!(
+            status(OddsError::UniqueViolation("leagues_name_key".to_string())),
+            StatusCode::CONFLICT
+        );
+        assert_eq!(
+            status(OddsError::ConnectionError("pool timed out".to_string())),
+            StatusCode::SERVICE_UNAVAILABLE
+        );
+        assert_eq!(
+            status(OddsError::DbError("closed".to_string())),
+            StatusCode::INTERNAL_SERVER_ERROR
+        );
+    }
+}
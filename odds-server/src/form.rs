@@ -0,0 +1,94 @@
+use data::{BookMaker, ConflictPolicy, League, MarginRemoval, MatchesInfo, OddsInfo, Team};
+use serde::Deserialize;
+
+/// body of a bookmaker create or update
+#[derive(Debug, Deserialize)]
+pub struct BookMakerForm {
+    pub name: String,
+    pub url: Option<String>,
+    pub note: Option<String>,
+}
+
+impl BookMakerForm {
+    pub fn into_bookmaker(self, id: i32) -> BookMaker {
+        BookMaker {
+            id,
+            name: self.name,
+            url: self.url,
+            note: self.note,
+            created_at: Default::default(),
+            updated_at: Default::default(),
+        }
+    }
+}
+
+/// body of a league create or update
+#[derive(Debug, Deserialize)]
+pub struct LeagueForm {
+    pub name: String,
+    pub note: Option<String>,
+}
+
+impl LeagueForm {
+    pub fn into_league(self, id: i32) -> League {
+        League {
+            id,
+            name: self.name,
+            note: self.note,
+            created_at: Default::default(),
+            updated_at: Default::default(),
+        }
+    }
+}
+
+/// body of a team create or update
+#[derive(Debug, Deserialize)]
+pub struct TeamForm {
+    pub league_id: i32,
+    pub name: String,
+    pub note: Option<String>,
+}
+
+impl TeamForm {
+    pub fn into_team(self, id: i32) -> Team {
+        Team {
+            id,
+            league_id: self.league_id,
+            league_name: None,
+            name: self.name,
+            note: self.note,
+            created_at: Default::default(),
+            updated_at: Default::default(),
+        }
+    }
+}
+
+/// body of a match save, the arguments of the `save_match_odds` command of the app
+#[derive(Debug, Deserialize)]
+pub struct MatchForm {
+    pub match_info: MatchesInfo,
+    #[serde(default)]
+    pub odds_infos: Vec<OddsInfo>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct KellyParams {
+    pub method: Option<MarginRemoval>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TimelineParams {
+    pub bookmaker_id: i32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ImportParams {
+    #[serde(default)]
+    pub create_missing: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RestoreParams {
+    #[serde(default)]
+    pub policy: ConflictPolicy,
+}
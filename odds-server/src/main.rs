@@ -0,0 +1,64 @@
+mod error;
+mod form;
+mod routes;
+
+use std::{fs::File, net::SocketAddr, path::PathBuf, process::ExitCode};
+
+use clap::Parser;
+use data::{Config, OddsError};
+
+use crate::routes::{router, AppState};
+
+/// serve the odds data of the database in the config as a json api
+#[derive(Debug, Parser)]
+#[command(name = "odds-server", version)]
+struct Args {
+    /// config file of the database, the config.yml of the app works as well
+    #[arg(short, long, env = "ODDS_CONFIG", default_value = "config.yml")]
+    config: PathBuf,
+    /// address to listen on
+    #[arg(
+        short,
+        long,
+        env = "ODDS_SERVER_ADDR",
+        default_value = "127.0.0.1:3000"
+    )]
+    addr: SocketAddr,
+}
+
+impl Args {
+    async fn state(&self) -> Result<AppState, OddsError> {
+        let file = File::open(&self.config)
+            .map_err(|err| OddsError::FileError(format!("{}: {err}", self.config.display())))?;
+        let config = Config::from_file(file)?;
+        let store = odds::connect(&config.db).await?;
+        Ok(AppState::new(store, config.football_data))
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let args = Args::parse();
+    let state = match args.state().await {
+        Ok(state) => state,
+        Err(err) => {
+            eprintln!("error: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let served = match tokio::net::TcpListener::bind(args.addr).await {
+        Ok(listener) => {
+            println!("listening on http://{}", args.addr);
+            axum::serve(listener, router(state)).await
+        }
+        Err(err) => Err(err),
+    };
+    match served {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {}: {err}", args.addr);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,683 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    routing::{get, post, put},
+    Json, Router,
+};
+use data::{
+    Archive, AsianHandicapInfo, AsianHandicapOdds, BookMaker, FootballDataConfig, ImportReport,
+    KellyReport, League, MatchFilter, MatchInfo, Matches, Odds, OddsBand, OddsBandQuery,
+    OddsConsensus, OddsInfo, OddsMovement, OddsProbability, OddsSnapshot, OverUnderInfo,
+    OverUnderOdds, OverUnderSettlement, Page, PredictionScorecard, RestoreReport, ScorecardQuery,
+    SimilarOdds, SimilarOddsQuery, Team,
+};
+use odds::OddsStore;
+
+use crate::{
+    error::ApiError,
+    form::{
+        BookMakerForm, ImportParams, KellyParams, LeagueForm, MatchForm, RestoreParams, TeamForm,
+        TimelineParams,
+    },
+};
+
+type ApiResult<T> = Result<Json<T>, ApiError>;
+
+type Created<T> = Result<(StatusCode, Json<T>), ApiError>;
+
+#[derive(Clone)]
+pub struct AppState {
+    pub store: Arc<OddsStore>,
+    pub football_data: Arc<FootballDataConfig>,
+}
+
+impl AppState {
+    pub fn new(store: OddsStore, football_data: FootballDataConfig) -> Self {
+        Self {
+            store: Arc::new(store),
+            football_data: Arc::new(football_data),
+        }
+    }
+}
+
+/// the json api of every operation of the odds store
+pub fn router(state: AppState) -> Router {
+    Router::new()
+        .route("/bookmakers", get(list_bookmakers).post(create_bookmaker))
+        .route(
+            "/bookmakers/{id}",
+            get(get_bookmaker)
+                .put(update_bookmaker)
+                .delete(delete_bookmaker),
+        )
+        .route("/leagues", get(list_leagues).post(create_league))
+        .route(
+            "/leagues/{id}",
+            get(get_league).put(update_league).delete(delete_league),
+        )
+        .route("/leagues/{id}/teams", get(list_league_teams))
+        .route("/teams", get(list_teams).post(create_team))
+        .route(
+            "/teams/{id}",
+            get(get_team).put(update_team).delete(delete_team),
+        )
+        .route("/matches", get(query_matches).post(create_match))
+        .route("/matches/odds", get(query_match_odds))
+        .route(
+            "/matches/{id}",
+            get(get_match).put(update_match).delete(delete_match),
+        )
+        .route("/matches/{id}/odds", get(list_odds).post(create_odds))
+        .route("/matches/{id}/probabilities", get(query_probabilities))
+        .route("/matches/{id}/movements", get(query_movements))
+        .route("/matches/{id}/consensus", get(query_consensus))
+        .route("/matches/{id}/kelly", get(query_kelly))
+        .route("/matches/{id}/snapshots", post(append_snapshot))
+        .route("/matches/{id}/timeline", get(query_timeline))
+        .route(
+            "/matches/{id}/asian_handicaps",
+            get(list_asian_handicaps).post(create_asian_handicap),
+        )
+        .route(
+            "/matches/{id}/over_unders",
+            get(list_over_unders).post(create_over_under),
+        )
+        .route(
+            "/matches/{id}/over_under_settlements",
+            get(query_over_under_settlements),
+        )
+        .route("/odds/{id}", put(update_odds).delete(delete_odds))
+        .route(
+            "/asian_handicaps/{id}",
+            put(update_asian_handicap).delete(delete_asian_handicap),
+        )
+        .route(
+            "/over_unders/{id}",
+            put(update_over_under).delete(delete_over_under),
+        )
+        .route("/similar_odds", post(query_similar_odds))
+        .route("/odds_bands", post(query_odds_bands))
+        .route("/prediction_scorecard", get(query_prediction_scorecard))
+        .route("/imports/csv", post(import_csv))
+        .route("/imports/football_data", post(import_football_data))
+        .route("/archive", get(export_archive).post(restore_archive))
+        .with_state(state)
+}
+
+async fn list_bookmakers(State(state): State<AppState>) -> ApiResult<Vec<BookMaker>> {
+    let bookmakers = state.store.list_bookermaker().await?;
+    Ok(Json(bookmakers))
+}
+
+async fn create_bookmaker(
+    State(state): State<AppState>,
+    Json(form): Json<BookMakerForm>,
+) -> Created<Vec<BookMaker>> {
+    let bookmakers = state
+        .store
+        .create_bookermaker(form.into_bookmaker(0))
+        .await?;
+    Ok((StatusCode::CREATED, Json(bookmakers)))
+}
+
+async fn get_bookmaker(State(state): State<AppState>, Path(id): Path<i32>) -> ApiResult<BookMaker> {
+    let bookmaker = state.store.query_bookermaker_with_id(id).await?;
+    Ok(Json(bookmaker))
+}
+
+async fn update_bookmaker(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+    Json(form): Json<BookMakerForm>,
+) -> ApiResult<Vec<BookMaker>> {
+    let bookmakers = state
+        .store
+        .update_bookermaker(form.into_bookmaker(id))
+        .await?;
+    Ok(Json(bookmakers))
+}
+
+async fn delete_bookmaker(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+) -> ApiResult<Vec<BookMaker>> {
+    let bookmakers = state.store.delete_bookermaker(id).await?;
+    Ok(Json(bookmakers))
+}
+
+async fn list_leagues(State(state): State<AppState>) -> ApiResult<Vec<League>> {
+    let leagues = state.store.list_leagues().await?;
+    Ok(Json(leagues))
+}
+
+async fn create_league(
+    State(state): State<AppState>,
+    Json(form): Json<LeagueForm>,
+) -> Created<Vec<League>> {
+    let leagues = state.store.create_league(form.into_league(0)).await?;
+    Ok((StatusCode::CREATED, Json(leagues)))
+}
+
+async fn get_league(State(state): State<AppState>, Path(id): Path<i32>) -> ApiResult<League> {
+    let league = state.store.query_league_with_id(id).await?;
+    Ok(Json(league))
+}
+
+async fn update_league(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+    Json(form): Json<LeagueForm>,
+) -> ApiResult<Vec<League>> {
+    let leagues = state.store.update_league(form.into_league(id)).await?;
+    Ok(Json(leagues))
+}
+
+async fn delete_league(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+) -> ApiResult<Vec<League>> {
+    let leagues = state.store.delete_league(id).await?;
+    Ok(Json(leagues))
+}
+
+async fn list_league_teams(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+) -> ApiResult<Vec<Team>> {
+    let teams = state.store.query_teams_with_condition(id).await?;
+    Ok(Json(teams))
+}
+
+async fn list_teams(State(state): State<AppState>) -> ApiResult<Vec<Team>> {
+    let teams = state.store.list_teams().await?;
+    Ok(Json(teams))
+}
+
+async fn create_team(
+    State(state): State<AppState>,
+    Json(form): Json<TeamForm>,
+) -> Created<Vec<Team>> {
+    let teams = state.store.create_team(form.into_team(0)).await?;
+    Ok((StatusCode::CREATED, Json(teams)))
+}
+
+async fn get_team(State(state): State<AppState>, Path(id): Path<i32>) -> ApiResult<Team> {
+    let team = state.store.query_team_with_id(id).await?;
+    Ok(Json(team))
+}
+
+async fn update_team(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+    Json(form): Json<TeamForm>,
+) -> ApiResult<Vec<Team>> {
+    let teams = state.store.update_team(form.into_team(id)).await?;
+    Ok(Json(teams))
+}
+
+async fn delete_team(State(state): State<AppState>, Path(id): Path<i32>) -> ApiResult<Vec<Team>> {
+    let teams = state.store.delete_team(id).await?;
+    Ok(Json(teams))
+}
+
+async fn query_matches(
+    State(state): State<AppState>,
+    Query(filter): Query<MatchFilter>,
+) -> ApiResult<Page<Matches>> {
+    let page = state.store.query_match_info(filter).await?;
+    Ok(Json(page))
+}
+
+async fn query_match_odds(
+    State(state): State<AppState>,
+    Query(filter): Query<MatchFilter>,
+) -> ApiResult<Vec<MatchInfo>> {
+    let match_infos = state.store.query_match_info_with_odds(filter).await?;
+    Ok(Json(match_infos))
+}
+
+async fn create_match(
+    State(state): State<AppState>,
+    Json(form): Json<MatchForm>,
+) -> Created<MatchInfo> {
+    let mut match_info = form.match_info.into_match_info(form.odds_infos)?;
+    // always insert a new match with new odds
+    match_info.matches.id = 0;
+    match_info.odds.iter_mut().for_each(|odd| odd.id = 0);
+    let match_info = state.store.save_match_info(match_info).await?;
+    Ok((StatusCode::CREATED, Json(match_info)))
+}
+
+async fn get_match(State(state): State<AppState>, Path(id): Path<i32>) -> ApiResult<Matches> {
+    let matches = state.store.query_match_info_by_id(id).await?;
+    Ok(Json(matches))
+}
+
+async fn update_match(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+    Json(form): Json<MatchForm>,
+) -> ApiResult<MatchInfo> {
+    let mut match_info = form.match_info.into_match_info(form.odds_infos)?;
+    // update the match and insert or update or delete its odds in one transaction
+    match_info.matches.id = id;
+    let match_info = state.store.save_match_info(match_info).await?;
+    Ok(Json(match_info))
+}
+
+async fn delete_match(State(state): State<AppState>, Path(id): Path<i32>) -> ApiResult<i32> {
+    let count = state.store.delete_match_info(id).await?;
+    Ok(Json(count))
+}
+
+async fn list_odds(State(state): State<AppState>, Path(id): Path<i32>) -> ApiResult<Vec<Odds>> {
+    let odds = state.store.query_odds_info_by_id(id).await?;
+    Ok(Json(odds))
+}
+
+async fn create_odds(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+    Json(info): Json<OddsInfo>,
+) -> Created<Odds> {
+    let odds = state.store.create_odd_info(id, info.try_into()?).await?;
+    Ok((StatusCode::CREATED, Json(odds)))
+}
+
+async fn update_odds(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+    Json(info): Json<OddsInfo>,
+) -> ApiResult<Odds> {
+    let odds = Odds {
+        id,
+        ..info.try_into()?
+    };
+    let odds = state.store.update_odd_info(odds).await?;
+    Ok(Json(odds))
+}
+
+async fn delete_odds(State(state): State<AppState>, Path(id): Path<i32>) -> ApiResult<i32> {
+    let count = state.store.delete_odds_info(id).await?;
+    Ok(Json(count))
+}
+
+async fn query_probabilities(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+) -> ApiResult<Vec<OddsProbability>> {
+    let probabilities = state.store.query_odds_probability_by_id(id).await?;
+    Ok(Json(probabilities))
+}
+
+async fn query_movements(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+) -> ApiResult<Vec<OddsMovement>> {
+    let movements = state.store.query_odds_movement_by_id(id).await?;
+    Ok(Json(movements))
+}
+
+async fn query_consensus(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+) -> ApiResult<OddsConsensus> {
+    let consensus = state.store.query_odds_consensus_by_id(id).await?;
+    Ok(Json(consensus))
+}
+
+async fn query_kelly(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+    Query(params): Query<KellyParams>,
+) -> ApiResult<KellyReport> {
+    let report = state
+        .store
+        .query_odds_kelly_by_id(id, params.method.unwrap_or_default())
+        .await?;
+    Ok(Json(report))
+}
+
+async fn append_snapshot(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+    Json(snapshot): Json<OddsSnapshot>,
+) -> Created<OddsSnapshot> {
+    let snapshot = OddsSnapshot {
+        match_id: id,
+        ..snapshot
+    };
+    let snapshot = state.store.append_odds_snapshot(snapshot).await?;
+    Ok((StatusCode::CREATED, Json(snapshot)))
+}
+
+async fn query_timeline(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+    Query(params): Query<TimelineParams>,
+) -> ApiResult<Vec<OddsSnapshot>> {
+    let timeline = state
+        .store
+        .query_odds_timeline(id, params.bookmaker_id)
+        .await?;
+    Ok(Json(timeline))
+}
+
+async fn list_asian_handicaps(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+) -> ApiResult<Vec<AsianHandicapOdds>> {
+    let odds = state.store.query_asian_handicap_by_id(id).await?;
+    Ok(Json(odds))
+}
+
+async fn create_asian_handicap(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+    Json(info): Json<AsianHandicapInfo>,
+) -> Created<AsianHandicapOdds> {
+    let odds = state.store.create_asian_handicap(id, info.into()).await?;
+    Ok((StatusCode::CREATED, Json(odds)))
+}
+
+async fn update_asian_handicap(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+    Json(info): Json<AsianHandicapInfo>,
+) -> ApiResult<AsianHandicapOdds> {
+    let odds = AsianHandicapOdds { id, ..info.into() };
+    let odds = state.store.update_asian_handicap(odds).await?;
+    Ok(Json(odds))
+}
+
+async fn delete_asian_handicap(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+) -> ApiResult<i32> {
+    let count = state.store.delete_asian_handicap(id).await?;
+    Ok(Json(count))
+}
+
+async fn list_over_unders(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+) -> ApiResult<Vec<OverUnderOdds>> {
+    let odds = state.store.query_over_under_by_id(id).await?;
+    Ok(Json(odds))
+}
+
+async fn create_over_under(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+    Json(info): Json<OverUnderInfo>,
+) -> Created<OverUnderOdds> {
+    let odds = state.store.create_over_under(id, info.into()).await?;
+    Ok((StatusCode::CREATED, Json(odds)))
+}
+
+async fn update_over_under(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+    Json(info): Json<OverUnderInfo>,
+) -> ApiResult<OverUnderOdds> {
+    let odds = OverUnderOdds { id, ..info.into() };
+    let odds = state.store.update_over_under(odds).await?;
+    Ok(Json(odds))
+}
+
+async fn delete_over_under(State(state): State<AppState>, Path(id): Path<i32>) -> ApiResult<i32> {
+    let count = state.store.delete_over_under(id).await?;
+    Ok(Json(count))
+}
+
+async fn query_over_under_settlements(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+) -> ApiResult<Vec<OverUnderSettlement>> {
+    let settlements = state.store.query_over_under_settlement_by_id(id).await?;
+    Ok(Json(settlements))
+}
+
+async fn query_similar_odds(
+    State(state): State<AppState>,
+    Json(query): Json<SimilarOddsQuery>,
+) -> ApiResult<SimilarOdds> {
+    let similar = state.store.query_similar_odds(query).await?;
+    Ok(Json(similar))
+}
+
+async fn query_odds_bands(
+    State(state): State<AppState>,
+    Json(query): Json<OddsBandQuery>,
+) -> ApiResult<Vec<OddsBand>> {
+    let bands = state.store.query_odds_bands(query).await?;
+    Ok(Json(bands))
+}
+
+async fn query_prediction_scorecard(
+    State(state): State<AppState>,
+    Query(query): Query<ScorecardQuery>,
+) -> ApiResult<PredictionScorecard> {
+    let scorecard = state.store.query_prediction_scorecard(query).await?;
+    Ok(Json(scorecard))
+}
+
+/// import the csv of the body, see `odds::read_match_csv`
+async fn import_csv(
+    State(state): State<AppState>,
+    Query(params): Query<ImportParams>,
+    csv: String,
+) -> ApiResult<ImportReport> {
+    let report = state
+        .store
+        .import_match_csv(&csv, params.create_missing)
+        .await?;
+    Ok(Json(report))
+}
+
+/// import the football-data.co.uk season csv of the body with the mapping of the config
+async fn import_football_data(
+    State(state): State<AppState>,
+    Query(params): Query<ImportParams>,
+    csv: String,
+) -> ApiResult<ImportReport> {
+    let report = state
+        .store
+        .import_football_data_csv(&csv, &state.football_data, params.create_missing)
+        .await?;
+    Ok(Json(report))
+}
+
+async fn export_archive(State(state): State<AppState>) -> ApiResult<Archive> {
+    let archive = state.store.export_archive().await?;
+    Ok(Json(archive))
+}
+
+/// restore the archive of the body, its version is checked before it is read
+async fn restore_archive(
+    State(state): State<AppState>,
+    Query(params): Query<RestoreParams>,
+    archive: String,
+) -> ApiResult<RestoreReport> {
+    let archive = Archive::from_json(&archive)?;
+    let report = state.store.restore_archive(archive, params.policy).await?;
+    Ok(Json(report))
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{
+        body::{to_bytes, Body},
+        http::{header, Method, Request},
+    };
+    use odds::InMemoryOdds;
+    use serde_json::{json, Value};
+    use tower::ServiceExt;
+
+    use super::*;
+
+    fn app() -> Router {
+        let store: OddsStore = Box::new(InMemoryOdds::new());
+        router(AppState::new(store, FootballDataConfig::default()))
+    }
+
+    async fn send(
+        app: &Router,
+        method: Method,
+        uri: &str,
+        body: Option<Value>,
+    ) -> (StatusCode, Value) {
+        let request = Request::builder().method(method).uri(uri);
+        let request = match body {
+            Some(body) => request
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from(body.to_string())),
+            None => request.body(Body::empty()),
+        }
+        .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (
+            status,
+            serde_json::from_slice(&bytes).unwrap_or(Value::Null),
+        )
+    }
+
+    #[tokio::test]
+    async fn rows_should_be_created_and_queried() {
+        let app = app();
+        let (status, body) = send(
+            &app,
+            Method::POST,
+            "/bookmakers",
+            Some(json!({ "name": "威廉希尔", "url": "https://www.williamhill.com" })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        let id = body[0]["id"].as_i64().unwrap();
+
+        let (status, body) = send(&app, Method::GET, &format!("/bookmakers/{id}"), None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["name"], "威廉希尔");
+
+        let (status, body) = send(
+            &app,
+            Method::POST,
+            "/bookmakers",
+            Some(json!({ "name": "威廉希尔" })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CONFLICT);
+        assert_eq!(body["code"], "unique_violation");
+
+        let (status, body) = send(&app, Method::GET, "/bookmakers/999", None).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body["code"], "not_found");
+    }
+
+    #[tokio::test]
+    async fn match_should_be_saved_and_queried() {
+        let app = app();
+        let league = json!({ "name": "英超" });
+        let (status, _) = send(&app, Method::POST, "/leagues", Some(league)).await;
+        assert_eq!(status, StatusCode::CREATED);
+        for name in ["阿森纳", "利物浦"] {
+            let team = json!({ "name": name, "league_id": 1 });
+            let (status, _) = send(&app, Method::POST, "/teams", Some(team)).await;
+            assert_eq!(status, StatusCode::CREATED);
+        }
+        let match_form = json!({
+            "match_info": {
+                "id": 0,
+                "league_id": 1,
+                "league_name": "英超",
+                "home_team_id": 1,
+                "home_team_name": "阿森纳",
+                "away_team_id": 2,
+                "away_team_name": "利物浦",
+                "game_time": "2023-04-01 20:00:00",
+                "home_goals": 2,
+                "away_goals": 1
+            },
+            "odds_infos": [{
+                "id": 0,
+                "match_id": 0,
+                "bookmaker_id": 1,
+                "bookmaker_name": "威廉希尔",
+                "home_win_start": "2.10",
+                "draw_start": "3.40",
+                "away_win_start": "3.30",
+                "home_win_end": "2.05",
+                "draw_end": "3.50",
+                "away_win_end": "3.40"
+            }]
+        });
+        let (status, body) = send(&app, Method::POST, "/matches", Some(match_form)).await;
+        assert_eq!(status, StatusCode::CREATED);
+        let id = body["matches"]["id"].as_i64().unwrap();
+
+        let (status, body) = send(&app, Method::GET, "/matches?league_id=1", None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["items"][0]["id"].as_i64(), Some(id));
+
+        let (status, body) = send(&app, Method::GET, "/matches/odds?team_id=2", None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body[0]["odds"][0]["bookmaker_name"], "威廉希尔");
+
+        let (status, body) = send(&app, Method::GET, &format!("/matches/{id}/odds"), None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body.as_array().unwrap().len(), 1);
+
+        let uri = "/prediction_scorecard?group_by=month&window=5";
+        let (status, body) = send(&app, Method::GET, uri, None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["group_by"], "month");
+        assert_eq!(body["window"], 5);
+    }
+
+    #[tokio::test]
+    async fn invalid_request_should_be_bad_request() {
+        let app = app();
+        let (status, body) = send(&app, Method::GET, "/matches?cursor=zz", None).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["code"], "invalid_cursor");
+
+        let odds = json!({
+            "id": 0,
+            "match_id": 1,
+            "bookmaker_id": 1,
+            "bookmaker_name": "威廉希尔",
+            "home_win_start": "two"
+        });
+        let (status, body) = send(&app, Method::POST, "/matches/1/odds", Some(odds)).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["code"], "invalid_input");
+
+        let match_form = json!({
+            "match_info": {
+                "id": 0,
+                "league_id": 1,
+                "league_name": "英超",
+                "home_team_id": 1,
+                "home_team_name": "阿森纳",
+                "away_team_id": 1,
+                "away_team_name": "阿森纳",
+                "game_time": "2023-04-01 20:00:00"
+            },
+            "odds_infos": [{
+                "id": 0,
+                "match_id": 0,
+                "bookmaker_id": 1,
+                "bookmaker_name": "威廉希尔",
+                "home_win_start": "1.00"
+            }]
+        });
+        let (status, body) = send(&app, Method::POST, "/matches", Some(match_form)).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["code"], "validation");
+        assert_eq!(body["fields"][0]["field"], "away_team_id");
+    }
+}
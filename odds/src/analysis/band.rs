@@ -0,0 +1,171 @@
+use std::collections::{BTreeMap, HashMap};
+
+use bigdecimal::{BigDecimal, One, Zero};
+use data::{Matches, Odds, OddsBand, OddsBandQuery, OutcomeDistribution};
+
+use crate::PROBABILITY_SCALE;
+
+/// width of a band when the query gives none
+pub const DEFAULT_BAND_WIDTH: &str = "0.10";
+
+#[derive(Default)]
+struct BandAccumulator {
+    league_name: Option<String>,
+    bookmaker_name: Option<String>,
+    distribution: OutcomeDistribution,
+    implied_sum: BigDecimal,
+    count: i64,
+}
+
+/// bucket the matches by the closing home win price of every bookmaker's odds
+/// and count the results in each bucket, odds without a closing home win price are left out
+pub fn odds_bands(matches: &[Matches], odds: &[Odds], query: &OddsBandQuery) -> Vec<OddsBand> {
+    let band_width = query
+        .band_width
+        .clone()
+        .filter(|width| *width > BigDecimal::zero())
+        .unwrap_or_else(|| DEFAULT_BAND_WIDTH.parse().unwrap());
+    let matches: HashMap<i32, &Matches> = matches
+        .iter()
+        .filter(|m| query.league_id == 0 || m.league_id == query.league_id)
+        .map(|m| (m.id, m))
+        .collect();
+
+    let mut bands: BTreeMap<(Option<i32>, Option<i32>, BigDecimal), BandAccumulator> =
+        BTreeMap::new();
+    for odd in odds.iter() {
+        if query.bookmaker_id != 0 && odd.bookmaker_id != query.bookmaker_id {
+            continue;
+        }
+        if odd.home_win_end <= BigDecimal::zero() {
+            continue;
+        }
+        let Some(game) = matches.get(&odd.match_id) else {
+            continue;
+        };
+
+        let lower = (&odd.home_win_end / &band_width).with_scale(0) * &band_width;
+        let league_id = query.per_league.then_some(game.league_id);
+        let bookmaker_id = query.per_bookmaker.then_some(odd.bookmaker_id);
+        let band = bands.entry((league_id, bookmaker_id, lower)).or_default();
+        if query.per_league {
+            band.league_name = game.league_name.clone();
+        }
+        if query.per_bookmaker {
+            band.bookmaker_name = Some(odd.bookmaker_name.clone());
+        }
+        band.distribution.add(game.outcome());
+        band.implied_sum += BigDecimal::one() / &odd.home_win_end;
+        band.count += 1;
+    }
+
+    bands
+        .into_iter()
+        .map(|((league_id, bookmaker_id, lower), band)| {
+            let settled = BigDecimal::from(band.distribution.settled());
+            let rate = |count: u32| {
+                if settled.is_zero() {
+                    BigDecimal::zero()
+                } else {
+                    (BigDecimal::from(count) / &settled).round(PROBABILITY_SCALE)
+                }
+            };
+            OddsBand {
+                league_id,
+                league_name: band.league_name,
+                bookmaker_id,
+                bookmaker_name: band.bookmaker_name,
+                upper: &lower + &band_width,
+                lower,
+                home_win_rate: rate(band.distribution.home_win),
+                draw_rate: rate(band.distribution.draw),
+                away_win_rate: rate(band.distribution.away_win),
+                implied_home_win: (band.implied_sum / BigDecimal::from(band.count))
+                    .round(PROBABILITY_SCALE),
+                distribution: band.distribution,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use data::{MatchesBuilder, OddsBuilder};
+
+    use super::*;
+
+    fn game(id: i32, league_id: i32, game_result: &str) -> Matches {
+        MatchesBuilder::default()
+            .id(id)
+            .league_id(league_id)
+            .game_result(game_result)
+            .build()
+            .unwrap()
+    }
+
+    fn odd(match_id: i32, bookmaker_id: i32, home_win_end: &str) -> Odds {
+        OddsBuilder::default()
+            .match_id(match_id)
+            .bookmaker_id(bookmaker_id)
+            .bookmaker_name(format!("bookmaker{bookmaker_id}"))
+            .home_win_end_setter(home_win_end)
+            .build()
+            .unwrap()
+    }
+
+    fn query(per_bookmaker: bool) -> OddsBandQuery {
+        OddsBandQuery {
+            band_width: None,
+            league_id: 0,
+            bookmaker_id: 0,
+            per_league: false,
+            per_bookmaker,
+        }
+    }
+
+    #[test]
+    fn results_should_be_counted_per_band() {
+        let matches = vec![
+            game(1, 1, "3"),
+            game(2, 1, "1"),
+            game(3, 1, "3"),
+            game(4, 2, "0"),
+        ];
+        let odds = vec![
+            odd(1, 1, "1.25"),
+            odd(2, 1, "1.28"),
+            odd(3, 1, "1.30"),
+            odd(4, 1, "1.35"),
+            odd(4, 2, "0"),
+        ];
+        let bands = odds_bands(&matches, &odds, &query(false));
+        assert_eq!(bands.len(), 2);
+
+        assert_eq!(bands[0].lower, "1.20".parse().unwrap());
+        assert_eq!(bands[0].upper, "1.30".parse().unwrap());
+        assert_eq!(bands[0].distribution.home_win, 1);
+        assert_eq!(bands[0].distribution.draw, 1);
+        assert_eq!(bands[0].home_win_rate, "0.5".parse().unwrap());
+        // (1 / 1.25 + 1 / 1.28) / 2
+        assert_eq!(bands[0].implied_home_win, "0.7906".parse().unwrap());
+
+        assert_eq!(bands[1].lower, "1.30".parse().unwrap());
+        assert_eq!(bands[1].distribution.home_win, 1);
+        assert_eq!(bands[1].distribution.away_win, 1);
+        assert_eq!(bands[1].away_win_rate, "0.5".parse().unwrap());
+    }
+
+    #[test]
+    fn bands_should_be_split_by_bookmaker() {
+        let matches = vec![game(1, 1, "3"), game(2, 1, "")];
+        let odds = vec![odd(1, 1, "1.25"), odd(1, 2, "1.22"), odd(2, 2, "1.21")];
+        let bands = odds_bands(&matches, &odds, &query(true));
+        assert_eq!(bands.len(), 2);
+        assert_eq!(bands[0].bookmaker_id, Some(1));
+        assert_eq!(bands[1].bookmaker_id, Some(2));
+        assert_eq!(bands[1].bookmaker_name, Some("bookmaker2".into()));
+        assert_eq!(bands[1].distribution.home_win, 1);
+        assert_eq!(bands[1].distribution.unknown, 1);
+        assert_eq!(bands[1].home_win_rate, "1".parse().unwrap());
+    }
+}
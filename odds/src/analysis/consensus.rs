@@ -0,0 +1,132 @@
+use bigdecimal::{BigDecimal, Zero};
+use data::{Odds, OddsConsensus, Outcome, OutlierBookmaker, PriceStatistics};
+
+/// decimal places kept for price statistics
+pub const PRICE_SCALE: i64 = 3;
+
+/// calculate the statistics of one outcome's price across bookmakers,
+/// bookmakers without a price (stored as zero) are left out
+pub fn price_statistics(
+    outcome: Outcome,
+    odds: &[Odds],
+    price: fn(&Odds, Outcome) -> &BigDecimal,
+) -> Option<PriceStatistics> {
+    let quotes: Vec<(&Odds, &BigDecimal)> = odds
+        .iter()
+        .map(|odd| (odd, price(odd, outcome)))
+        .filter(|(_, price)| **price > BigDecimal::zero())
+        .collect();
+    if quotes.is_empty() {
+        return None;
+    }
+
+    let count = BigDecimal::from(quotes.len() as i64);
+    let mean = quotes
+        .iter()
+        .fold(BigDecimal::zero(), |sum, (_, price)| sum + *price)
+        / &count;
+    let variance = quotes.iter().fold(BigDecimal::zero(), |sum, (_, price)| {
+        let deviation = *price - &mean;
+        sum + &deviation * &deviation
+    }) / &count;
+    let std_dev = variance.sqrt().unwrap_or_default();
+
+    let mut prices: Vec<&BigDecimal> = quotes.iter().map(|(_, price)| *price).collect();
+    prices.sort();
+    let middle = prices.len() / 2;
+    let median = match prices.len() % 2 {
+        0 => (prices[middle - 1] + prices[middle]).half(),
+        _ => prices[middle].clone(),
+    };
+
+    let outlier = if quotes.len() > 1 {
+        quotes
+            .iter()
+            .max_by(|(_, a), (_, b)| (*a - &mean).abs().cmp(&(*b - &mean).abs()))
+            .map(|(odd, price)| OutlierBookmaker {
+                bookmaker_id: odd.bookmaker_id,
+                bookmaker_name: odd.bookmaker_name.clone(),
+                price: (*price).clone(),
+                deviation: (*price - &mean).round(PRICE_SCALE),
+            })
+    } else {
+        None
+    };
+
+    Some(PriceStatistics {
+        outcome,
+        count: quotes.len(),
+        mean: mean.round(PRICE_SCALE),
+        median: median.round(PRICE_SCALE),
+        min: prices[0].clone(),
+        max: prices[prices.len() - 1].clone(),
+        std_dev: std_dev.round(PRICE_SCALE),
+        outlier,
+    })
+}
+
+/// calculate the consensus of the opening and closing prices of a match
+pub fn odds_consensus(match_id: i32, odds: &[Odds]) -> OddsConsensus {
+    OddsConsensus {
+        match_id,
+        start: Outcome::ALL
+            .into_iter()
+            .filter_map(|outcome| price_statistics(outcome, odds, Odds::start_price))
+            .collect(),
+        end: Outcome::ALL
+            .into_iter()
+            .filter_map(|outcome| price_statistics(outcome, odds, Odds::end_price))
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use data::OddsBuilder;
+
+    use super::*;
+
+    fn odds(bookmaker_id: i32, home_win_start: &str, home_win_end: &str) -> Odds {
+        OddsBuilder::default()
+            .bookmaker_id(bookmaker_id)
+            .bookmaker_name(format!("bookmaker{bookmaker_id}"))
+            .home_win_start_setter(home_win_start)
+            .home_win_end_setter(home_win_end)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn price_statistics_should_be_calculated() {
+        let odds = vec![
+            odds(1, "2.00", "1.90"),
+            odds(2, "2.10", "1.95"),
+            odds(3, "2.20", "2.00"),
+            odds(4, "2.50", "0"),
+        ];
+        let statistics = price_statistics(Outcome::HomeWin, &odds, Odds::start_price).unwrap();
+        assert_eq!(statistics.count, 4);
+        assert_eq!(statistics.mean, "2.2".parse().unwrap());
+        assert_eq!(statistics.median, "2.15".parse().unwrap());
+        assert_eq!(statistics.min, "2.00".parse().unwrap());
+        assert_eq!(statistics.max, "2.50".parse().unwrap());
+        assert_eq!(statistics.std_dev, "0.187".parse().unwrap());
+        let outlier = statistics.outlier.unwrap();
+        assert_eq!(outlier.bookmaker_id, 4);
+        assert_eq!(outlier.deviation, "0.3".parse().unwrap());
+
+        // the missing closing price is left out
+        let statistics = price_statistics(Outcome::HomeWin, &odds, Odds::end_price).unwrap();
+        assert_eq!(statistics.count, 3);
+        assert_eq!(statistics.median, "1.95".parse().unwrap());
+    }
+
+    #[test]
+    fn single_bookmaker_should_have_no_outlier() {
+        let odds = vec![odds(1, "2.00", "1.90")];
+        let consensus = odds_consensus(1, &odds);
+        assert_eq!(consensus.start.len(), 1);
+        assert_eq!(consensus.start[0].std_dev, BigDecimal::zero());
+        assert!(consensus.start[0].outlier.is_none());
+    }
+}
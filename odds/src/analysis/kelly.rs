@@ -0,0 +1,156 @@
+use bigdecimal::{BigDecimal, FromPrimitive};
+use data::{
+    valid_prices, FairProbability, KellyIndex, KellyReport, MarginRemoval, Odds, OddsKelly,
+};
+
+use crate::PROBABILITY_SCALE;
+
+/// decimal places kept for the variance of kelly indices
+pub const KELLY_VARIANCE_SCALE: i64 = 6;
+
+/// average the fair probabilities of every bookmaker with valid prices
+pub fn market_probability(prices: &[[f64; 3]], method: MarginRemoval) -> Option<[f64; 3]> {
+    let probabilities: Vec<[f64; 3]> = prices
+        .iter()
+        .filter_map(|prices| method.fair_probabilities(*prices))
+        .collect();
+    if probabilities.is_empty() {
+        return None;
+    }
+
+    let count = probabilities.len() as f64;
+    let mut average = [0.0; 3];
+    for probability in probabilities.iter() {
+        for (sum, p) in average.iter_mut().zip(probability.iter()) {
+            *sum += p / count;
+        }
+    }
+    Some(average)
+}
+
+/// calculate the kelly indices of a price triple, the price multiplied by the
+/// market average fair probability, returns None when any price is missing or not
+/// valid for the fair probabilities either
+pub fn kelly_index(prices: [f64; 3], probability: [f64; 3]) -> Option<KellyIndex> {
+    if !valid_prices(&prices) {
+        return None;
+    }
+
+    let kelly = [
+        prices[0] * probability[0],
+        prices[1] * probability[1],
+        prices[2] * probability[2],
+    ];
+    let mean = kelly.iter().sum::<f64>() / 3.0;
+    let variance = kelly.iter().map(|k| (k - mean).powi(2)).sum::<f64>() / 3.0;
+
+    Some(KellyIndex {
+        home_win: to_decimal(kelly[0], PROBABILITY_SCALE),
+        draw: to_decimal(kelly[1], PROBABILITY_SCALE),
+        away_win: to_decimal(kelly[2], PROBABILITY_SCALE),
+        variance: to_decimal(variance, KELLY_VARIANCE_SCALE),
+    })
+}
+
+/// calculate the kelly indices of every bookmaker of a match
+pub fn kelly_report(match_id: i32, odds: &[Odds], method: MarginRemoval) -> KellyReport {
+    let start_prices: Vec<[f64; 3]> = odds.iter().map(Odds::start_prices).collect();
+    let end_prices: Vec<[f64; 3]> = odds.iter().map(Odds::end_prices).collect();
+    let start_probability = market_probability(&start_prices, method);
+    let end_probability = market_probability(&end_prices, method);
+
+    let odds = odds
+        .iter()
+        .map(|odd| OddsKelly {
+            odds_id: odd.id,
+            match_id: odd.match_id,
+            bookmaker_id: odd.bookmaker_id,
+            bookmaker_name: odd.bookmaker_name.clone(),
+            start: start_probability
+                .and_then(|probability| kelly_index(odd.start_prices(), probability)),
+            end: end_probability.and_then(|probability| kelly_index(odd.end_prices(), probability)),
+        })
+        .collect();
+
+    KellyReport {
+        match_id,
+        margin_removal: method,
+        start_probability: start_probability.map(to_fair_probability),
+        end_probability: end_probability.map(to_fair_probability),
+        odds,
+    }
+}
+
+fn to_fair_probability(probability: [f64; 3]) -> FairProbability {
+    FairProbability {
+        home_win: to_decimal(probability[0], PROBABILITY_SCALE),
+        draw: to_decimal(probability[1], PROBABILITY_SCALE),
+        away_win: to_decimal(probability[2], PROBABILITY_SCALE),
+    }
+}
+
+fn to_decimal(value: f64, scale: i64) -> BigDecimal {
+    BigDecimal::from_f64(value).unwrap_or_default().round(scale)
+}
+
+#[cfg(test)]
+mod tests {
+    use data::OddsBuilder;
+
+    use super::*;
+
+    #[test]
+    fn market_probability_should_average_fair_probabilities() {
+        let prices = [[2.0, 4.0, 4.0], [2.5, 2.5, 5.0], [0.0, 3.0, 3.0]];
+        let probability = market_probability(&prices, MarginRemoval::Multiplicative).unwrap();
+        assert!((probability[0] - 0.45).abs() < 1e-9);
+        assert!((probability[1] - 0.325).abs() < 1e-9);
+        assert!((probability[2] - 0.225).abs() < 1e-9);
+    }
+
+    #[test]
+    fn kelly_index_should_be_calculated() {
+        let kelly = kelly_index([2.0, 4.0, 4.0], [0.45, 0.325, 0.225]).unwrap();
+        assert_eq!(kelly.home_win, "0.9".parse().unwrap());
+        assert_eq!(kelly.draw, "1.3".parse().unwrap());
+        assert_eq!(kelly.away_win, "0.9".parse().unwrap());
+        // mean 1.0333, squared deviations 0.017778, 0.071111, 0.017778
+        assert_eq!(kelly.variance, "0.035556".parse().unwrap());
+    }
+
+    #[test]
+    fn prices_without_fair_probabilities_should_have_no_kelly_index() {
+        let probability = [0.45, 0.325, 0.225];
+        assert!(MarginRemoval::Multiplicative
+            .fair_probabilities([1.0, 4.0, 4.0])
+            .is_none());
+        assert!(kelly_index([1.0, 4.0, 4.0], probability).is_none());
+        assert!(kelly_index([0.5, 4.0, 4.0], probability).is_none());
+    }
+
+    #[test]
+    fn kelly_report_should_skip_missing_prices() {
+        let odd_1 = OddsBuilder::default()
+            .bookmaker_id(1)
+            .home_win_start_setter("2.00")
+            .draw_start_setter("4.00")
+            .away_win_start_setter("4.00")
+            .build()
+            .unwrap();
+        let odd_2 = OddsBuilder::default()
+            .bookmaker_id(2)
+            .home_win_start_setter("2.50")
+            .draw_start_setter("2.50")
+            .away_win_start_setter("5.00")
+            .build()
+            .unwrap();
+        let report = kelly_report(1, &[odd_1, odd_2], MarginRemoval::Multiplicative);
+        assert_eq!(report.odds.len(), 2);
+        assert!(report.end_probability.is_none());
+        assert!(report.odds.iter().all(|odd| odd.end.is_none()));
+        let start = report.odds[1].start.clone().unwrap();
+        assert_eq!(start.home_win, "1.125".parse().unwrap());
+        assert_eq!(start.draw, "0.8125".parse().unwrap());
+        assert_eq!(start.away_win, "1.125".parse().unwrap());
+    }
+}
@@ -0,0 +1,17 @@
+mod band;
+mod consensus;
+mod kelly;
+mod movement;
+mod probability;
+mod scorecard;
+mod settlement;
+mod similar;
+
+pub use band::*;
+pub use consensus::*;
+pub use kelly::*;
+pub use movement::*;
+pub use probability::*;
+pub use scorecard::*;
+pub use settlement::*;
+pub use similar::*;
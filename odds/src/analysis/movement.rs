@@ -0,0 +1,118 @@
+use bigdecimal::{BigDecimal, One, Zero};
+use data::{Odds, OddsMovement, Outcome, OutcomeMovement, PriceMovement};
+
+use crate::PROBABILITY_SCALE;
+
+/// decimal places kept for percentage changes
+pub const PERCENT_SCALE: i64 = 2;
+
+/// calculate the movement between an opening and a closing price,
+/// returns None when either price is missing (stored as zero)
+pub fn outcome_movement(
+    outcome: Outcome,
+    start: &BigDecimal,
+    end: &BigDecimal,
+) -> Option<OutcomeMovement> {
+    if *start <= BigDecimal::zero() || *end <= BigDecimal::zero() {
+        return None;
+    }
+
+    let change = end - start;
+    let change_percent = (&change * BigDecimal::from(100) / start).round(PERCENT_SCALE);
+    let probability_shift =
+        (BigDecimal::one() / end - BigDecimal::one() / start).round(PROBABILITY_SCALE);
+    let movement = if change < BigDecimal::zero() {
+        PriceMovement::Steam
+    } else if change > BigDecimal::zero() {
+        PriceMovement::Drift
+    } else {
+        PriceMovement::Unchanged
+    };
+
+    Some(OutcomeMovement {
+        outcome,
+        start: start.clone(),
+        end: end.clone(),
+        change,
+        change_percent,
+        probability_shift,
+        movement,
+    })
+}
+
+/// calculate the opening to closing movement of one bookmaker's odds
+pub fn odds_movement(odds: &Odds) -> OddsMovement {
+    let outcomes: Vec<OutcomeMovement> = Outcome::ALL
+        .into_iter()
+        .filter_map(|outcome| {
+            outcome_movement(outcome, odds.start_price(outcome), odds.end_price(outcome))
+        })
+        .collect();
+    let moved_towards = outcomes
+        .iter()
+        .filter(|movement| movement.probability_shift > BigDecimal::zero())
+        .max_by(|a, b| a.probability_shift.cmp(&b.probability_shift))
+        .map(|movement| movement.outcome);
+
+    OddsMovement {
+        odds_id: odds.id,
+        match_id: odds.match_id,
+        bookmaker_id: odds.bookmaker_id,
+        bookmaker_name: odds.bookmaker_name.clone(),
+        outcomes,
+        moved_towards,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use data::OddsBuilder;
+
+    use super::*;
+
+    #[test]
+    fn outcome_movement_should_be_calculated() {
+        let movement = outcome_movement(
+            Outcome::HomeWin,
+            &"2.00".parse().unwrap(),
+            &"1.60".parse().unwrap(),
+        )
+        .unwrap();
+        assert_eq!(movement.change, "-0.40".parse().unwrap());
+        assert_eq!(movement.change_percent, "-20".parse().unwrap());
+        assert_eq!(movement.probability_shift, "0.125".parse().unwrap());
+        assert_eq!(movement.movement, PriceMovement::Steam);
+    }
+
+    #[test]
+    fn market_should_move_towards_the_steamed_outcome() {
+        let odds = OddsBuilder::default()
+            .home_win_start_setter("2.10")
+            .draw_start_setter("3.30")
+            .away_win_start_setter("3.50")
+            .home_win_end_setter("2.40")
+            .draw_end_setter("3.30")
+            .away_win_end_setter("2.90")
+            .build()
+            .unwrap();
+        let movement = odds_movement(&odds);
+        assert_eq!(movement.outcomes.len(), 3);
+        assert_eq!(movement.outcomes[0].movement, PriceMovement::Drift);
+        assert_eq!(movement.outcomes[1].movement, PriceMovement::Unchanged);
+        assert_eq!(movement.outcomes[2].movement, PriceMovement::Steam);
+        assert_eq!(movement.moved_towards, Some(Outcome::AwayWin));
+    }
+
+    #[test]
+    fn missing_closing_prices_should_be_skipped() {
+        let odds = OddsBuilder::default()
+            .home_win_start_setter("2.10")
+            .draw_start_setter("3.30")
+            .away_win_start_setter("3.50")
+            .build()
+            .unwrap();
+        let movement = odds_movement(&odds);
+        assert!(movement.outcomes.is_empty());
+        assert_eq!(movement.moved_towards, None);
+    }
+}
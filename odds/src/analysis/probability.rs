@@ -0,0 +1,98 @@
+use bigdecimal::{BigDecimal, One, Zero};
+use data::{ImpliedProbability, Odds, OddsProbability};
+
+/// decimal places kept for probabilities, margins and payout rates
+pub const PROBABILITY_SCALE: i64 = 4;
+
+/// calculate the implied probabilities of a 1X2 price triple,
+/// returns None when any price is missing (stored as zero)
+pub fn implied_probability(
+    home_win: &BigDecimal,
+    draw: &BigDecimal,
+    away_win: &BigDecimal,
+) -> Option<ImpliedProbability> {
+    if [home_win, draw, away_win]
+        .iter()
+        .any(|price| *price <= &BigDecimal::zero())
+    {
+        return None;
+    }
+
+    let home_win = BigDecimal::one() / home_win;
+    let draw = BigDecimal::one() / draw;
+    let away_win = BigDecimal::one() / away_win;
+    let book = &home_win + &draw + &away_win;
+
+    Some(ImpliedProbability {
+        home_win: home_win.round(PROBABILITY_SCALE),
+        draw: draw.round(PROBABILITY_SCALE),
+        away_win: away_win.round(PROBABILITY_SCALE),
+        overround: (&book - BigDecimal::one()).round(PROBABILITY_SCALE),
+        payout_rate: (BigDecimal::one() / book).round(PROBABILITY_SCALE),
+    })
+}
+
+/// calculate the implied probabilities of the opening and closing prices of odds
+pub fn odds_probability(odds: &Odds) -> OddsProbability {
+    OddsProbability {
+        odds_id: odds.id,
+        match_id: odds.match_id,
+        bookmaker_id: odds.bookmaker_id,
+        bookmaker_name: odds.bookmaker_name.clone(),
+        start: implied_probability(&odds.home_win_start, &odds.draw_start, &odds.away_win_start),
+        end: implied_probability(&odds.home_win_end, &odds.draw_end, &odds.away_win_end),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use data::OddsBuilder;
+
+    use super::*;
+
+    fn decimal(value: &str) -> BigDecimal {
+        BigDecimal::from_str(value).unwrap()
+    }
+
+    #[test]
+    fn implied_probability_should_be_calculated() {
+        let probability = implied_probability(&decimal("2.00"), &decimal("4.00"), &decimal("4.00"));
+        assert_eq!(
+            probability,
+            Some(ImpliedProbability {
+                home_win: decimal("0.5"),
+                draw: decimal("0.25"),
+                away_win: decimal("0.25"),
+                overround: decimal("0"),
+                payout_rate: decimal("1"),
+            })
+        );
+    }
+
+    #[test]
+    fn overround_and_payout_rate_should_be_calculated() {
+        let probability =
+            implied_probability(&decimal("1.91"), &decimal("3.40"), &decimal("4.20")).unwrap();
+        assert_eq!(probability.home_win, decimal("0.5236"));
+        assert_eq!(probability.draw, decimal("0.2941"));
+        assert_eq!(probability.away_win, decimal("0.2381"));
+        assert_eq!(probability.overround, decimal("0.0558"));
+        assert_eq!(probability.payout_rate, decimal("0.9472"));
+    }
+
+    #[test]
+    fn missing_prices_should_have_no_probability() {
+        let odds = OddsBuilder::default()
+            .bookmaker_id(1)
+            .home_win_start_setter("1.91")
+            .draw_start_setter("3.40")
+            .away_win_start_setter("4.20")
+            .build()
+            .unwrap();
+        let probability = odds_probability(&odds);
+        assert!(probability.start.is_some());
+        assert!(probability.end.is_none());
+    }
+}
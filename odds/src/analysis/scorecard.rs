@@ -0,0 +1,199 @@
+use std::collections::{BTreeMap, VecDeque};
+
+use bigdecimal::{BigDecimal, Zero};
+use data::{
+    AccuracyPoint, ConfusionMatrix, Matches, PredictionAccuracy, PredictionScorecard,
+    ScorecardGroup, ScorecardQuery,
+};
+
+use crate::PROBABILITY_SCALE;
+
+/// number of settled predictions in the rolling window when the query gives none
+pub const DEFAULT_SCORECARD_WINDOW: u32 = 20;
+
+#[derive(Default)]
+struct AccuracyAccumulator {
+    confusion: ConfusionMatrix,
+    pending: u32,
+}
+
+impl AccuracyAccumulator {
+    fn into_accuracy(self, key: Option<String>) -> PredictionAccuracy {
+        let settled = self.confusion.total();
+        let hits = self.confusion.hits();
+        PredictionAccuracy {
+            key,
+            settled,
+            hits,
+            hit_rate: rate(hits, settled),
+            pending: self.pending,
+            confusion: self.confusion,
+        }
+    }
+}
+
+fn rate(count: u32, total: u32) -> BigDecimal {
+    if total == 0 {
+        BigDecimal::zero()
+    } else {
+        (BigDecimal::from(count) / BigDecimal::from(total)).round(PROBABILITY_SCALE)
+    }
+}
+
+/// the group of a match, blank values fall in the group without a key
+fn group_key(game: &Matches, group_by: ScorecardGroup) -> Option<String> {
+    let key = match group_by {
+        ScorecardGroup::League => game.league_name.clone(),
+        ScorecardGroup::Season => game.game_year.clone(),
+        ScorecardGroup::Note => game.note.clone(),
+        ScorecardGroup::Month => game.game_time.map(|time| time.format("%Y-%m").to_string()),
+    };
+    key.map(|key| key.trim().to_string())
+        .filter(|key| !key.is_empty())
+}
+
+/// compare the predicted outcome of every match with its result per group and over time,
+/// matches without a recognisable prediction are left out
+pub fn prediction_scorecard(matches: &[Matches], query: &ScorecardQuery) -> PredictionScorecard {
+    let window = query
+        .window
+        .filter(|window| *window > 0)
+        .unwrap_or(DEFAULT_SCORECARD_WINDOW);
+    let mut predicted: Vec<_> = matches
+        .iter()
+        .filter(|m| query.league_id.is_none_or(|id| m.league_id == id))
+        .filter_map(|m| Some((m, m.predicted_outcome()?)))
+        .collect();
+    predicted.sort_by_key(|(m, _)| (m.game_time, m.id));
+
+    let mut overall = AccuracyAccumulator::default();
+    let mut groups: BTreeMap<Option<String>, AccuracyAccumulator> = BTreeMap::new();
+    let mut trend = Vec::new();
+    let mut recent = VecDeque::new();
+    for (game, prediction) in predicted {
+        let group = groups.entry(group_key(game, query.group_by)).or_default();
+        let Some(actual) = game.outcome() else {
+            overall.pending += 1;
+            group.pending += 1;
+            continue;
+        };
+        overall.confusion.add(prediction, actual);
+        group.confusion.add(prediction, actual);
+
+        let hit = prediction == actual;
+        recent.push_back(hit);
+        if recent.len() > window as usize {
+            recent.pop_front();
+        }
+        let recent_hits = recent.iter().filter(|hit| **hit).count() as u32;
+        trend.push(AccuracyPoint {
+            match_id: game.id,
+            game_time: game.game_time,
+            hit,
+            rolling_hit_rate: rate(recent_hits, recent.len() as u32),
+            cumulative_hit_rate: rate(overall.confusion.hits(), overall.confusion.total()),
+        });
+    }
+
+    PredictionScorecard {
+        group_by: query.group_by,
+        window,
+        overall: overall.into_accuracy(None),
+        groups: groups
+            .into_iter()
+            .map(|(key, group)| group.into_accuracy(key))
+            .collect(),
+        trend,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDateTime;
+    use data::{MatchesBuilder, Outcome};
+
+    use super::*;
+
+    fn game(id: i32, game_time: &str, predicted: &str, game_result: Option<&str>) -> Matches {
+        let mut game = MatchesBuilder::default()
+            .id(id)
+            .league_id(1)
+            .league_name("英超")
+            .game_time(NaiveDateTime::parse_from_str(game_time, "%Y-%m-%d %H:%M").unwrap())
+            .predict_game_result(predicted)
+            .build()
+            .unwrap();
+        game.game_result = game_result.map(str::to_string);
+        game
+    }
+
+    fn query(group_by: ScorecardGroup, window: Option<u32>) -> ScorecardQuery {
+        ScorecardQuery {
+            league_id: None,
+            group_by,
+            window,
+        }
+    }
+
+    #[test]
+    fn predictions_should_be_scored_per_month() {
+        let matches = vec![
+            game(4, "2023-05-02 20:00", "1", Some("1")),
+            game(1, "2023-04-01 20:00", "3", Some("3")),
+            game(2, "2023-04-08 20:00", "3", Some("0")),
+            game(3, "2023-04-15 20:00", "0", Some("0")),
+            game(5, "2023-05-09 20:00", "3", None),
+            // without a prediction
+            game(6, "2023-05-09 20:00", "", Some("3")),
+        ];
+        let scorecard = prediction_scorecard(&matches, &query(ScorecardGroup::Month, Some(2)));
+
+        assert_eq!(scorecard.overall.settled, 4);
+        assert_eq!(scorecard.overall.hits, 3);
+        assert_eq!(scorecard.overall.hit_rate, "0.75".parse().unwrap());
+        assert_eq!(scorecard.overall.pending, 1);
+        let confusion = &scorecard.overall.confusion;
+        assert_eq!(confusion.count(Outcome::HomeWin, Outcome::HomeWin), 1);
+        assert_eq!(confusion.count(Outcome::HomeWin, Outcome::AwayWin), 1);
+        assert_eq!(confusion.count(Outcome::Draw, Outcome::Draw), 1);
+
+        assert_eq!(scorecard.groups.len(), 2);
+        assert_eq!(scorecard.groups[0].key.as_deref(), Some("2023-04"));
+        assert_eq!(scorecard.groups[0].settled, 3);
+        assert_eq!(scorecard.groups[0].hit_rate, "0.6667".parse().unwrap());
+        assert_eq!(scorecard.groups[1].key.as_deref(), Some("2023-05"));
+        assert_eq!(scorecard.groups[1].hits, 1);
+        assert_eq!(scorecard.groups[1].pending, 1);
+
+        let ids: Vec<i32> = scorecard.trend.iter().map(|point| point.match_id).collect();
+        assert_eq!(ids, [1, 2, 3, 4]);
+        let rolling: Vec<BigDecimal> = ["1", "0.5", "0.5", "1"]
+            .iter()
+            .map(|rate| rate.parse().unwrap())
+            .collect();
+        for (point, rolling) in scorecard.trend.iter().zip(rolling) {
+            assert_eq!(point.rolling_hit_rate, rolling);
+        }
+        assert_eq!(
+            scorecard.trend[2].cumulative_hit_rate,
+            "0.6667".parse().unwrap()
+        );
+    }
+
+    #[test]
+    fn blank_keys_should_be_grouped_together() {
+        let mut noted = game(1, "2023-04-01 20:00", "3", Some("3"));
+        noted.note = Some("主队降盘".into());
+        let mut blank = game(2, "2023-04-08 20:00", "3", Some("1"));
+        blank.note = Some(" ".into());
+        let plain = game(3, "2023-04-15 20:00", "1", Some("1"));
+        let scorecard =
+            prediction_scorecard(&[noted, blank, plain], &query(ScorecardGroup::Note, None));
+        assert_eq!(scorecard.window, DEFAULT_SCORECARD_WINDOW);
+        assert_eq!(scorecard.groups.len(), 2);
+        assert_eq!(scorecard.groups[0].key, None);
+        assert_eq!(scorecard.groups[0].settled, 2);
+        assert_eq!(scorecard.groups[1].key.as_deref(), Some("主队降盘"));
+        assert_eq!(scorecard.groups[1].hit_rate, "1".parse().unwrap());
+    }
+}
@@ -0,0 +1,98 @@
+use bigdecimal::{BigDecimal, Zero};
+use data::{settle_line, LineSettlement, Matches, OverUnderOdds, OverUnderSettlement};
+
+/// settle both sides of a total goals line, returns None when the line has no prices
+pub fn line_settlement(
+    total_goals: i32,
+    line: &BigDecimal,
+    over_price: &BigDecimal,
+    under_price: &BigDecimal,
+) -> Option<LineSettlement> {
+    if *over_price <= BigDecimal::zero() || *under_price <= BigDecimal::zero() {
+        return None;
+    }
+
+    let over = settle_line(&BigDecimal::from(total_goals), line);
+    Some(LineSettlement {
+        line: line.clone(),
+        over,
+        under: over.opposite(),
+    })
+}
+
+/// settle the opening and closing lines of over/under odds against the final score
+pub fn over_under_settlement(matches: &Matches, odds: &OverUnderOdds) -> OverUnderSettlement {
+    let total_goals = matches
+        .home_goals
+        .zip(matches.away_goals)
+        .map(|(home, away)| home + away);
+
+    OverUnderSettlement {
+        odds_id: odds.id,
+        match_id: odds.match_id,
+        bookmaker_id: odds.bookmaker_id,
+        bookmaker_name: odds.bookmaker_name.clone(),
+        total_goals,
+        start: total_goals.and_then(|goals| {
+            line_settlement(
+                goals,
+                &odds.line_start,
+                &odds.over_price_start,
+                &odds.under_price_start,
+            )
+        }),
+        end: total_goals.and_then(|goals| {
+            line_settlement(
+                goals,
+                &odds.line_end,
+                &odds.over_price_end,
+                &odds.under_price_end,
+            )
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use data::{MatchesBuilder, OverUnderOddsBuilder, Settlement};
+
+    use super::*;
+
+    fn odds() -> OverUnderOdds {
+        OverUnderOddsBuilder::default()
+            .line_start("2.5".parse::<BigDecimal>().unwrap())
+            .over_price_start("1.90".parse::<BigDecimal>().unwrap())
+            .under_price_start("1.95".parse::<BigDecimal>().unwrap())
+            .line_end("2.75".parse::<BigDecimal>().unwrap())
+            .over_price_end("2.02".parse::<BigDecimal>().unwrap())
+            .under_price_end("1.84".parse::<BigDecimal>().unwrap())
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn odds_should_be_settled_against_the_final_score() {
+        let matches = MatchesBuilder::default()
+            .home_goals(2)
+            .away_goals(1)
+            .build()
+            .unwrap();
+        let settlement = over_under_settlement(&matches, &odds());
+        assert_eq!(settlement.total_goals, Some(3));
+        let start = settlement.start.unwrap();
+        assert_eq!(start.over, Settlement::Win);
+        assert_eq!(start.under, Settlement::Loss);
+        let end = settlement.end.unwrap();
+        assert_eq!(end.over, Settlement::HalfWin);
+        assert_eq!(end.under, Settlement::HalfLoss);
+    }
+
+    #[test]
+    fn odds_should_not_be_settled_without_a_score() {
+        let matches = MatchesBuilder::default().build().unwrap();
+        let settlement = over_under_settlement(&matches, &odds());
+        assert_eq!(settlement.total_goals, None);
+        assert!(settlement.start.is_none());
+        assert!(settlement.end.is_none());
+    }
+}
@@ -0,0 +1,108 @@
+use bigdecimal::BigDecimal;
+use data::{Matches, Odds, SimilarMatch, SimilarOdds};
+
+/// the tolerances of a similar odds lookup, a tolerance is ignored when
+/// the looked up odds have no price on that side
+pub fn effective_tolerances(
+    odds: &Odds,
+    start_tolerance: Option<BigDecimal>,
+    end_tolerance: Option<BigDecimal>,
+) -> (Option<BigDecimal>, Option<BigDecimal>) {
+    (
+        start_tolerance.filter(|_| odds.start_prices().iter().all(|price| *price > 0.0)),
+        end_tolerance.filter(|_| odds.end_prices().iter().all(|price| *price > 0.0)),
+    )
+}
+
+/// whether every price of the candidate is within the tolerance of the looked up odds
+pub fn within_tolerance(
+    odds: &Odds,
+    candidate: &Odds,
+    start_tolerance: Option<&BigDecimal>,
+    end_tolerance: Option<&BigDecimal>,
+) -> bool {
+    let close = |a: [&BigDecimal; 3], b: [&BigDecimal; 3], tolerance: &BigDecimal| {
+        a.iter()
+            .zip(b.iter())
+            .all(|(a, b)| (*a - *b).abs() <= *tolerance)
+    };
+    fn start(odd: &Odds) -> [&BigDecimal; 3] {
+        [&odd.home_win_start, &odd.draw_start, &odd.away_win_start]
+    }
+    fn end(odd: &Odds) -> [&BigDecimal; 3] {
+        [&odd.home_win_end, &odd.draw_end, &odd.away_win_end]
+    }
+
+    start_tolerance.is_none_or(|tolerance| close(start(odds), start(candidate), tolerance))
+        && end_tolerance.is_none_or(|tolerance| close(end(odds), end(candidate), tolerance))
+}
+
+/// pair the similar odds with their matches and count the results,
+/// the order of the similar odds is kept
+pub fn similar_odds(odds: Odds, similar_odds: Vec<Odds>, mut matches: Vec<Matches>) -> SimilarOdds {
+    let mut similar_matches = vec![];
+    for odd in similar_odds {
+        if let Some(index) = matches.iter().position(|m| m.id == odd.match_id) {
+            similar_matches.push(SimilarMatch::new(matches.swap_remove(index), odd));
+        }
+    }
+    let distribution = similar_matches
+        .iter()
+        .map(|similar| similar.matches.outcome())
+        .collect();
+
+    SimilarOdds {
+        odds,
+        matches: similar_matches,
+        distribution,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use data::OddsBuilder;
+
+    use super::*;
+
+    fn odds(home_win_start: &str, home_win_end: &str) -> Odds {
+        OddsBuilder::default()
+            .home_win_start_setter(home_win_start)
+            .draw_start_setter("3.30")
+            .away_win_start_setter("3.40")
+            .home_win_end_setter(home_win_end)
+            .draw_end_setter("3.30")
+            .away_win_end_setter("3.40")
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn candidates_should_be_compared_within_tolerance() {
+        let tolerance: BigDecimal = "0.10".parse().unwrap();
+        let looked_up = odds("2.10", "2.00");
+        let candidate = odds("2.20", "2.20");
+        assert!(within_tolerance(
+            &looked_up,
+            &candidate,
+            Some(&tolerance),
+            None
+        ));
+        assert!(!within_tolerance(
+            &looked_up,
+            &candidate,
+            Some(&tolerance),
+            Some(&tolerance)
+        ));
+        assert!(within_tolerance(&looked_up, &candidate, None, None));
+    }
+
+    #[test]
+    fn missing_prices_should_ignore_the_tolerance() {
+        let looked_up = odds("2.10", "0");
+        let tolerance: BigDecimal = "0.10".parse().unwrap();
+        let (start, end) =
+            effective_tolerances(&looked_up, Some(tolerance.clone()), Some(tolerance));
+        assert!(start.is_some());
+        assert!(end.is_none());
+    }
+}
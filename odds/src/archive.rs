@@ -0,0 +1,343 @@
+use std::collections::HashMap;
+
+use chrono::NaiveDateTime;
+use data::{
+    Archive, AsianHandicapOdds, BookMaker, ConflictPolicy, League, Matches, Odds, OddsError,
+    OddsSnapshot, OverUnderOdds, RestoreCount, RestoreReport, Team,
+};
+
+use crate::{BookMakerId, LeagueId, MatchId, OddId, TeamId};
+
+/// how an archived row is restored
+#[derive(Debug)]
+pub(crate) enum RowAction<T> {
+    /// insert the row
+    Create(T),
+    /// update the stored row of the id of the row
+    Overwrite(T),
+    /// keep the stored row of the id
+    Skip(i32),
+}
+
+/// the stored rows of a restore by their natural key and the ids of the archived rows
+/// in the database restored into, the rows are restored parents first so the ids of
+/// the parents are known when their children are remapped
+pub(crate) struct Restore {
+    policy: ConflictPolicy,
+    pub report: RestoreReport,
+    bookmakers: HashMap<String, BookMakerId>,
+    leagues: HashMap<String, LeagueId>,
+    teams: HashMap<String, TeamId>,
+    matches: HashMap<(LeagueId, TeamId, TeamId, NaiveDateTime), MatchId>,
+    odds: HashMap<(MatchId, BookMakerId), OddId>,
+    snapshots: HashMap<(MatchId, BookMakerId, NaiveDateTime), i32>,
+    asian_handicaps: HashMap<(MatchId, BookMakerId), OddId>,
+    over_unders: HashMap<(MatchId, BookMakerId), OddId>,
+    bookmaker_ids: HashMap<BookMakerId, BookMakerId>,
+    league_ids: HashMap<LeagueId, LeagueId>,
+    team_ids: HashMap<TeamId, TeamId>,
+    match_ids: HashMap<MatchId, MatchId>,
+}
+
+impl Restore {
+    /// a restore into the database whose rows are stored
+    pub fn new(policy: ConflictPolicy, stored: &Archive) -> Self {
+        Self {
+            policy,
+            report: RestoreReport::default(),
+            bookmakers: stored
+                .bookmakers
+                .iter()
+                .map(|b| (b.name.clone(), b.id))
+                .collect(),
+            leagues: stored
+                .leagues
+                .iter()
+                .map(|l| (l.name.clone(), l.id))
+                .collect(),
+            teams: stored
+                .teams
+                .iter()
+                .map(|t| (t.name.clone(), t.id))
+                .collect(),
+            matches: stored
+                .matches
+                .iter()
+                .map(|m| (match_key(m), m.id))
+                .collect(),
+            odds: stored
+                .odds
+                .iter()
+                .map(|o| ((o.match_id, o.bookmaker_id), o.id))
+                .collect(),
+            snapshots: stored
+                .snapshots
+                .iter()
+                .map(|s| ((s.match_id, s.bookmaker_id, s.recorded_at), s.id))
+                .collect(),
+            asian_handicaps: stored
+                .asian_handicaps
+                .iter()
+                .map(|o| ((o.match_id, o.bookmaker_id), o.id))
+                .collect(),
+            over_unders: stored
+                .over_unders
+                .iter()
+                .map(|o| ((o.match_id, o.bookmaker_id), o.id))
+                .collect(),
+            bookmaker_ids: HashMap::new(),
+            league_ids: HashMap::new(),
+            team_ids: HashMap::new(),
+            match_ids: HashMap::new(),
+        }
+    }
+
+    pub fn bookmaker(&mut self, bookmaker: &BookMaker) -> Result<RowAction<BookMaker>, OddsError> {
+        let stored = self.bookmakers.get(&bookmaker.name).copied();
+        row_action(
+            self.policy,
+            &mut self.report.bookmakers,
+            stored,
+            || format!("bookmaker {}", bookmaker.name),
+            |id| BookMaker {
+                id,
+                ..bookmaker.clone()
+            },
+        )
+    }
+
+    pub fn bookmaker_restored(&mut self, bookmaker: &BookMaker, id: BookMakerId) {
+        self.bookmakers.insert(bookmaker.name.clone(), id);
+        self.bookmaker_ids.insert(bookmaker.id, id);
+    }
+
+    pub fn league(&mut self, league: &League) -> Result<RowAction<League>, OddsError> {
+        let stored = self.leagues.get(&league.name).copied();
+        row_action(
+            self.policy,
+            &mut self.report.leagues,
+            stored,
+            || format!("league {}", league.name),
+            |id| League {
+                id,
+                ..league.clone()
+            },
+        )
+    }
+
+    pub fn league_restored(&mut self, league: &League, id: LeagueId) {
+        self.leagues.insert(league.name.clone(), id);
+        self.league_ids.insert(league.id, id);
+    }
+
+    /// the team in the league it is restored with
+    pub fn team(&mut self, team: &Team) -> Result<RowAction<Team>, OddsError> {
+        let league_id = remap(&self.league_ids, "league", team.league_id)?;
+        let stored = self.teams.get(&team.name).copied();
+        row_action(
+            self.policy,
+            &mut self.report.teams,
+            stored,
+            || format!("team {}", team.name),
+            |id| Team {
+                id,
+                league_id,
+                ..team.clone()
+            },
+        )
+    }
+
+    pub fn team_restored(&mut self, team: &Team, id: TeamId) {
+        self.teams.insert(team.name.clone(), id);
+        self.team_ids.insert(team.id, id);
+    }
+
+    /// the match with the league and teams it is restored with, a match of the same
+    /// league, teams and kickoff is the same match
+    pub fn matches(&mut self, matches: &Matches) -> Result<RowAction<Matches>, OddsError> {
+        let remapped = Matches {
+            league_id: remap(&self.league_ids, "league", matches.league_id)?,
+            home_team_id: remap(&self.team_ids, "team", matches.home_team_id)?,
+            away_team_id: remap(&self.team_ids, "team", matches.away_team_id)?,
+            ..matches.clone()
+        };
+        let stored = self.matches.get(&match_key(&remapped)).copied();
+        let describe = || {
+            format!(
+                "match {} - {} at {}",
+                matches.home_team.as_deref().unwrap_or_default(),
+                matches.away_team.as_deref().unwrap_or_default(),
+                matches.game_time.unwrap_or_default()
+            )
+        };
+        row_action(
+            self.policy,
+            &mut self.report.matches,
+            stored,
+            describe,
+            |id| Matches { id, ..remapped },
+        )
+    }
+
+    pub fn matches_restored(&mut self, matches: &Matches, id: MatchId) {
+        let key = (
+            self.league_ids[&matches.league_id],
+            self.team_ids[&matches.home_team_id],
+            self.team_ids[&matches.away_team_id],
+            matches.game_time.unwrap_or_default(),
+        );
+        self.matches.insert(key, id);
+        self.match_ids.insert(matches.id, id);
+    }
+
+    /// the odds with the match and bookmaker they are restored with, odds of the same
+    /// match and bookmaker are the same odds when they are stored before the restore,
+    /// the archived ones are all created as a bookmaker may have several odds of a match
+    pub fn odds(&mut self, odd: &Odds) -> Result<RowAction<Odds>, OddsError> {
+        let match_id = remap(&self.match_ids, "match", odd.match_id)?;
+        let bookmaker_id = remap(&self.bookmaker_ids, "bookmaker", odd.bookmaker_id)?;
+        let stored = self.odds.get(&(match_id, bookmaker_id)).copied();
+        row_action(
+            self.policy,
+            &mut self.report.odds,
+            stored,
+            || format!("odds of {} for match {}", odd.bookmaker_name, match_id),
+            |id| Odds {
+                id,
+                match_id,
+                bookmaker_id,
+                ..odd.clone()
+            },
+        )
+    }
+
+    /// the snapshot with the match and bookmaker it is restored with, a snapshot of the
+    /// same match, bookmaker and recording time is the same snapshot
+    pub fn snapshot(
+        &mut self,
+        snapshot: &OddsSnapshot,
+    ) -> Result<RowAction<OddsSnapshot>, OddsError> {
+        let match_id = remap(&self.match_ids, "match", snapshot.match_id)?;
+        let bookmaker_id = remap(&self.bookmaker_ids, "bookmaker", snapshot.bookmaker_id)?;
+        let stored = self
+            .snapshots
+            .get(&(match_id, bookmaker_id, snapshot.recorded_at))
+            .copied();
+        row_action(
+            self.policy,
+            &mut self.report.snapshots,
+            stored,
+            || {
+                format!(
+                    "snapshot of bookmaker {} for match {} at {}",
+                    bookmaker_id, match_id, snapshot.recorded_at
+                )
+            },
+            |id| OddsSnapshot {
+                id,
+                match_id,
+                bookmaker_id,
+                ..snapshot.clone()
+            },
+        )
+    }
+
+    /// the asian handicap odds with the match and bookmaker they are restored with,
+    /// matched like the odds
+    pub fn asian_handicap(
+        &mut self,
+        odds: &AsianHandicapOdds,
+    ) -> Result<RowAction<AsianHandicapOdds>, OddsError> {
+        let match_id = remap(&self.match_ids, "match", odds.match_id)?;
+        let bookmaker_id = remap(&self.bookmaker_ids, "bookmaker", odds.bookmaker_id)?;
+        let stored = self.asian_handicaps.get(&(match_id, bookmaker_id)).copied();
+        row_action(
+            self.policy,
+            &mut self.report.asian_handicaps,
+            stored,
+            || {
+                format!(
+                    "asian handicap odds of {} for match {}",
+                    odds.bookmaker_name, match_id
+                )
+            },
+            |id| AsianHandicapOdds {
+                id,
+                match_id,
+                bookmaker_id,
+                ..odds.clone()
+            },
+        )
+    }
+
+    /// the over/under odds with the match and bookmaker they are restored with,
+    /// matched like the odds
+    pub fn over_under(
+        &mut self,
+        odds: &OverUnderOdds,
+    ) -> Result<RowAction<OverUnderOdds>, OddsError> {
+        let match_id = remap(&self.match_ids, "match", odds.match_id)?;
+        let bookmaker_id = remap(&self.bookmaker_ids, "bookmaker", odds.bookmaker_id)?;
+        let stored = self.over_unders.get(&(match_id, bookmaker_id)).copied();
+        row_action(
+            self.policy,
+            &mut self.report.over_unders,
+            stored,
+            || {
+                format!(
+                    "over/under odds of {} for match {}",
+                    odds.bookmaker_name, match_id
+                )
+            },
+            |id| OverUnderOdds {
+                id,
+                match_id,
+                bookmaker_id,
+                ..odds.clone()
+            },
+        )
+    }
+}
+
+/// the action of the policy on an archived row, counted in the table of the row,
+/// the row is given the id of the stored one it overwrites
+fn row_action<T>(
+    policy: ConflictPolicy,
+    count: &mut RestoreCount,
+    stored: Option<i32>,
+    describe: impl FnOnce() -> String,
+    row: impl FnOnce(i32) -> T,
+) -> Result<RowAction<T>, OddsError> {
+    match (stored, policy) {
+        (None, _) => {
+            count.created += 1;
+            Ok(RowAction::Create(row(0)))
+        }
+        (Some(id), ConflictPolicy::Skip) => {
+            count.skipped += 1;
+            Ok(RowAction::Skip(id))
+        }
+        (Some(id), ConflictPolicy::Overwrite) => {
+            count.overwritten += 1;
+            Ok(RowAction::Overwrite(row(id)))
+        }
+        (Some(_), ConflictPolicy::Fail) => Err(OddsError::Conflict(describe())),
+    }
+}
+
+fn match_key(matches: &Matches) -> (LeagueId, TeamId, TeamId, NaiveDateTime) {
+    (
+        matches.league_id,
+        matches.home_team_id,
+        matches.away_team_id,
+        matches.game_time.unwrap_or_default(),
+    )
+}
+
+/// the id an archived id is restored with, which is missing when the archive
+/// refers to a row it does not have
+fn remap(ids: &HashMap<i32, i32>, table: &str, id: i32) -> Result<i32, OddsError> {
+    ids.get(&id)
+        .copied()
+        .ok_or_else(|| OddsError::InvalidArchive(format!("{table} {id} is not in the archive")))
+}
@@ -0,0 +1,97 @@
+use data::{AsianHandicapOdds, Odds, OddsError, OverUnderOdds};
+
+use crate::OddId;
+
+/// odds of a match told apart by their id
+pub trait OddsRow {
+    fn id(&self) -> OddId;
+}
+
+impl OddsRow for Odds {
+    fn id(&self) -> OddId {
+        self.id
+    }
+}
+
+impl OddsRow for AsianHandicapOdds {
+    fn id(&self) -> OddId {
+        self.id
+    }
+}
+
+impl OddsRow for OverUnderOdds {
+    fn id(&self) -> OddId {
+        self.id
+    }
+}
+
+/// changes needed to turn the stored odds of a match into the submitted odds
+#[derive(Debug)]
+pub struct OddsChanges<T = Odds> {
+    pub create: Vec<T>,
+    pub update: Vec<T>,
+    pub delete: Vec<OddId>,
+    /// submitted ids that are not stored for the match
+    pub unknown: Vec<OddId>,
+}
+
+impl<T> OddsChanges<T> {
+    /// the changes when every submitted id is stored for the match,
+    /// odds of another match are not found
+    pub fn known(self) -> Result<Self, OddsError> {
+        if self.unknown.is_empty() {
+            Ok(self)
+        } else {
+            Err(OddsError::NotFound)
+        }
+    }
+}
+
+/// compare the submitted odds of a match with the stored ones, odds without id are created,
+/// odds with a stored id are updated and stored odds that are not submitted are deleted,
+/// submitted odds with an unknown id are left out
+pub fn diff_odds<T: OddsRow>(stored: &[T], submitted: Vec<T>) -> OddsChanges<T> {
+    let mut changes = OddsChanges {
+        create: vec![],
+        update: vec![],
+        delete: vec![],
+        unknown: vec![],
+    };
+    let mut remaining: Vec<OddId> = stored.iter().map(|odd| odd.id()).collect();
+    for odd in submitted {
+        if odd.id() == 0 {
+            changes.create.push(odd);
+        } else if let Some(index) = remaining.iter().position(|id| *id == odd.id()) {
+            remaining.remove(index);
+            changes.update.push(odd);
+        } else {
+            changes.unknown.push(odd.id());
+        }
+    }
+    changes.delete = remaining;
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use data::OddsBuilder;
+
+    use super::*;
+
+    fn odd(id: i32) -> Odds {
+        OddsBuilder::default().id(id).build().unwrap()
+    }
+
+    #[test]
+    fn odds_should_be_diffed_by_id() {
+        let stored = vec![odd(1), odd(2), odd(3)];
+        let submitted = vec![odd(0), odd(2), odd(9), odd(0)];
+        let changes = diff_odds(&stored, submitted);
+        assert_eq!(changes.create.len(), 2);
+        let updated: Vec<OddId> = changes.update.iter().map(|odd| odd.id).collect();
+        assert_eq!(updated, vec![2]);
+        assert_eq!(changes.delete, vec![1, 3]);
+        assert_eq!(changes.unknown, vec![9]);
+        assert!(matches!(changes.known(), Err(OddsError::NotFound)));
+    }
+}
@@ -0,0 +1,215 @@
+use std::collections::HashMap;
+
+use chrono::NaiveDateTime;
+use data::{
+    CursorDirection, MatchCursor, MatchFilter, MatchInfo, Matches, Odds, Page, PredictionFilter,
+};
+use sqlx::{database::HasArguments, Database, Encode, QueryBuilder, Type};
+
+/// full-time result of a match in the 3/1/0 notation, taken from the goals when both are
+/// recorded and from the game result otherwise, a score recorded as the game result
+/// counts through the goals filled in from it
+const RESULT_SQL: &str =
+    "(CASE WHEN matches.home_goals IS NOT NULL AND matches.away_goals IS NOT NULL
+    THEN CASE WHEN matches.home_goals > matches.away_goals THEN '3'
+    WHEN matches.home_goals = matches.away_goals THEN '1' ELSE '0' END
+    ELSE TRIM(matches.game_result) END)";
+
+/// predicted result of a match in the 3/1/0 notation
+const PREDICT_SQL: &str = "TRIM(matches.predict_game_result)";
+
+/// count the matches meeting the filter on every page
+pub(crate) fn match_count_query<'a, DB: Database>(
+    schema: &str,
+    filter: &MatchFilter,
+) -> QueryBuilder<'a, DB>
+where
+    <DB as HasArguments<'a>>::Arguments: Default,
+    i32: Encode<'a, DB> + Type<DB>,
+    i64: Encode<'a, DB> + Type<DB>,
+    String: Encode<'a, DB> + Type<DB>,
+    NaiveDateTime: Encode<'a, DB> + Type<DB>,
+{
+    let mut builder = QueryBuilder::new(format!(
+        "SELECT COUNT(*) FROM {schema}matches matches WHERE 1 = 1"
+    ));
+    push_conditions(&mut builder, schema, filter);
+    builder
+}
+
+/// build the page of matches meeting the filter after or before the cursor, one more
+/// match than the page size is fetched to tell whether the scan goes on
+pub(crate) fn match_query<'a, DB: Database>(
+    schema: &str,
+    filter: &MatchFilter,
+    cursor: Option<&MatchCursor>,
+) -> QueryBuilder<'a, DB>
+where
+    <DB as HasArguments<'a>>::Arguments: Default,
+    i32: Encode<'a, DB> + Type<DB>,
+    i64: Encode<'a, DB> + Type<DB>,
+    String: Encode<'a, DB> + Type<DB>,
+    NaiveDateTime: Encode<'a, DB> + Type<DB>,
+{
+    let mut builder = QueryBuilder::new(format!(
+        "SELECT matches.* FROM {schema}matches matches WHERE 1 = 1"
+    ));
+    push_conditions(&mut builder, schema, filter);
+
+    let desc = scan_desc(filter, cursor);
+    if let Some(cursor) = cursor {
+        let compare = if desc { "<" } else { ">" };
+        builder
+            .push(format!(" AND (matches.game_time {compare} "))
+            .push_bind(cursor.game_time)
+            .push(" OR (matches.game_time = ")
+            .push_bind(cursor.game_time)
+            .push(format!(" AND matches.id {compare} "))
+            .push_bind(cursor.id)
+            .push("))");
+    }
+    let order = if desc { "DESC" } else { "ASC" };
+    builder
+        .push(format!(
+            " ORDER BY matches.game_time {order}, matches.id {order} LIMIT "
+        ))
+        .push_bind(filter.limit() as i64 + 1);
+    builder
+}
+
+/// the conditions of the filter, the bookmaker condition is a semi-join on the odds table
+/// so a match is listed once, `schema` prefixes the table names
+fn push_conditions<'a, DB: Database>(
+    builder: &mut QueryBuilder<'a, DB>,
+    schema: &str,
+    filter: &MatchFilter,
+) where
+    i32: Encode<'a, DB> + Type<DB>,
+    String: Encode<'a, DB> + Type<DB>,
+    NaiveDateTime: Encode<'a, DB> + Type<DB>,
+{
+    if let Some(id) = filter.bookmaker_id {
+        builder
+            .push(format!(
+                " AND EXISTS (SELECT 1 FROM {schema}odds odds
+                WHERE odds.match_id = matches.id AND odds.bookmaker_id = "
+            ))
+            .push_bind(id)
+            .push(")");
+    }
+    if let Some(id) = filter.league_id {
+        builder.push(" AND matches.league_id = ").push_bind(id);
+    }
+    if let Some(id) = filter.team_id {
+        builder
+            .push(" AND (matches.home_team_id = ")
+            .push_bind(id)
+            .push(" OR matches.away_team_id = ")
+            .push_bind(id)
+            .push(")");
+    }
+    if let Some(year) = &filter.game_year {
+        builder
+            .push(" AND matches.game_year = ")
+            .push_bind(year.clone());
+    }
+    if let Some(round) = &filter.game_round {
+        builder
+            .push(" AND matches.game_round = ")
+            .push_bind(round.clone());
+    }
+    if let Some(time) = filter.start_time {
+        builder.push(" AND matches.game_time >= ").push_bind(time);
+    }
+    if let Some(time) = filter.end_time {
+        builder.push(" AND matches.game_time < ").push_bind(time);
+    }
+    if let Some(result) = filter.result {
+        builder
+            .push(format!(" AND {RESULT_SQL} = "))
+            .push_bind(result.game_result().to_string());
+    }
+    if let Some(prediction) = filter.prediction {
+        let compare = match prediction {
+            PredictionFilter::Hit => "=",
+            PredictionFilter::Miss => "<>",
+        };
+        builder.push(format!(
+            " AND {PREDICT_SQL} IN ('3', '1', '0') AND {RESULT_SQL} IN ('3', '1', '0')
+            AND {PREDICT_SQL} {compare} {RESULT_SQL}"
+        ));
+    }
+}
+
+/// whether the matches are scanned from the latest kickoff, a page before the cursor
+/// is scanned against the order of the filter and turned around afterwards
+pub(crate) fn scan_desc(filter: &MatchFilter, cursor: Option<&MatchCursor>) -> bool {
+    let backward = cursor.is_some_and(|cursor| cursor.direction == CursorDirection::Before);
+    filter.is_desc != backward
+}
+
+/// cut the scanned matches to a page in the order of the filter and attach the cursors
+pub(crate) fn match_page(
+    filter: &MatchFilter,
+    cursor: Option<&MatchCursor>,
+    mut matches: Vec<Matches>,
+    total: i64,
+) -> Page<Matches> {
+    let (has_prev, has_next) = cut_page(filter, cursor, &mut matches);
+
+    Page {
+        next_cursor: matches
+            .last()
+            .filter(|_| has_next)
+            .map(|m| MatchCursor::new(m, CursorDirection::After).encode()),
+        prev_cursor: matches
+            .first()
+            .filter(|_| has_prev)
+            .map(|m| MatchCursor::new(m, CursorDirection::Before).encode()),
+        items: matches,
+        total,
+    }
+}
+
+/// cut the scanned matches to a page in the order of the filter
+pub(crate) fn page_items(
+    filter: &MatchFilter,
+    cursor: Option<&MatchCursor>,
+    mut matches: Vec<Matches>,
+) -> Vec<Matches> {
+    cut_page(filter, cursor, &mut matches);
+    matches
+}
+
+/// returns whether there are pages before and after the cut one
+fn cut_page(
+    filter: &MatchFilter,
+    cursor: Option<&MatchCursor>,
+    matches: &mut Vec<Matches>,
+) -> (bool, bool) {
+    let has_more = matches.len() > filter.limit() as usize;
+    matches.truncate(filter.limit() as usize);
+    match cursor.map(|cursor| cursor.direction) {
+        None => (false, has_more),
+        Some(CursorDirection::After) => (true, has_more),
+        Some(CursorDirection::Before) => {
+            matches.reverse();
+            (has_more, true)
+        }
+    }
+}
+
+/// pair every match with its odds, the order of the matches and of the odds is kept
+pub(crate) fn match_infos(matches: Vec<Matches>, odds: Vec<Odds>) -> Vec<MatchInfo> {
+    let mut odds_by_match: HashMap<i32, Vec<Odds>> = HashMap::new();
+    for odd in odds {
+        odds_by_match.entry(odd.match_id).or_default().push(odd);
+    }
+    matches
+        .into_iter()
+        .map(|matches| {
+            let odds = odds_by_match.remove(&matches.id).unwrap_or_default();
+            MatchInfo::new(matches, odds)
+        })
+        .collect()
+}
@@ -0,0 +1,328 @@
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime};
+use csv::StringRecord;
+use data::{FootballDataConfig, ImportRowError, ImportedOdds, MatchImportRow, Outcome};
+
+use crate::import::{price, score};
+
+/// the column of a season csv under its older name as well
+const COLUMN_ALIASES: [(&str, &str); 4] = [
+    ("HomeTeam", "HT"),
+    ("AwayTeam", "AT"),
+    ("FTHG", "HG"),
+    ("FTAG", "AG"),
+];
+
+/// read a football-data.co.uk season csv, a line per match with the `Div`, `Date`, `Time`,
+/// `HomeTeam`, `AwayTeam`, `FTHG`, `FTAG`, `FTR`, `HTHG` and `HTAG` columns and the prices
+/// of the bookmakers in the columns of their prefix, a bookmaker with opening prices but
+/// no closing ones closes at the opening prices, the season is taken from the date
+pub fn read_football_data_csv(
+    text: &str,
+    config: &FootballDataConfig,
+) -> Result<Vec<MatchImportRow>, Vec<ImportRowError>> {
+    let text = text.trim_start_matches('\u{feff}');
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .flexible(true)
+        .from_reader(text.as_bytes());
+    let headers = reader
+        .headers()
+        .map_err(|err| vec![ImportRowError::new(1, err)])?;
+    let columns = Columns::new(headers);
+    let missing: Vec<ImportRowError> = ["Div", "Date", "HomeTeam", "AwayTeam"]
+        .into_iter()
+        .filter(|column| !columns.contains(column))
+        .map(|column| ImportRowError::new(1, format!("missing column {column}")))
+        .collect();
+    if !missing.is_empty() {
+        return Err(missing);
+    }
+
+    let mut rows = vec![];
+    let mut errors = vec![];
+    for record in reader.records() {
+        let row = record
+            .map_err(|err| {
+                let line = err.position().map_or(0, |p| p.line() as usize);
+                ImportRowError::new(line, err)
+            })
+            .and_then(|record| {
+                let line = record.position().map_or(0, |p| p.line() as usize);
+                columns
+                    .row(line, &record, config)
+                    .map_err(|message| ImportRowError::new(line, message))
+            });
+        match row {
+            Ok(Some(row)) => rows.push(row),
+            Ok(None) => {}
+            Err(error) => errors.push(error),
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(rows)
+    } else {
+        Err(errors)
+    }
+}
+
+/// the index of every column of a season csv by name
+struct Columns {
+    index: HashMap<String, usize>,
+}
+
+impl Columns {
+    fn new(headers: &StringRecord) -> Self {
+        let mut index: HashMap<String, usize> = headers
+            .iter()
+            .enumerate()
+            .map(|(i, header)| (header.to_string(), i))
+            .collect();
+        for (column, alias) in COLUMN_ALIASES {
+            if let (None, Some(i)) = (index.get(column), index.get(alias).copied()) {
+                index.insert(column.to_string(), i);
+            }
+        }
+        Self { index }
+    }
+
+    fn contains(&self, column: &str) -> bool {
+        self.index.contains_key(column)
+    }
+
+    /// the value of a column, None when the column or the value is missing
+    fn get(&self, record: &StringRecord, column: &str) -> Option<String> {
+        self.index
+            .get(column)
+            .and_then(|i| record.get(*i))
+            .filter(|value| !value.is_empty())
+            .map(str::to_string)
+    }
+
+    /// the match of a line, None for the empty lines at the end of some files
+    fn row(
+        &self,
+        line: usize,
+        record: &StringRecord,
+        config: &FootballDataConfig,
+    ) -> Result<Option<MatchImportRow>, String> {
+        if record.iter().all(str::is_empty) {
+            return Ok(None);
+        }
+        let required = |column: &str| {
+            self.get(record, column)
+                .ok_or_else(|| format!("{column} is required"))
+        };
+        let renamed = |names: &BTreeMap<String, String>, name: String| {
+            names.get(&name).cloned().unwrap_or(name)
+        };
+
+        let date = required("Date")?;
+        let date = parse_date(&date)
+            .ok_or_else(|| format!("Date is not a date like 01/04/2023: {date}"))?;
+        let time = match self.get(record, "Time") {
+            Some(time) => NaiveTime::parse_from_str(&time, "%H:%M")
+                .map_err(|_| format!("Time is not a time like 20:00: {time}"))?,
+            None => NaiveTime::default(),
+        };
+        let (home_goals, away_goals) = score(
+            ("FTHG", self.get(record, "FTHG")),
+            ("FTAG", self.get(record, "FTAG")),
+        )?;
+        let (half_home_goals, half_away_goals) = score(
+            ("HTHG", self.get(record, "HTHG")),
+            ("HTAG", self.get(record, "HTAG")),
+        )?;
+        let result = match self.get(record, "FTR").as_deref() {
+            Some("H") => Some(Outcome::HomeWin),
+            Some("D") => Some(Outcome::Draw),
+            Some("A") => Some(Outcome::AwayWin),
+            Some(result) => return Err(format!("FTR is not one of H, D and A: {result}")),
+            None => None,
+        };
+
+        let mut odds = vec![];
+        for (prefix, bookmaker) in &config.bookmakers {
+            if let Some(odd) = self.odds(record, prefix, bookmaker)? {
+                odds.push(odd);
+            }
+        }
+
+        Ok(Some(MatchImportRow {
+            line,
+            league: renamed(&config.leagues, required("Div")?),
+            home_team: renamed(&config.teams, required("HomeTeam")?),
+            away_team: renamed(&config.teams, required("AwayTeam")?),
+            game_time: NaiveDateTime::new(date, time),
+            game_year: Some(season(date)),
+            game_round: None,
+            home_goals,
+            away_goals,
+            half_home_goals,
+            half_away_goals,
+            result,
+            odds,
+        }))
+    }
+
+    /// the prices of a bookmaker prefix, None when the line has no opening prices of it
+    fn odds(
+        &self,
+        record: &StringRecord,
+        prefix: &str,
+        bookmaker: &str,
+    ) -> Result<Option<ImportedOdds>, String> {
+        let prices = |infix: &str| {
+            ["H", "D", "A"].map(|outcome| {
+                let column = format!("{prefix}{infix}{outcome}");
+                let value = self.get(record, &column);
+                (column, value)
+            })
+        };
+        let start = prices("");
+        if start.iter().all(|(_, value)| value.is_none()) {
+            return Ok(None);
+        }
+        let end = prices("C");
+        let end = if end.iter().all(|(_, value)| value.is_none()) {
+            start.clone()
+        } else {
+            end
+        };
+
+        let [home_win_start, draw_start, away_win_start] = start;
+        let [home_win_end, draw_end, away_win_end] = end;
+        Ok(Some(ImportedOdds {
+            bookmaker: bookmaker.to_string(),
+            home_win_start: price(&home_win_start.0, home_win_start.1)?,
+            draw_start: price(&draw_start.0, draw_start.1)?,
+            away_win_start: price(&away_win_start.0, away_win_start.1)?,
+            home_win_end: price(&home_win_end.0, home_win_end.1)?,
+            draw_end: price(&draw_end.0, draw_end.1)?,
+            away_win_end: price(&away_win_end.0, away_win_end.1)?,
+        }))
+    }
+}
+
+/// the season of a match day like 22/23, a season starts in july
+fn season(date: NaiveDate) -> String {
+    let start = if date.month() >= 7 {
+        date.year()
+    } else {
+        date.year() - 1
+    };
+    format!("{:02}/{:02}", start % 100, (start + 1) % 100)
+}
+
+/// a dd/mm/yyyy date, or dd/mm/yy in the older season files, a four digit format
+/// would read the two digits of 22 as the year 22
+fn parse_date(date: &str) -> Option<NaiveDate> {
+    let format = match date.rsplit('/').next() {
+        Some(year) if year.len() == 2 => "%d/%m/%y",
+        _ => "%d/%m/%Y",
+    };
+    NaiveDate::parse_from_str(date, format).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use bigdecimal::BigDecimal;
+
+    use super::*;
+
+    const CSV: &str = "\u{feff}Div,Date,Time,HomeTeam,AwayTeam,FTHG,FTAG,FTR,HTHG,HTAG,HTR,B365H,B365D,B365A,BWH,BWD,BWA,PSH,PSD,PSA,PSCH,PSCD,PSCA
+E0,05/08/2022,20:00,Crystal Palace,Arsenal,0,2,A,0,1,A,4.2,3.6,1.85,4.1,3.5,1.85,4.5,3.65,1.89,4.6,3.6,1.88
+E0,06/08/2022,12:30,Fulham,Liverpool,2,2,D,1,0,H,11,6,1.25,,,,11.5,6.2,1.27,12,6.5,1.25
+,,,,,,,,,,,,,,,,,,,,,,
+";
+
+    fn decimal(value: &str) -> BigDecimal {
+        BigDecimal::from_str(value).unwrap()
+    }
+
+    #[test]
+    fn season_csv_should_be_read() {
+        let mut config = FootballDataConfig::default();
+        config.leagues.insert("E0".into(), "英超".into());
+        config.teams.insert("Arsenal".into(), "阿森纳".into());
+        let rows = read_football_data_csv(CSV, &config).unwrap();
+        assert_eq!(rows.len(), 2);
+
+        let row = &rows[0];
+        assert_eq!(row.line, 2);
+        assert_eq!(row.league, "英超");
+        assert_eq!(
+            (row.home_team.as_str(), row.away_team.as_str()),
+            ("Crystal Palace", "阿森纳")
+        );
+        assert_eq!(row.game_time.to_string(), "2022-08-05 20:00:00");
+        assert_eq!(row.game_year.as_deref(), Some("22/23"));
+        assert_eq!((row.home_goals, row.away_goals), (Some(0), Some(2)));
+        assert_eq!(
+            (row.half_home_goals, row.half_away_goals),
+            (Some(0), Some(1))
+        );
+        assert_eq!(row.result, Some(Outcome::AwayWin));
+        let bookmakers: Vec<&str> = row.odds.iter().map(|o| o.bookmaker.as_str()).collect();
+        assert_eq!(bookmakers, vec!["Bet365", "Bet&Win", "Pinnacle"]);
+        // the closing prices of bet365 are not in the file
+        assert_eq!(row.odds[0].home_win_end, decimal("4.2"));
+        assert_eq!(row.odds[2].home_win_start, decimal("4.5"));
+        assert_eq!(row.odds[2].home_win_end, decimal("4.6"));
+
+        // bet&win has no prices for the second match
+        let bookmakers: Vec<&str> = rows[1].odds.iter().map(|o| o.bookmaker.as_str()).collect();
+        assert_eq!(bookmakers, vec!["Bet365", "Pinnacle"]);
+    }
+
+    #[test]
+    fn two_digit_years_should_be_read() {
+        let csv = "Div,Date,HomeTeam,AwayTeam,FTHG,FTAG,FTR,PSH,PSD,PSA
+E0,06/08/22,Fulham,Liverpool,2,2,D,11.5,6.2,1.27
+";
+        let rows = read_football_data_csv(csv, &FootballDataConfig::default()).unwrap();
+        assert_eq!(rows[0].game_time.date().year(), 2022);
+        assert_eq!(rows[0].game_year.as_deref(), Some("22/23"));
+        assert_eq!(
+            parse_date("06/08/2022"),
+            NaiveDate::from_ymd_opt(2022, 8, 6)
+        );
+    }
+
+    #[test]
+    fn bookmakers_should_be_configured() {
+        let config = FootballDataConfig {
+            bookmakers: [("PS".to_string(), "平博".to_string())].into(),
+            ..FootballDataConfig::default()
+        };
+        let rows = read_football_data_csv(CSV, &config).unwrap();
+        assert_eq!(rows[0].league, "E0");
+        assert_eq!(rows[0].odds.len(), 1);
+        assert_eq!(rows[0].odds[0].bookmaker, "平博");
+    }
+
+    #[test]
+    fn bad_lines_should_be_reported() {
+        let csv = "Div,Date,HomeTeam,AwayTeam,FTHG,FTAG,FTR,PSH,PSD,PSA
+E0,2022-08-05,Crystal Palace,Arsenal,0,2,A,4.5,3.65,1.89
+E0,06/08/22,Fulham,Liverpool,2,,D,11.5,6.2,1.27
+E0,06/08/22,Bournemouth,Aston Villa,2,0,X,2.9,3.3,2.5
+E0,06/08/22,Leeds,Wolves,2,1,H,2.1,,3.6
+E0,06/08/22,Newcastle,Nott'm Forest,2,0,H,1.6,4.0,6.0
+";
+        let errors = read_football_data_csv(csv, &FootballDataConfig::default()).unwrap_err();
+        let lines: Vec<usize> = errors.iter().map(|error| error.line).collect();
+        assert_eq!(lines, vec![2, 3, 4, 5]);
+
+        let errors = read_football_data_csv("Div,Date,HomeTeam\n", &FootballDataConfig::default())
+            .unwrap_err();
+        assert_eq!(
+            errors,
+            vec![ImportRowError::new(1, "missing column AwayTeam")]
+        );
+    }
+}
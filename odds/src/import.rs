@@ -0,0 +1,558 @@
+use std::{collections::HashMap, str::FromStr};
+
+use bigdecimal::BigDecimal;
+use chrono::NaiveDateTime;
+use data::{
+    BookMaker, BookMakerBuilder, ImportReport, ImportRowError, ImportedOdds, League, LeagueBuilder,
+    MatchImportRow, MatchInfo, Matches, MatchesBuilder, Odds, OddsBuilder, OddsError, Outcome,
+    Team, TeamBuilder,
+};
+use serde::Deserialize;
+
+/// the columns a match csv has to have, the other columns are optional
+const REQUIRED_COLUMNS: [&str; 4] = ["league", "game_time", "home_team", "away_team"];
+
+const GAME_TIME_FORMATS: [&str; 2] = ["%Y-%m-%d %H:%M:%S", "%Y-%m-%d %H:%M"];
+
+/// a line of a match csv as written
+#[derive(Debug, Deserialize)]
+struct CsvRecord {
+    league: Option<String>,
+    game_time: Option<String>,
+    home_team: Option<String>,
+    away_team: Option<String>,
+    game_year: Option<String>,
+    game_round: Option<String>,
+    home_goals: Option<String>,
+    away_goals: Option<String>,
+    half_home_goals: Option<String>,
+    half_away_goals: Option<String>,
+    bookmaker: Option<String>,
+    home_win_start: Option<String>,
+    draw_start: Option<String>,
+    away_win_start: Option<String>,
+    home_win_end: Option<String>,
+    draw_end: Option<String>,
+    away_win_end: Option<String>,
+}
+
+/// read a csv with a header line and a line per match and bookmaker, the columns are
+///
+/// `league, game_time, home_team, away_team, game_year, game_round, home_goals, away_goals,
+/// half_home_goals, half_away_goals, bookmaker, home_win_start, draw_start, away_win_start,
+/// home_win_end, draw_end, away_win_end`
+///
+/// lines of the same match repeat the match columns with another bookmaker, a line
+/// without bookmaker imports the match alone, the error of every bad line is returned
+pub fn read_match_csv(text: &str) -> Result<Vec<MatchImportRow>, Vec<ImportRowError>> {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .flexible(true)
+        .from_reader(text.as_bytes());
+    let headers = reader
+        .headers()
+        .map_err(|err| vec![ImportRowError::new(1, err)])?
+        .clone();
+    let missing: Vec<ImportRowError> = REQUIRED_COLUMNS
+        .iter()
+        .filter(|column| !headers.iter().any(|header| header == **column))
+        .map(|column| ImportRowError::new(1, format!("missing column {column}")))
+        .collect();
+    if !missing.is_empty() {
+        return Err(missing);
+    }
+
+    let mut rows = vec![];
+    let mut errors = vec![];
+    for record in reader.records() {
+        let row = record.map_err(|err| {
+            let line = err.position().map_or(0, |p| p.line() as usize);
+            ImportRowError::new(line, err)
+        });
+        let row = row.and_then(|record| {
+            let line = record.position().map_or(0, |p| p.line() as usize);
+            record
+                .deserialize::<CsvRecord>(Some(&headers))
+                .map_err(|err| err.to_string())
+                .and_then(|record| match_row(line, record))
+                .map_err(|message| ImportRowError::new(line, message))
+        });
+        match row {
+            Ok(row) => rows.push(row),
+            Err(error) => errors.push(error),
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(rows)
+    } else {
+        Err(errors)
+    }
+}
+
+fn match_row(line: usize, record: CsvRecord) -> Result<MatchImportRow, String> {
+    let game_time = required("game_time", record.game_time)?;
+    let game_time = GAME_TIME_FORMATS
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(&game_time, format).ok())
+        .ok_or_else(|| format!("game_time is not a time like 2023-04-01 20:00: {game_time}"))?;
+    let (home_goals, away_goals) = score(
+        ("home_goals", record.home_goals),
+        ("away_goals", record.away_goals),
+    )?;
+    let (half_home_goals, half_away_goals) = score(
+        ("half_home_goals", record.half_home_goals),
+        ("half_away_goals", record.half_away_goals),
+    )?;
+
+    let odds = match record.bookmaker {
+        Some(bookmaker) => vec![ImportedOdds {
+            bookmaker,
+            home_win_start: price("home_win_start", record.home_win_start)?,
+            draw_start: price("draw_start", record.draw_start)?,
+            away_win_start: price("away_win_start", record.away_win_start)?,
+            home_win_end: price("home_win_end", record.home_win_end)?,
+            draw_end: price("draw_end", record.draw_end)?,
+            away_win_end: price("away_win_end", record.away_win_end)?,
+        }],
+        None => {
+            let prices = [
+                &record.home_win_start,
+                &record.draw_start,
+                &record.away_win_start,
+                &record.home_win_end,
+                &record.draw_end,
+                &record.away_win_end,
+            ];
+            if prices.iter().any(|price| price.is_some()) {
+                return Err("prices are given without a bookmaker".into());
+            }
+            vec![]
+        }
+    };
+
+    Ok(MatchImportRow {
+        line,
+        league: required("league", record.league)?,
+        home_team: required("home_team", record.home_team)?,
+        away_team: required("away_team", record.away_team)?,
+        game_time,
+        game_year: record.game_year,
+        game_round: record.game_round,
+        home_goals,
+        away_goals,
+        half_home_goals,
+        half_away_goals,
+        result: None,
+        odds,
+    })
+}
+
+fn required(column: &str, value: Option<String>) -> Result<String, String> {
+    value.ok_or_else(|| format!("{column} is required"))
+}
+
+/// the goals of both teams, which are given together or not at all
+pub(crate) fn score(
+    (home_column, home): (&str, Option<String>),
+    (away_column, away): (&str, Option<String>),
+) -> Result<(Option<i32>, Option<i32>), String> {
+    let home = goals(home_column, home)?;
+    let away = goals(away_column, away)?;
+    if home.is_some() != away.is_some() {
+        return Err(format!(
+            "{home_column} and {away_column} have to be given together"
+        ));
+    }
+    Ok((home, away))
+}
+
+fn goals(column: &str, value: Option<String>) -> Result<Option<i32>, String> {
+    value
+        .map(|value| {
+            value
+                .parse::<u16>()
+                .map(i32::from)
+                .map_err(|_| format!("{column} is not a number of goals: {value}"))
+        })
+        .transpose()
+}
+
+/// a decimal price, which pays back more than the stake
+pub(crate) fn price(column: &str, value: Option<String>) -> Result<BigDecimal, String> {
+    let value = required(column, value)?;
+    match BigDecimal::from_str(&value) {
+        Ok(price) if price > BigDecimal::from(1) => Ok(price),
+        _ => Err(format!("{column} is not a price above 1: {value}")),
+    }
+}
+
+/// report an error of the store at the line being imported, with the message of the database
+pub(crate) fn row_error(line: usize) -> impl FnOnce(OddsError) -> ImportRowError {
+    move |err| match err {
+        OddsError::DbError(message) => ImportRowError::new(line, message),
+        err => ImportRowError::new(line, err),
+    }
+}
+
+/// the key a name is resolved by, names differing in case or surrounding spaces are the same
+fn name_key(name: &str) -> String {
+    name.trim().to_lowercase()
+}
+
+/// a league, team or bookmaker missing from the store with the first line naming it
+#[derive(Debug)]
+pub(crate) struct NewName {
+    pub line: usize,
+    pub name: String,
+    /// league of a new team
+    pub league: String,
+}
+
+/// a match to create with the lines of its odds
+#[derive(Debug)]
+pub(crate) struct PlannedMatch {
+    pub row: MatchImportRow,
+    pub odds: Vec<(usize, ImportedOdds)>,
+}
+
+/// what an import creates, the new names are created before the matches
+#[derive(Debug)]
+pub(crate) struct ImportPlan {
+    pub leagues: Vec<NewName>,
+    pub teams: Vec<NewName>,
+    pub bookmakers: Vec<NewName>,
+    pub matches: Vec<PlannedMatch>,
+}
+
+impl ImportPlan {
+    /// the report of the plan once it is carried out
+    pub fn report(&self) -> ImportReport {
+        let names = |names: &[NewName]| names.iter().map(|n| n.name.clone()).collect();
+        ImportReport {
+            matches: self.matches.len(),
+            odds: self.matches.iter().map(|m| m.odds.len()).sum(),
+            created_leagues: names(&self.leagues),
+            created_teams: names(&self.teams),
+            created_bookmakers: names(&self.bookmakers),
+            errors: vec![],
+        }
+    }
+}
+
+/// the leagues, teams and bookmakers of the store by name
+pub(crate) struct ImportNames {
+    leagues: HashMap<String, League>,
+    teams: HashMap<String, Team>,
+    bookmakers: HashMap<String, BookMaker>,
+}
+
+impl ImportNames {
+    pub fn new(leagues: Vec<League>, teams: Vec<Team>, bookmakers: Vec<BookMaker>) -> Self {
+        let mut names = Self {
+            leagues: HashMap::new(),
+            teams: HashMap::new(),
+            bookmakers: HashMap::new(),
+        };
+        leagues
+            .into_iter()
+            .for_each(|league| names.add_league(league));
+        teams.into_iter().for_each(|team| names.add_team(team));
+        bookmakers
+            .into_iter()
+            .for_each(|bookmaker| names.add_bookmaker(bookmaker));
+        names
+    }
+
+    pub fn add_league(&mut self, league: League) {
+        self.leagues.insert(name_key(&league.name), league);
+    }
+
+    pub fn add_team(&mut self, team: Team) {
+        self.teams.insert(name_key(&team.name), team);
+    }
+
+    pub fn add_bookmaker(&mut self, bookmaker: BookMaker) {
+        self.bookmakers.insert(name_key(&bookmaker.name), bookmaker);
+    }
+
+    /// resolve the names of the rows and group the rows into matches, a missing name is
+    /// an error of its rows unless `create_missing` is set, then it is to be created
+    pub fn plan(
+        &self,
+        rows: Vec<MatchImportRow>,
+        create_missing: bool,
+    ) -> Result<ImportPlan, Vec<ImportRowError>> {
+        let mut plan = ImportPlan {
+            leagues: vec![],
+            teams: vec![],
+            bookmakers: vec![],
+            matches: vec![],
+        };
+        let mut errors = vec![];
+        let mut match_index: HashMap<(String, String, String, NaiveDateTime), usize> =
+            HashMap::new();
+
+        for row in rows {
+            let line = row.line;
+            let mut resolve = |kind: &str, name: &str, exists: bool, new: &mut Vec<NewName>| {
+                if exists || new.iter().any(|n| name_key(&n.name) == name_key(name)) {
+                    return;
+                }
+                if create_missing {
+                    new.push(NewName {
+                        line,
+                        name: name.trim().to_string(),
+                        league: row.league.trim().to_string(),
+                    });
+                } else {
+                    errors.push(ImportRowError::new(
+                        line,
+                        format!("{kind} {name} does not exist"),
+                    ));
+                }
+            };
+
+            let league = name_key(&row.league);
+            resolve(
+                "league",
+                &row.league,
+                self.leagues.contains_key(&league),
+                &mut plan.leagues,
+            );
+            for team in [&row.home_team, &row.away_team] {
+                let exists = self.teams.contains_key(&name_key(team));
+                resolve("team", team, exists, &mut plan.teams);
+            }
+            for odds in &row.odds {
+                let exists = self.bookmakers.contains_key(&name_key(&odds.bookmaker));
+                resolve("bookmaker", &odds.bookmaker, exists, &mut plan.bookmakers);
+            }
+            if name_key(&row.home_team) == name_key(&row.away_team) {
+                errors.push(ImportRowError::new(
+                    line,
+                    "home_team and away_team are the same team",
+                ));
+            }
+
+            let key = (
+                league,
+                name_key(&row.home_team),
+                name_key(&row.away_team),
+                row.game_time,
+            );
+            let odds = row.odds.iter().map(|odds| (line, odds.clone())).collect();
+            match match_index.get(&key) {
+                Some(index) => plan.matches[*index].odds.extend(odds),
+                None => {
+                    match_index.insert(key, plan.matches.len());
+                    plan.matches.push(PlannedMatch { row, odds });
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(plan)
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// a league to create for the plan
+    pub fn league(&self, name: &NewName) -> League {
+        LeagueBuilder::default().name(&name.name).build().unwrap()
+    }
+
+    /// a team to create in its league, which is created before the teams
+    pub fn team(&self, name: &NewName) -> Team {
+        let league = &self.leagues[&name_key(&name.league)];
+        TeamBuilder::default()
+            .name(&name.name)
+            .league_id(league.id)
+            .league_name(&league.name)
+            .build()
+            .unwrap()
+    }
+
+    pub fn bookmaker(&self, name: &NewName) -> BookMaker {
+        BookMakerBuilder::default()
+            .name(&name.name)
+            .build()
+            .unwrap()
+    }
+
+    /// the match of the plan with the ids of its names, which all exist by now,
+    /// the result is taken from the goals when the row has them
+    pub fn matches(&self, planned: &PlannedMatch) -> Matches {
+        let row = &planned.row;
+        let league = &self.leagues[&name_key(&row.league)];
+        let home_team = &self.teams[&name_key(&row.home_team)];
+        let away_team = &self.teams[&name_key(&row.away_team)];
+        let mut builder = MatchesBuilder::default();
+        builder
+            .league_id(league.id)
+            .league_name(&league.name)
+            .home_team_id(home_team.id)
+            .home_team(&home_team.name)
+            .away_team_id(away_team.id)
+            .away_team(&away_team.name)
+            .game_time(row.game_time);
+        if let Some(year) = &row.game_year {
+            builder.game_year(year);
+        }
+        if let Some(round) = &row.game_round {
+            builder.game_round(round);
+        }
+        if let (Some(home), Some(away)) = (row.home_goals, row.away_goals) {
+            let outcome = Outcome::from_goals(home as u32, away as u32);
+            builder
+                .home_goals(home)
+                .away_goals(away)
+                .game_result(outcome.game_result());
+        } else if let Some(outcome) = row.result {
+            builder.game_result(outcome.game_result());
+        }
+        if let (Some(home), Some(away)) = (row.half_home_goals, row.half_away_goals) {
+            builder.half_home_goals(home).half_away_goals(away);
+        }
+        builder.build().unwrap()
+    }
+
+    /// the odds of a bookmaker for a match of the plan
+    pub fn odds(&self, odds: &ImportedOdds) -> Odds {
+        let bookmaker = &self.bookmakers[&name_key(&odds.bookmaker)];
+        Odds {
+            bookmaker_id: bookmaker.id,
+            bookmaker_name: bookmaker.name.clone(),
+            home_win_start: odds.home_win_start.clone(),
+            draw_start: odds.draw_start.clone(),
+            away_win_start: odds.away_win_start.clone(),
+            home_win_end: odds.home_win_end.clone(),
+            draw_end: odds.draw_end.clone(),
+            away_win_end: odds.away_win_end.clone(),
+            ..OddsBuilder::default().build().unwrap()
+        }
+    }
+
+    /// the match and odds of a planned match as they are saved
+    pub fn match_info(&self, planned: &PlannedMatch) -> MatchInfo {
+        let odds = planned
+            .odds
+            .iter()
+            .map(|(_, odds)| self.odds(odds))
+            .collect();
+        MatchInfo::new(self.matches(planned), odds)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CSV: &str = "league,game_time,home_team,away_team,game_year,game_round,home_goals,away_goals,bookmaker,home_win_start,draw_start,away_win_start,home_win_end,draw_end,away_win_end
+英超,2023-04-01 20:00,阿森纳,利物浦,22/23,29,2,1,威廉希尔,2.10,3.40,3.30,2.05,3.50,3.40
+英超,2023-04-01 20:00,阿森纳,利物浦,22/23,29,2,1,立博,2.15,3.30,3.25,2.00,3.50,3.50
+英超,2023-04-02 18:30,切尔西,曼联,,,,,,,,,,,
+";
+
+    fn names() -> ImportNames {
+        ImportNames::new(
+            vec![LeagueBuilder::default().id(1).name("英超").build().unwrap()],
+            vec![
+                TeamBuilder::default()
+                    .id(1)
+                    .league_id(1)
+                    .name("阿森纳")
+                    .build()
+                    .unwrap(),
+                TeamBuilder::default()
+                    .id(2)
+                    .league_id(1)
+                    .name("利物浦")
+                    .build()
+                    .unwrap(),
+            ],
+            vec![BookMakerBuilder::default()
+                .id(1)
+                .name("威廉希尔")
+                .build()
+                .unwrap()],
+        )
+    }
+
+    #[test]
+    fn match_csv_should_be_read() {
+        let rows = read_match_csv(CSV).unwrap();
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[0].line, 2);
+        assert_eq!(rows[0].home_goals, Some(2));
+        assert_eq!(rows[0].odds[0].bookmaker, "威廉希尔");
+        assert_eq!(
+            rows[1].odds[0].draw_start,
+            BigDecimal::from_str("3.3").unwrap()
+        );
+        assert_eq!(rows[2].game_year, None);
+        assert!(rows[2].odds.is_empty());
+    }
+
+    #[test]
+    fn bad_lines_should_be_reported() {
+        let errors =
+            read_match_csv("league,game_time,home_team\n英超,2023-04-01,阿森纳\n").unwrap_err();
+        assert_eq!(
+            errors,
+            vec![ImportRowError::new(1, "missing column away_team")]
+        );
+
+        let csv =
+            "league,game_time,home_team,away_team,home_goals,away_goals,bookmaker,home_win_start
+英超,2023-04-01,阿森纳,利物浦,,,,
+英超,2023-04-01 20:00,阿森纳,利物浦,2,,,
+英超,2023-04-01 20:00,阿森纳,利物浦,,,威廉希尔,0.9
+英超,2023-04-01 20:00,阿森纳,利物浦,,,,2.1
+";
+        let lines: Vec<usize> = read_match_csv(csv)
+            .unwrap_err()
+            .iter()
+            .map(|error| error.line)
+            .collect();
+        assert_eq!(lines, vec![2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn rows_should_be_grouped_into_matches() {
+        let rows = read_match_csv(CSV).unwrap();
+        let errors = names().plan(rows.clone(), false).unwrap_err();
+        assert_eq!(
+            errors,
+            vec![
+                ImportRowError::new(3, "bookmaker 立博 does not exist"),
+                ImportRowError::new(4, "team 切尔西 does not exist"),
+                ImportRowError::new(4, "team 曼联 does not exist"),
+            ]
+        );
+
+        let mut names = names();
+        let plan = names.plan(rows, true).unwrap();
+        let report = plan.report();
+        assert!(report.created_leagues.is_empty());
+        assert_eq!(report.created_teams, vec!["切尔西", "曼联"]);
+        assert_eq!(report.created_bookmakers, vec!["立博"]);
+        assert_eq!((report.matches, report.odds), (2, 2));
+
+        for (id, name) in plan.teams.iter().enumerate() {
+            let mut team = names.team(name);
+            team.id = id as i32 + 3;
+            names.add_team(team);
+        }
+        let mut bookmaker = names.bookmaker(&plan.bookmakers[0]);
+        bookmaker.id = 2;
+        names.add_bookmaker(bookmaker);
+        let info = names.match_info(&plan.matches[0]);
+        assert_eq!(info.matches.outcome(), Some(Outcome::HomeWin));
+        assert_eq!(info.odds[1].bookmaker_id, 2);
+        let info = names.match_info(&plan.matches[1]);
+        assert_eq!(info.matches.home_team_id, 3);
+        assert_eq!(info.matches.away_team.as_deref(), Some("曼联"));
+    }
+}
@@ -0,0 +1,1193 @@
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use data::{
+    Archive, AsianHandicapOdds, BookMaker, ConflictPolicy, ImportReport, ImportRowError, League,
+    MatchCursor, MatchFilter, MatchImportRow, MatchInfo, Matches, Odds, OddsBand, OddsBandQuery,
+    OddsError, OddsSnapshot, OverUnderOdds, Page, PredictionScorecard, RestoreReport,
+    ScorecardQuery, SimilarOdds, SimilarOddsQuery, Team, Validate,
+};
+use tokio::sync::RwLock;
+
+use crate::{
+    archive::{Restore, RowAction},
+    diff_odds, effective_tolerances,
+    filter::{match_infos, match_page, page_items, scan_desc},
+    import::{row_error, ImportNames, ImportPlan},
+    now, odds_bands, prediction_scorecard, similar_odds, within_tolerance, BookMakerId, EuropeOdds,
+    LeagueId, MatchId, OddId, TeamId,
+};
+
+/// odds persistence kept in process memory, it mirrors the constraints of the
+/// postgres schema so it can stand in for a database in tests and demos
+#[derive(Default)]
+pub struct InMemoryOdds {
+    state: RwLock<State>,
+}
+
+impl InMemoryOdds {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// rows of one table with the last generated id, ids are never reused like a sequence
+#[derive(Clone)]
+struct Table<T> {
+    rows: Vec<T>,
+    last_id: i32,
+}
+
+impl<T> Default for Table<T> {
+    fn default() -> Self {
+        Self {
+            rows: vec![],
+            last_id: 0,
+        }
+    }
+}
+
+impl<T> Table<T> {
+    fn next_id(&mut self) -> i32 {
+        self.last_id += 1;
+        self.last_id
+    }
+}
+
+#[derive(Clone, Default)]
+struct State {
+    bookmakers: Table<BookMaker>,
+    leagues: Table<League>,
+    teams: Table<Team>,
+    matches: Table<Matches>,
+    odds: Table<Odds>,
+    snapshots: Table<OddsSnapshot>,
+    asian_handicaps: Table<AsianHandicapOdds>,
+    over_unders: Table<OverUnderOdds>,
+}
+
+impl State {
+    /// teams joined with their league, teams of a missing league are left out like the inner join
+    fn team_with_league(&self, team: &Team) -> Option<Team> {
+        let league = self.leagues.rows.iter().find(|l| l.id == team.league_id)?;
+        let mut team = team.clone();
+        team.league_name = Some(league.name.clone());
+        Some(team)
+    }
+
+    fn check_match_exists(&self, id: MatchId, constraint: &str) -> Result<(), OddsError> {
+        if self.matches.rows.iter().any(|m| m.id == id) {
+            Ok(())
+        } else {
+            Err(OddsError::ForeignKeyViolation(constraint.to_string()))
+        }
+    }
+
+    fn insert_bookmaker(&mut self, bookmaker: BookMaker) -> Result<BookMaker, OddsError> {
+        bookmaker.validate()?;
+        if self
+            .bookmakers
+            .rows
+            .iter()
+            .any(|b| b.name == bookmaker.name)
+        {
+            return Err(duplicate_key("bookmakers_name_key"));
+        }
+        let created_at = now();
+        let bookmaker = BookMaker {
+            id: self.bookmakers.next_id(),
+            created_at,
+            updated_at: created_at,
+            ..bookmaker
+        };
+        self.bookmakers.rows.push(bookmaker.clone());
+        Ok(bookmaker)
+    }
+
+    fn insert_league(&mut self, league: League) -> Result<League, OddsError> {
+        league.validate()?;
+        if self.leagues.rows.iter().any(|l| l.name == league.name) {
+            return Err(duplicate_key("leagues_name_key"));
+        }
+        let created_at = now();
+        let league = League {
+            id: self.leagues.next_id(),
+            created_at,
+            updated_at: created_at,
+            ..league
+        };
+        self.leagues.rows.push(league.clone());
+        Ok(league)
+    }
+
+    fn insert_team(&mut self, team: Team) -> Result<Team, OddsError> {
+        team.validate()?;
+        if self.teams.rows.iter().any(|t| t.name == team.name) {
+            return Err(duplicate_key("teams_name_key"));
+        }
+        let created_at = now();
+        let team = Team {
+            id: self.teams.next_id(),
+            league_name: None,
+            created_at,
+            updated_at: created_at,
+            ..team
+        };
+        self.teams.rows.push(team.clone());
+        Ok(team)
+    }
+
+    fn update_bookmaker(&mut self, bookmaker: BookMaker) -> Result<(), OddsError> {
+        bookmaker.validate()?;
+        let index = self
+            .bookmakers
+            .rows
+            .iter()
+            .position(|b| b.id == bookmaker.id)
+            .ok_or(OddsError::NotFound)?;
+        if self
+            .bookmakers
+            .rows
+            .iter()
+            .any(|b| b.id != bookmaker.id && b.name == bookmaker.name)
+        {
+            return Err(duplicate_key("bookmakers_name_key"));
+        }
+        let stored = &mut self.bookmakers.rows[index];
+        stored.name = bookmaker.name;
+        stored.url = bookmaker.url;
+        stored.note = bookmaker.note;
+        Ok(())
+    }
+
+    fn update_league(&mut self, league: League) -> Result<(), OddsError> {
+        league.validate()?;
+        let index = self
+            .leagues
+            .rows
+            .iter()
+            .position(|l| l.id == league.id)
+            .ok_or(OddsError::NotFound)?;
+        if self
+            .leagues
+            .rows
+            .iter()
+            .any(|l| l.id != league.id && l.name == league.name)
+        {
+            return Err(duplicate_key("leagues_name_key"));
+        }
+        let stored = &mut self.leagues.rows[index];
+        stored.name = league.name;
+        stored.note = league.note;
+        Ok(())
+    }
+
+    fn update_team(&mut self, team: Team) -> Result<(), OddsError> {
+        team.validate()?;
+        let index = self
+            .teams
+            .rows
+            .iter()
+            .position(|t| t.id == team.id)
+            .ok_or(OddsError::NotFound)?;
+        if self
+            .teams
+            .rows
+            .iter()
+            .any(|t| t.id != team.id && t.name == team.name)
+        {
+            return Err(duplicate_key("teams_name_key"));
+        }
+        let stored = &mut self.teams.rows[index];
+        stored.name = team.name;
+        stored.league_id = team.league_id;
+        stored.note = team.note;
+        Ok(())
+    }
+
+    /// restore the rows of an archive parents first
+    fn restore(&mut self, archive: &Archive, restore: &mut Restore) -> Result<(), OddsError> {
+        for bookmaker in &archive.bookmakers {
+            let id = match restore.bookmaker(bookmaker)? {
+                RowAction::Create(row) => self.insert_bookmaker(row)?.id,
+                RowAction::Overwrite(row) => {
+                    let id = row.id;
+                    self.update_bookmaker(row)?;
+                    id
+                }
+                RowAction::Skip(id) => id,
+            };
+            restore.bookmaker_restored(bookmaker, id);
+        }
+        for league in &archive.leagues {
+            let id = match restore.league(league)? {
+                RowAction::Create(row) => self.insert_league(row)?.id,
+                RowAction::Overwrite(row) => {
+                    let id = row.id;
+                    self.update_league(row)?;
+                    id
+                }
+                RowAction::Skip(id) => id,
+            };
+            restore.league_restored(league, id);
+        }
+        for team in &archive.teams {
+            let id = match restore.team(team)? {
+                RowAction::Create(row) => self.insert_team(row)?.id,
+                RowAction::Overwrite(row) => {
+                    let id = row.id;
+                    self.update_team(row)?;
+                    id
+                }
+                RowAction::Skip(id) => id,
+            };
+            restore.team_restored(team, id);
+        }
+        for matches in &archive.matches {
+            let id = match restore.matches(matches)? {
+                RowAction::Create(row) => self.insert_match(&row)?.id,
+                RowAction::Overwrite(row) => self.update_match(&row)?.id,
+                RowAction::Skip(id) => id,
+            };
+            restore.matches_restored(matches, id);
+        }
+        for odd in &archive.odds {
+            match restore.odds(odd)? {
+                RowAction::Create(row) => {
+                    self.insert_odds(row.match_id, &row)?;
+                }
+                RowAction::Overwrite(row) => {
+                    self.update_odds(&row)?;
+                }
+                RowAction::Skip(_) => {}
+            }
+        }
+        for snapshot in &archive.snapshots {
+            match restore.snapshot(snapshot)? {
+                RowAction::Create(row) => {
+                    self.insert_snapshot(&row)?;
+                }
+                RowAction::Overwrite(row) => {
+                    self.update_snapshot(&row)?;
+                }
+                RowAction::Skip(_) => {}
+            }
+        }
+        for odds in &archive.asian_handicaps {
+            match restore.asian_handicap(odds)? {
+                RowAction::Create(row) => {
+                    self.insert_asian_handicap(row.match_id, &row)?;
+                }
+                RowAction::Overwrite(row) => {
+                    self.update_asian_handicap(&row)?;
+                }
+                RowAction::Skip(_) => {}
+            }
+        }
+        for odds in &archive.over_unders {
+            match restore.over_under(odds)? {
+                RowAction::Create(row) => {
+                    self.insert_over_under(row.match_id, &row)?;
+                }
+                RowAction::Overwrite(row) => {
+                    self.update_over_under(&row)?;
+                }
+                RowAction::Skip(_) => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// create the new names of the plan and then its matches with their odds
+    fn import(&mut self, names: &mut ImportNames, plan: &ImportPlan) -> Result<(), ImportRowError> {
+        for name in &plan.leagues {
+            let league = self
+                .insert_league(names.league(name))
+                .map_err(row_error(name.line))?;
+            names.add_league(league);
+        }
+        for name in &plan.teams {
+            let team = self
+                .insert_team(names.team(name))
+                .map_err(row_error(name.line))?;
+            names.add_team(team);
+        }
+        for name in &plan.bookmakers {
+            let bookmaker = self
+                .insert_bookmaker(names.bookmaker(name))
+                .map_err(row_error(name.line))?;
+            names.add_bookmaker(bookmaker);
+        }
+        for planned in &plan.matches {
+            let MatchInfo { matches, odds } = names.match_info(planned);
+            let matches = self
+                .insert_match(&matches)
+                .map_err(row_error(planned.row.line))?;
+            for (odd, (line, _)) in odds.iter().zip(&planned.odds) {
+                self.insert_odds(matches.id, odd)
+                    .map_err(row_error(*line))?;
+            }
+        }
+        Ok(())
+    }
+
+    fn insert_match(&mut self, matches: &Matches) -> Result<Matches, OddsError> {
+        matches.validate_with(&self.teams.rows)?;
+        let mut matches = matches.clone();
+        (matches.home_goals, matches.away_goals) = matches.full_time_goals();
+        matches.id = self.matches.next_id();
+        matches.created_at = now();
+        matches.updated_at = matches.created_at;
+        self.matches.rows.push(matches.clone());
+        Ok(matches)
+    }
+
+    fn update_match(&mut self, matches: &Matches) -> Result<Matches, OddsError> {
+        matches.validate_with(&self.teams.rows)?;
+        let stored = self
+            .matches
+            .rows
+            .iter_mut()
+            .find(|m| m.id == matches.id)
+            .ok_or(OddsError::NotFound)?;
+        let (home_goals, away_goals) = matches.full_time_goals();
+        *stored = Matches {
+            home_goals,
+            away_goals,
+            created_at: stored.created_at,
+            updated_at: now(),
+            ..matches.clone()
+        };
+        Ok(stored.clone())
+    }
+
+    fn insert_odds(&mut self, id: MatchId, odd: &Odds) -> Result<Odds, OddsError> {
+        odd.validate()?;
+        self.check_match_exists(id, "odds_match_id_fkey")?;
+        let mut odd = odd.clone();
+        odd.id = self.odds.next_id();
+        odd.match_id = id;
+        self.odds.rows.push(odd.clone());
+        Ok(odd)
+    }
+
+    fn update_odds(&mut self, odd: &Odds) -> Result<Odds, OddsError> {
+        odd.validate()?;
+        let stored = self
+            .odds
+            .rows
+            .iter_mut()
+            .find(|o| o.match_id == odd.match_id && o.id == odd.id)
+            .ok_or(OddsError::NotFound)?;
+        *stored = odd.clone();
+        Ok(stored.clone())
+    }
+
+    fn insert_asian_handicap(
+        &mut self,
+        id: MatchId,
+        odds: &AsianHandicapOdds,
+    ) -> Result<AsianHandicapOdds, OddsError> {
+        odds.validate()?;
+        self.check_match_exists(id, "asian_handicap_odds_match_id_fkey")?;
+        let mut odds = odds.clone();
+        odds.id = self.asian_handicaps.next_id();
+        odds.match_id = id;
+        self.asian_handicaps.rows.push(odds.clone());
+        Ok(odds)
+    }
+
+    fn update_asian_handicap(
+        &mut self,
+        odds: &AsianHandicapOdds,
+    ) -> Result<AsianHandicapOdds, OddsError> {
+        odds.validate()?;
+        let stored = self
+            .asian_handicaps
+            .rows
+            .iter_mut()
+            .find(|o| o.match_id == odds.match_id && o.id == odds.id)
+            .ok_or(OddsError::NotFound)?;
+        *stored = odds.clone();
+        Ok(stored.clone())
+    }
+
+    fn insert_over_under(
+        &mut self,
+        id: MatchId,
+        odds: &OverUnderOdds,
+    ) -> Result<OverUnderOdds, OddsError> {
+        odds.validate()?;
+        self.check_match_exists(id, "over_under_odds_match_id_fkey")?;
+        let mut odds = odds.clone();
+        odds.id = self.over_unders.next_id();
+        odds.match_id = id;
+        self.over_unders.rows.push(odds.clone());
+        Ok(odds)
+    }
+
+    fn update_over_under(&mut self, odds: &OverUnderOdds) -> Result<OverUnderOdds, OddsError> {
+        odds.validate()?;
+        let stored = self
+            .over_unders
+            .rows
+            .iter_mut()
+            .find(|o| o.match_id == odds.match_id && o.id == odds.id)
+            .ok_or(OddsError::NotFound)?;
+        *stored = odds.clone();
+        Ok(stored.clone())
+    }
+
+    fn insert_snapshot(&mut self, snapshot: &OddsSnapshot) -> Result<OddsSnapshot, OddsError> {
+        snapshot.validate()?;
+        self.check_match_exists(snapshot.match_id, "odds_snapshots_match_id_fkey")?;
+        let created_at = now();
+        let snapshot = OddsSnapshot {
+            id: self.snapshots.next_id(),
+            // the snapshot is recorded now when no time is given
+            recorded_at: if snapshot.recorded_at == NaiveDateTime::default() {
+                created_at
+            } else {
+                snapshot.recorded_at
+            },
+            created_at,
+            ..snapshot.clone()
+        };
+        self.snapshots.rows.push(snapshot.clone());
+        Ok(snapshot)
+    }
+
+    fn update_snapshot(&mut self, snapshot: &OddsSnapshot) -> Result<OddsSnapshot, OddsError> {
+        snapshot.validate()?;
+        let stored = self
+            .snapshots
+            .rows
+            .iter_mut()
+            .find(|s| s.id == snapshot.id)
+            .ok_or(OddsError::NotFound)?;
+        stored.home_win = snapshot.home_win.clone();
+        stored.draw = snapshot.draw.clone();
+        stored.away_win = snapshot.away_win.clone();
+        Ok(stored.clone())
+    }
+
+    fn append_odds_snapshot(&mut self, snapshot: OddsSnapshot) -> Result<OddsSnapshot, OddsError> {
+        snapshot.validate()?;
+        let bookmaker_name = self
+            .bookmakers
+            .rows
+            .iter()
+            .find(|b| b.id == snapshot.bookmaker_id)
+            .map(|b| b.name.clone())
+            .ok_or(OddsError::NotFound)?;
+        let snapshot = self.insert_snapshot(&snapshot)?;
+
+        let timeline = self.timeline(snapshot.match_id, snapshot.bookmaker_id);
+        let (Some(first), Some(last)) = (timeline.first(), timeline.last()) else {
+            return Err(OddsError::NotFound);
+        };
+        let derived = Odds {
+            id: 0,
+            match_id: snapshot.match_id,
+            bookmaker_id: snapshot.bookmaker_id,
+            bookmaker_name,
+            home_win_start: first.home_win.clone(),
+            draw_start: first.draw.clone(),
+            away_win_start: first.away_win.clone(),
+            home_win_end: last.home_win.clone(),
+            draw_end: last.draw.clone(),
+            away_win_end: last.away_win.clone(),
+            note: Some(String::new()),
+        };
+        let mut updated = false;
+        for odd in
+            self.odds.rows.iter_mut().filter(|o| {
+                o.match_id == snapshot.match_id && o.bookmaker_id == snapshot.bookmaker_id
+            })
+        {
+            *odd = Odds {
+                id: odd.id,
+                note: odd.note.take(),
+                ..derived.clone()
+            };
+            updated = true;
+        }
+        if !updated {
+            self.insert_odds(snapshot.match_id, &derived)?;
+        }
+
+        Ok(snapshot)
+    }
+
+    /// the matches meeting the filter in scan order from the cursor, one more than the page
+    /// size, with the number of matches meeting the filter on every page
+    fn scan_matches(
+        &self,
+        filter: &MatchFilter,
+        cursor: Option<&MatchCursor>,
+    ) -> (Vec<Matches>, i64) {
+        let mut matches: Vec<Matches> = self
+            .matches
+            .rows
+            .iter()
+            .filter(|m| filter.matches(m))
+            .filter(|m| {
+                filter.bookmaker_id.is_none_or(|id| {
+                    self.odds
+                        .rows
+                        .iter()
+                        .any(|o| o.match_id == m.id && o.bookmaker_id == id)
+                })
+            })
+            .cloned()
+            .collect();
+        let total = matches.len() as i64;
+
+        let key = |m: &Matches| (m.game_time.unwrap_or_default(), m.id);
+        let desc = scan_desc(filter, cursor);
+        matches.sort_by_key(key);
+        if desc {
+            matches.reverse();
+        }
+        if let Some(cursor) = cursor {
+            let cursor_key = (cursor.game_time, cursor.id);
+            matches.retain(|m| {
+                if desc {
+                    key(m) < cursor_key
+                } else {
+                    key(m) > cursor_key
+                }
+            });
+        }
+        matches.truncate(filter.limit() as usize + 1);
+        (matches, total)
+    }
+
+    fn timeline(&self, match_id: MatchId, bookmaker_id: BookMakerId) -> Vec<OddsSnapshot> {
+        let mut timeline: Vec<OddsSnapshot> = self
+            .snapshots
+            .rows
+            .iter()
+            .filter(|s| s.match_id == match_id && s.bookmaker_id == bookmaker_id)
+            .cloned()
+            .collect();
+        timeline.sort_by_key(|s| (s.recorded_at, s.id));
+        timeline
+    }
+}
+
+#[async_trait]
+impl EuropeOdds for InMemoryOdds {
+    /// get all bookmaker data
+    async fn list_bookermaker(&self) -> Result<Vec<BookMaker>, OddsError> {
+        Ok(self.state.read().await.bookmakers.rows.clone())
+    }
+
+    /// query bookmaker data by id
+    async fn query_bookermaker_with_id(&self, id: BookMakerId) -> Result<BookMaker, OddsError> {
+        let state = self.state.read().await;
+        state
+            .bookmakers
+            .rows
+            .iter()
+            .find(|b| b.id == id)
+            .cloned()
+            .ok_or(OddsError::NotFound)
+    }
+
+    /// add bookmaker data to persistence
+    async fn create_bookermaker(&self, bookmaker: BookMaker) -> Result<Vec<BookMaker>, OddsError> {
+        let mut state = self.state.write().await;
+        state.insert_bookmaker(bookmaker)?;
+
+        Ok(state.bookmakers.rows.clone())
+    }
+
+    /// update bookmaker data to persistence
+    async fn update_bookermaker(&self, bookmaker: BookMaker) -> Result<Vec<BookMaker>, OddsError> {
+        let mut state = self.state.write().await;
+        state.update_bookmaker(bookmaker)?;
+
+        Ok(state.bookmakers.rows.clone())
+    }
+
+    /// delete bookmaker data from persistence
+    async fn delete_bookermaker(&self, id: BookMakerId) -> Result<Vec<BookMaker>, OddsError> {
+        let mut state = self.state.write().await;
+        state.bookmakers.rows.retain(|b| b.id != id);
+
+        Ok(state.bookmakers.rows.clone())
+    }
+
+    /// get all league data
+    async fn list_leagues(&self) -> Result<Vec<League>, OddsError> {
+        Ok(self.state.read().await.leagues.rows.clone())
+    }
+
+    /// query league data by id
+    async fn query_league_with_id(&self, id: LeagueId) -> Result<League, OddsError> {
+        let state = self.state.read().await;
+        state
+            .leagues
+            .rows
+            .iter()
+            .find(|l| l.id == id)
+            .cloned()
+            .ok_or(OddsError::NotFound)
+    }
+
+    /// add league data to persistence
+    async fn create_league(&self, league: League) -> Result<Vec<League>, OddsError> {
+        let mut state = self.state.write().await;
+        state.insert_league(league)?;
+
+        Ok(state.leagues.rows.clone())
+    }
+
+    /// update league data to persistence
+    async fn update_league(&self, league: League) -> Result<Vec<League>, OddsError> {
+        let mut state = self.state.write().await;
+        state.update_league(league)?;
+
+        Ok(state.leagues.rows.clone())
+    }
+
+    /// delete league data from persistence
+    async fn delete_league(&self, id: LeagueId) -> Result<Vec<League>, OddsError> {
+        let mut state = self.state.write().await;
+        state.leagues.rows.retain(|l| l.id != id);
+
+        Ok(state.leagues.rows.clone())
+    }
+
+    /// get all team data
+    async fn list_teams(&self) -> Result<Vec<Team>, OddsError> {
+        let state = self.state.read().await;
+        Ok(state
+            .teams
+            .rows
+            .iter()
+            .filter_map(|team| state.team_with_league(team))
+            .collect())
+    }
+
+    /// query team data by id
+    async fn query_team_with_id(&self, id: TeamId) -> Result<Team, OddsError> {
+        let state = self.state.read().await;
+        state
+            .teams
+            .rows
+            .iter()
+            .find(|t| t.id == id)
+            .and_then(|team| state.team_with_league(team))
+            .ok_or(OddsError::NotFound)
+    }
+
+    /// query team data by league id, the latest team first
+    async fn query_teams_with_condition(&self, id: LeagueId) -> Result<Vec<Team>, OddsError> {
+        let state = self.state.read().await;
+        Ok(state
+            .teams
+            .rows
+            .iter()
+            .rev()
+            .filter(|team| team.league_id == id)
+            .filter_map(|team| state.team_with_league(team))
+            .collect())
+    }
+
+    /// add team data to persistence
+    async fn create_team(&self, team: Team) -> Result<Vec<Team>, OddsError> {
+        self.state.write().await.insert_team(team)?;
+
+        self.list_teams().await
+    }
+
+    /// update team data to persistence
+    async fn update_team(&self, team: Team) -> Result<Vec<Team>, OddsError> {
+        self.state.write().await.update_team(team)?;
+
+        self.list_teams().await
+    }
+
+    /// delete team data from persistence
+    async fn delete_team(&self, id: TeamId) -> Result<Vec<Team>, OddsError> {
+        self.state.write().await.teams.rows.retain(|t| t.id != id);
+
+        self.list_teams().await
+    }
+
+    /// query a page of match data meeting the filter
+    async fn query_match_info(&self, filter: MatchFilter) -> Result<Page<Matches>, OddsError> {
+        let cursor = filter.keyset()?;
+        let state = self.state.read().await;
+        let (matches, total) = state.scan_matches(&filter, cursor.as_ref());
+
+        Ok(match_page(&filter, cursor.as_ref(), matches, total))
+    }
+
+    /// query the page of match data meeting the filter with the odds of every match
+    async fn query_match_info_with_odds(
+        &self,
+        filter: MatchFilter,
+    ) -> Result<Vec<MatchInfo>, OddsError> {
+        let cursor = filter.keyset()?;
+        let state = self.state.read().await;
+        let (matches, _) = state.scan_matches(&filter, cursor.as_ref());
+        let matches = page_items(&filter, cursor.as_ref(), matches);
+        let odds = state
+            .odds
+            .rows
+            .iter()
+            .filter(|o| matches.iter().any(|m| m.id == o.match_id))
+            .cloned()
+            .collect();
+
+        Ok(match_infos(matches, odds))
+    }
+
+    /// query match data by match id
+    async fn query_match_info_by_id(&self, id: MatchId) -> Result<Matches, OddsError> {
+        let state = self.state.read().await;
+        state
+            .matches
+            .rows
+            .iter()
+            .find(|m| m.id == id)
+            .cloned()
+            .ok_or(OddsError::NotFound)
+    }
+
+    /// query odds data by match id
+    async fn query_odds_info_by_id(&self, id: i32) -> Result<Vec<Odds>, OddsError> {
+        let state = self.state.read().await;
+        Ok(state
+            .odds
+            .rows
+            .iter()
+            .filter(|o| o.match_id == id)
+            .cloned()
+            .collect())
+    }
+
+    /// add match data to persistence
+    async fn create_match_info(&self, matches: Matches) -> Result<Matches, OddsError> {
+        self.state.write().await.insert_match(&matches)
+    }
+
+    /// update match data to persistence
+    async fn update_match_info(&self, matches: Matches) -> Result<Matches, OddsError> {
+        self.state.write().await.update_match(&matches)
+    }
+
+    /// save a match with its odds at once, the changes are made on a copy
+    /// and only kept when every one of them succeeds
+    async fn save_match_info(&self, match_info: MatchInfo) -> Result<MatchInfo, OddsError> {
+        let MatchInfo { matches, odds } = match_info;
+        let mut state = self.state.write().await;
+        let mut draft = state.clone();
+        let (matches, stored) = if matches.id == 0 {
+            (draft.insert_match(&matches)?, vec![])
+        } else {
+            let matches = draft.update_match(&matches)?;
+            let stored: Vec<Odds> = draft
+                .odds
+                .rows
+                .iter()
+                .filter(|o| o.match_id == matches.id)
+                .cloned()
+                .collect();
+            (matches, stored)
+        };
+
+        let changes = diff_odds(&stored, odds).known()?;
+        draft.odds.rows.retain(|o| !changes.delete.contains(&o.id));
+        let mut saved_odds = vec![];
+        for mut odd in changes.update {
+            odd.match_id = matches.id;
+            saved_odds.push(draft.update_odds(&odd)?);
+        }
+        for odd in changes.create {
+            saved_odds.push(draft.insert_odds(matches.id, &odd)?);
+        }
+        *state = draft;
+
+        Ok(MatchInfo::new(matches, saved_odds))
+    }
+
+    /// import matches with their odds at once, the changes are made on a copy
+    /// and only kept when every one of them succeeds
+    async fn import_matches(
+        &self,
+        rows: Vec<MatchImportRow>,
+        create_missing: bool,
+    ) -> Result<ImportReport, OddsError> {
+        let mut state = self.state.write().await;
+        let mut draft = state.clone();
+        let teams = draft
+            .teams
+            .rows
+            .iter()
+            .filter_map(|team| draft.team_with_league(team))
+            .collect();
+        let mut names = ImportNames::new(
+            draft.leagues.rows.clone(),
+            teams,
+            draft.bookmakers.rows.clone(),
+        );
+        let plan = match names.plan(rows, create_missing) {
+            Ok(plan) => plan,
+            Err(errors) => return Ok(ImportReport::failed(errors)),
+        };
+
+        let imported = draft.import(&mut names, &plan);
+
+        match imported {
+            Ok(()) => {
+                *state = draft;
+                Ok(plan.report())
+            }
+            Err(error) => Ok(ImportReport::failed(vec![error])),
+        }
+    }
+
+    /// restore an archive into a draft of the state which replaces the state
+    /// when every row is restored
+    async fn restore_archive(
+        &self,
+        archive: Archive,
+        policy: ConflictPolicy,
+    ) -> Result<RestoreReport, OddsError> {
+        let mut state = self.state.write().await;
+        let mut draft = state.clone();
+        let stored = Archive {
+            bookmakers: draft.bookmakers.rows.clone(),
+            leagues: draft.leagues.rows.clone(),
+            teams: draft.teams.rows.clone(),
+            matches: draft.matches.rows.clone(),
+            odds: draft.odds.rows.clone(),
+            snapshots: draft.snapshots.rows.clone(),
+            asian_handicaps: draft.asian_handicaps.rows.clone(),
+            over_unders: draft.over_unders.rows.clone(),
+            ..Archive::new(now())
+        };
+        let mut restore = Restore::new(policy, &stored);
+        draft.restore(&archive, &mut restore)?;
+
+        *state = draft;
+        Ok(restore.report)
+    }
+
+    /// delete match data from persistence, the odds of the match are deleted with it
+    async fn delete_match_info(&self, id: MatchId) -> Result<i32, OddsError> {
+        let mut state = self.state.write().await;
+        let count = state.matches.rows.len();
+        state.matches.rows.retain(|m| m.id != id);
+        let count = (count - state.matches.rows.len()) as i32;
+        state.odds.rows.retain(|o| o.match_id != id);
+        state.snapshots.rows.retain(|s| s.match_id != id);
+        state.asian_handicaps.rows.retain(|o| o.match_id != id);
+        state.over_unders.rows.retain(|o| o.match_id != id);
+
+        Ok(count)
+    }
+
+    /// add odds data of a match to persistence
+    async fn create_odd_info(&self, id: MatchId, odd: Odds) -> Result<Odds, OddsError> {
+        self.state.write().await.insert_odds(id, &odd)
+    }
+
+    /// update odds data to persistence
+    async fn update_odd_info(&self, odd: Odds) -> Result<Odds, OddsError> {
+        self.state.write().await.update_odds(&odd)
+    }
+
+    /// delete odds data from persistence
+    async fn delete_odds_info(&self, id: OddId) -> Result<i32, OddsError> {
+        let mut state = self.state.write().await;
+        let count = state.odds.rows.len();
+        state.odds.rows.retain(|o| o.id != id);
+
+        Ok((count - state.odds.rows.len()) as i32)
+    }
+
+    /// query earlier matches whose odds from the same bookmaker are within the tolerances
+    async fn query_similar_odds(&self, query: SimilarOddsQuery) -> Result<SimilarOdds, OddsError> {
+        let state = self.state.read().await;
+        let odds = state
+            .odds
+            .rows
+            .iter()
+            .find(|o| o.match_id == query.match_id && o.bookmaker_id == query.bookmaker_id)
+            .cloned()
+            .ok_or(OddsError::NotFound)?;
+        let game_time = state
+            .matches
+            .rows
+            .iter()
+            .find(|m| m.id == query.match_id)
+            .ok_or(OddsError::NotFound)?
+            .game_time;
+
+        let (start_tolerance, end_tolerance) =
+            effective_tolerances(&odds, query.start_tolerance, query.end_tolerance);
+        let mut similar: Vec<(&Matches, Odds)> = state
+            .odds
+            .rows
+            .iter()
+            .filter(|o| o.bookmaker_id == query.bookmaker_id && o.match_id != query.match_id)
+            .filter(|o| {
+                within_tolerance(&odds, o, start_tolerance.as_ref(), end_tolerance.as_ref())
+            })
+            .filter_map(|o| {
+                let game = state.matches.rows.iter().find(|m| m.id == o.match_id)?;
+                let earlier = game.game_time.is_some() && game.game_time < game_time;
+                let in_league = query.league_id == 0 || game.league_id == query.league_id;
+                let in_year = query.game_year.is_none() || game.game_year == query.game_year;
+                (earlier && in_league && in_year).then(|| (game, o.clone()))
+            })
+            .collect();
+        similar.sort_by_key(|(game, _)| std::cmp::Reverse(game.game_time));
+
+        let mut matches: Vec<Matches> = vec![];
+        for (game, _) in similar.iter() {
+            if !matches.iter().any(|m| m.id == game.id) {
+                matches.push((*game).clone());
+            }
+        }
+        let similar = similar.into_iter().map(|(_, odd)| odd).collect();
+
+        Ok(similar_odds(odds, similar, matches))
+    }
+
+    /// count the results of historical matches per band of closing home win prices
+    async fn query_odds_bands(&self, query: OddsBandQuery) -> Result<Vec<OddsBand>, OddsError> {
+        let state = self.state.read().await;
+        let matches: Vec<Matches> = state
+            .matches
+            .rows
+            .iter()
+            .filter(|m| query.league_id == 0 || m.league_id == query.league_id)
+            .filter(|m| {
+                (m.home_goals.is_some() && m.away_goals.is_some()) || m.game_result.is_some()
+            })
+            .cloned()
+            .collect();
+        let odds: Vec<Odds> = state
+            .odds
+            .rows
+            .iter()
+            .filter(|o| query.bookmaker_id == 0 || o.bookmaker_id == query.bookmaker_id)
+            .filter(|o| matches.iter().any(|m| m.id == o.match_id))
+            .cloned()
+            .collect();
+
+        Ok(odds_bands(&matches, &odds, &query))
+    }
+
+    /// compare the predicted outcomes of the matches with their results
+    async fn query_prediction_scorecard(
+        &self,
+        query: ScorecardQuery,
+    ) -> Result<PredictionScorecard, OddsError> {
+        let state = self.state.read().await;
+        Ok(prediction_scorecard(&state.matches.rows, &query))
+    }
+
+    /// append a price snapshot and derive the start and end odds at once
+    async fn append_odds_snapshot(
+        &self,
+        snapshot: OddsSnapshot,
+    ) -> Result<OddsSnapshot, OddsError> {
+        let mut state = self.state.write().await;
+        let mut draft = state.clone();
+        let snapshot = draft.append_odds_snapshot(snapshot)?;
+        *state = draft;
+
+        Ok(snapshot)
+    }
+
+    /// query the price snapshots of a bookmaker for a match in recorded order
+    async fn query_odds_timeline(
+        &self,
+        match_id: MatchId,
+        bookmaker_id: BookMakerId,
+    ) -> Result<Vec<OddsSnapshot>, OddsError> {
+        Ok(self.state.read().await.timeline(match_id, bookmaker_id))
+    }
+
+    /// query the price snapshots of every bookmaker for a match in recorded order
+    async fn query_odds_snapshots_by_id(
+        &self,
+        id: MatchId,
+    ) -> Result<Vec<OddsSnapshot>, OddsError> {
+        let state = self.state.read().await;
+        let mut snapshots: Vec<OddsSnapshot> = state
+            .snapshots
+            .rows
+            .iter()
+            .filter(|s| s.match_id == id)
+            .cloned()
+            .collect();
+        snapshots.sort_by_key(|s| (s.bookmaker_id, s.recorded_at, s.id));
+        Ok(snapshots)
+    }
+
+    /// query asian handicap odds by match id
+    async fn query_asian_handicap_by_id(
+        &self,
+        id: MatchId,
+    ) -> Result<Vec<AsianHandicapOdds>, OddsError> {
+        let state = self.state.read().await;
+        Ok(state
+            .asian_handicaps
+            .rows
+            .iter()
+            .filter(|o| o.match_id == id)
+            .cloned()
+            .collect())
+    }
+
+    /// add asian handicap odds of a match to persistence
+    async fn create_asian_handicap(
+        &self,
+        id: MatchId,
+        odds: AsianHandicapOdds,
+    ) -> Result<AsianHandicapOdds, OddsError> {
+        self.state.write().await.insert_asian_handicap(id, &odds)
+    }
+
+    /// update asian handicap odds to persistence
+    async fn update_asian_handicap(
+        &self,
+        odds: AsianHandicapOdds,
+    ) -> Result<AsianHandicapOdds, OddsError> {
+        self.state.write().await.update_asian_handicap(&odds)
+    }
+
+    /// save the asian handicap odds of a match at once, the changes are made on a copy
+    async fn save_asian_handicaps(
+        &self,
+        id: MatchId,
+        odds: Vec<AsianHandicapOdds>,
+    ) -> Result<Vec<AsianHandicapOdds>, OddsError> {
+        let mut state = self.state.write().await;
+        let mut draft = state.clone();
+        let stored: Vec<AsianHandicapOdds> = draft
+            .asian_handicaps
+            .rows
+            .iter()
+            .filter(|o| o.match_id == id)
+            .cloned()
+            .collect();
+
+        let changes = diff_odds(&stored, odds).known()?;
+        draft
+            .asian_handicaps
+            .rows
+            .retain(|o| !changes.delete.contains(&o.id));
+        let mut saved_odds = vec![];
+        for mut odds in changes.update {
+            odds.match_id = id;
+            saved_odds.push(draft.update_asian_handicap(&odds)?);
+        }
+        for odds in changes.create {
+            saved_odds.push(draft.insert_asian_handicap(id, &odds)?);
+        }
+        *state = draft;
+
+        Ok(saved_odds)
+    }
+
+    /// delete asian handicap odds from persistence
+    async fn delete_asian_handicap(&self, id: OddId) -> Result<i32, OddsError> {
+        let mut state = self.state.write().await;
+        let count = state.asian_handicaps.rows.len();
+        state.asian_handicaps.rows.retain(|o| o.id != id);
+
+        Ok((count - state.asian_handicaps.rows.len()) as i32)
+    }
+
+    /// query over/under odds by match id
+    async fn query_over_under_by_id(&self, id: MatchId) -> Result<Vec<OverUnderOdds>, OddsError> {
+        let state = self.state.read().await;
+        Ok(state
+            .over_unders
+            .rows
+            .iter()
+            .filter(|o| o.match_id == id)
+            .cloned()
+            .collect())
+    }
+
+    /// add over/under odds of a match to persistence
+    async fn create_over_under(
+        &self,
+        id: MatchId,
+        odds: OverUnderOdds,
+    ) -> Result<OverUnderOdds, OddsError> {
+        self.state.write().await.insert_over_under(id, &odds)
+    }
+
+    /// update over/under odds to persistence
+    async fn update_over_under(&self, odds: OverUnderOdds) -> Result<OverUnderOdds, OddsError> {
+        self.state.write().await.update_over_under(&odds)
+    }
+
+    /// save the over/under odds of a match at once, the changes are made on a copy
+    async fn save_over_unders(
+        &self,
+        id: MatchId,
+        odds: Vec<OverUnderOdds>,
+    ) -> Result<Vec<OverUnderOdds>, OddsError> {
+        let mut state = self.state.write().await;
+        let mut draft = state.clone();
+        let stored: Vec<OverUnderOdds> = draft
+            .over_unders
+            .rows
+            .iter()
+            .filter(|o| o.match_id == id)
+            .cloned()
+            .collect();
+
+        let changes = diff_odds(&stored, odds).known()?;
+        draft
+            .over_unders
+            .rows
+            .retain(|o| !changes.delete.contains(&o.id));
+        let mut saved_odds = vec![];
+        for mut odds in changes.update {
+            odds.match_id = id;
+            saved_odds.push(draft.update_over_under(&odds)?);
+        }
+        for odds in changes.create {
+            saved_odds.push(draft.insert_over_under(id, &odds)?);
+        }
+        *state = draft;
+
+        Ok(saved_odds)
+    }
+
+    /// delete over/under odds from persistence
+    async fn delete_over_under(&self, id: OddId) -> Result<i32, OddsError> {
+        let mut state = self.state.write().await;
+        let count = state.over_unders.rows.len();
+        state.over_unders.rows.retain(|o| o.id != id);
+
+        Ok((count - state.over_unders.rows.len()) as i32)
+    }
+}
+
+fn duplicate_key(constraint: &str) -> OddsError {
+    OddsError::UniqueViolation(constraint.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn setup() -> ((), InMemoryOdds) {
+        ((), InMemoryOdds::new())
+    }
+
+    crate::test_suite::europe_odds_test_suite!(setup);
+}
@@ -1,12 +1,36 @@
+mod analysis;
+mod archive;
+mod diff;
+mod filter;
+mod football_data;
+mod import;
+mod in_memory;
 mod manager;
+mod sqlite_manager;
+#[cfg(test)]
+mod test_suite;
 mod test_util;
 
-pub use manager::*;
+pub use analysis::*;
+pub use diff::*;
+pub use football_data::*;
+pub use import::*;
+pub use in_memory::*;
 pub use test_util::*;
 
+use std::time::{SystemTime, UNIX_EPOCH};
+
 use async_trait::async_trait;
-use data::{BookMaker, League, MatchInfoQuery, Matches, Odds, OddsError, Team};
-use sqlx::PgPool;
+use chrono::{DateTime, NaiveDateTime};
+use data::{
+    Archive, AsianHandicapOdds, BookMaker, ConflictPolicy, CursorDirection, DbBackend, DbConfig,
+    FootballDataConfig, ImportReport, KellyReport, League, MarginRemoval, MatchCursor, MatchFilter,
+    MatchImportRow, MatchInfo, Matches, Odds, OddsBand, OddsBandQuery, OddsConsensus, OddsError,
+    OddsMovement, OddsProbability, OddsSnapshot, OverUnderOdds, OverUnderSettlement, Page,
+    PredictionScorecard, RestoreReport, ScorecardQuery, SimilarOdds, SimilarOddsQuery, Team,
+    MAX_PAGE_SIZE,
+};
+use sqlx::{PgPool, SqlitePool};
 
 type BookMakerId = i32;
 type LeagueId = i32;
@@ -64,18 +88,164 @@ pub trait EuropeOdds {
     /// delete team data from persistence
     async fn delete_team(&self, id: TeamId) -> Result<Vec<Team>, OddsError>;
 
-    /// query match data by conditions
-    async fn query_match_info(&self, query: MatchInfoQuery) -> Result<Vec<Matches>, OddsError>;
+    /// query a page of match data meeting the filter with the total count and the
+    /// cursors of the neighbouring pages
+    async fn query_match_info(&self, filter: MatchFilter) -> Result<Page<Matches>, OddsError>;
+
+    /// query the page of match data meeting the filter with the odds of every match,
+    /// the odds of the whole page are fetched at once
+    async fn query_match_info_with_odds(
+        &self,
+        filter: MatchFilter,
+    ) -> Result<Vec<MatchInfo>, OddsError>;
+
+    /// query match data by match id
+    async fn query_match_info_by_id(&self, id: MatchId) -> Result<Matches, OddsError>;
 
     /// query odds data by match id
     async fn query_odds_info_by_id(&self, id: i32) -> Result<Vec<Odds>, OddsError>;
 
+    /// query implied probabilities, overround and payout rate of odds data by match id
+    async fn query_odds_probability_by_id(
+        &self,
+        id: MatchId,
+    ) -> Result<Vec<OddsProbability>, OddsError> {
+        let odds = self.query_odds_info_by_id(id).await?;
+        Ok(odds.iter().map(odds_probability).collect())
+    }
+
+    /// query opening to closing movement of odds data by match id
+    async fn query_odds_movement_by_id(&self, id: MatchId) -> Result<Vec<OddsMovement>, OddsError> {
+        let odds = self.query_odds_info_by_id(id).await?;
+        Ok(odds.iter().map(odds_movement).collect())
+    }
+
+    /// query cross-bookmaker consensus and dispersion of odds data by match id
+    async fn query_odds_consensus_by_id(&self, id: MatchId) -> Result<OddsConsensus, OddsError> {
+        let odds = self.query_odds_info_by_id(id).await?;
+        Ok(odds_consensus(id, &odds))
+    }
+
+    /// query kelly indices of odds data by match id against the market average fair probabilities
+    async fn query_odds_kelly_by_id(
+        &self,
+        id: MatchId,
+        method: MarginRemoval,
+    ) -> Result<KellyReport, OddsError> {
+        let odds = self.query_odds_info_by_id(id).await?;
+        Ok(kelly_report(id, &odds, method))
+    }
+
+    /// settle over/under odds of a match against its final score
+    async fn query_over_under_settlement_by_id(
+        &self,
+        id: MatchId,
+    ) -> Result<Vec<OverUnderSettlement>, OddsError> {
+        let matches = self.query_match_info_by_id(id).await?;
+        let odds = self.query_over_under_by_id(id).await?;
+        Ok(odds
+            .iter()
+            .map(|odd| over_under_settlement(&matches, odd))
+            .collect())
+    }
+
     /// add match data to persistence
     async fn create_match_info(&self, matches: Matches) -> Result<Matches, OddsError>;
 
     /// update match data to persistence
     async fn update_match_info(&self, matches: Matches) -> Result<Matches, OddsError>;
 
+    /// save a match with its odds in one transaction, a match without id is created,
+    /// otherwise the match is updated and its odds are diffed against the stored ones,
+    /// an odds id that is not stored for the match is not found
+    async fn save_match_info(&self, match_info: MatchInfo) -> Result<MatchInfo, OddsError>;
+
+    /// import matches with their odds in one transaction, the names of the rows are
+    /// resolved to ids and missing ones are created when `create_missing` is set,
+    /// nothing is imported when a row fails and the report lists the failed rows
+    async fn import_matches(
+        &self,
+        rows: Vec<MatchImportRow>,
+        create_missing: bool,
+    ) -> Result<ImportReport, OddsError>;
+
+    /// import the matches of a csv read by `read_match_csv`
+    async fn import_match_csv(
+        &self,
+        csv: &str,
+        create_missing: bool,
+    ) -> Result<ImportReport, OddsError> {
+        match read_match_csv(csv) {
+            Ok(rows) => self.import_matches(rows, create_missing).await,
+            Err(errors) => Ok(ImportReport::failed(errors)),
+        }
+    }
+
+    /// import the matches of a football-data.co.uk season csv read by
+    /// `read_football_data_csv`
+    async fn import_football_data_csv(
+        &self,
+        csv: &str,
+        config: &FootballDataConfig,
+        create_missing: bool,
+    ) -> Result<ImportReport, OddsError> {
+        match read_football_data_csv(csv, config) {
+            Ok(rows) => self.import_matches(rows, create_missing).await,
+            Err(errors) => Ok(ImportReport::failed(errors)),
+        }
+    }
+
+    /// export the bookmakers, leagues, teams, matches and odds to an archive with the price
+    /// snapshots, asian handicap and over/under odds of every match
+    async fn export_archive(&self) -> Result<Archive, OddsError> {
+        let mut archive = Archive::new(now());
+        archive.bookmakers = self.list_bookermaker().await?;
+        archive.leagues = self.list_leagues().await?;
+        archive.teams = self.list_teams().await?;
+
+        let mut filter = MatchFilter {
+            page_size: MAX_PAGE_SIZE,
+            ..MatchFilter::default()
+        };
+        loop {
+            let page = self.query_match_info_with_odds(filter.clone()).await?;
+            let next = match page.last() {
+                Some(last) if page.len() == MAX_PAGE_SIZE as usize => {
+                    Some(MatchCursor::new(&last.matches, CursorDirection::After).encode())
+                }
+                _ => None,
+            };
+            for MatchInfo { matches, odds } in page {
+                archive
+                    .snapshots
+                    .extend(self.query_odds_snapshots_by_id(matches.id).await?);
+                archive
+                    .asian_handicaps
+                    .extend(self.query_asian_handicap_by_id(matches.id).await?);
+                archive
+                    .over_unders
+                    .extend(self.query_over_under_by_id(matches.id).await?);
+                archive.matches.push(matches);
+                archive.odds.extend(odds);
+            }
+            match next {
+                Some(cursor) => filter.cursor = Some(cursor),
+                None => break,
+            }
+        }
+        Ok(archive)
+    }
+
+    /// restore an archive in one transaction, the archived rows are given the ids of the
+    /// stored rows they match by name, by league, teams and kickoff for a match, by match
+    /// and bookmaker for odds and by match, bookmaker and recording time for a snapshot,
+    /// or new ids, and the policy settles the rows already stored
+    async fn restore_archive(
+        &self,
+        archive: Archive,
+        policy: ConflictPolicy,
+    ) -> Result<RestoreReport, OddsError>;
+
     /// delete match data from persistence
     async fn delete_match_info(&self, id: MatchId) -> Result<i32, OddsError>;
 
@@ -87,8 +257,118 @@ pub trait EuropeOdds {
 
     /// delete match data from persistence
     async fn delete_odds_info(&self, id: OddId) -> Result<i32, OddsError>;
+
+    /// query earlier matches whose odds from the same bookmaker are within the tolerances
+    async fn query_similar_odds(&self, query: SimilarOddsQuery) -> Result<SimilarOdds, OddsError>;
+
+    /// count the results of historical matches per band of closing home win prices
+    async fn query_odds_bands(&self, query: OddsBandQuery) -> Result<Vec<OddsBand>, OddsError>;
+
+    /// compare the predicted outcomes of the matches with their results
+    async fn query_prediction_scorecard(
+        &self,
+        query: ScorecardQuery,
+    ) -> Result<PredictionScorecard, OddsError>;
+
+    /// append a price snapshot of a bookmaker, the start and end odds of the bookmaker
+    /// are derived from the first and the last snapshot
+    async fn append_odds_snapshot(&self, snapshot: OddsSnapshot)
+        -> Result<OddsSnapshot, OddsError>;
+
+    /// query the price snapshots of a bookmaker for a match in recorded order
+    async fn query_odds_timeline(
+        &self,
+        match_id: MatchId,
+        bookmaker_id: BookMakerId,
+    ) -> Result<Vec<OddsSnapshot>, OddsError>;
+
+    /// query the price snapshots of every bookmaker for a match in recorded order
+    async fn query_odds_snapshots_by_id(&self, id: MatchId)
+        -> Result<Vec<OddsSnapshot>, OddsError>;
+
+    /// query asian handicap odds by match id
+    async fn query_asian_handicap_by_id(
+        &self,
+        id: MatchId,
+    ) -> Result<Vec<AsianHandicapOdds>, OddsError>;
+
+    /// add asian handicap odds of a match to persistence
+    async fn create_asian_handicap(
+        &self,
+        id: MatchId,
+        odds: AsianHandicapOdds,
+    ) -> Result<AsianHandicapOdds, OddsError>;
+
+    /// update asian handicap odds to persistence
+    async fn update_asian_handicap(
+        &self,
+        odds: AsianHandicapOdds,
+    ) -> Result<AsianHandicapOdds, OddsError>;
+
+    /// save the asian handicap odds of a match in one transaction, odds without id are
+    /// created, stored odds that are not submitted are deleted and an id that is not
+    /// stored for the match is not found
+    async fn save_asian_handicaps(
+        &self,
+        id: MatchId,
+        odds: Vec<AsianHandicapOdds>,
+    ) -> Result<Vec<AsianHandicapOdds>, OddsError>;
+
+    /// delete asian handicap odds from persistence
+    async fn delete_asian_handicap(&self, id: OddId) -> Result<i32, OddsError>;
+
+    /// query over/under odds by match id
+    async fn query_over_under_by_id(&self, id: MatchId) -> Result<Vec<OverUnderOdds>, OddsError>;
+
+    /// add over/under odds of a match to persistence
+    async fn create_over_under(
+        &self,
+        id: MatchId,
+        odds: OverUnderOdds,
+    ) -> Result<OverUnderOdds, OddsError>;
+
+    /// update over/under odds to persistence
+    async fn update_over_under(&self, odds: OverUnderOdds) -> Result<OverUnderOdds, OddsError>;
+
+    /// save the over/under odds of a match in one transaction, odds without id are
+    /// created, stored odds that are not submitted are deleted and an id that is not
+    /// stored for the match is not found
+    async fn save_over_unders(
+        &self,
+        id: MatchId,
+        odds: Vec<OverUnderOdds>,
+    ) -> Result<Vec<OverUnderOdds>, OddsError>;
+
+    /// delete over/under odds from persistence
+    async fn delete_over_under(&self, id: OddId) -> Result<i32, OddsError>;
 }
 
 pub struct OddsManager {
     pub conn: PgPool,
 }
+
+pub struct SqliteOddsManager {
+    pub conn: SqlitePool,
+}
+
+/// odds persistence of the backend chosen by the configuration
+pub type OddsStore = Box<dyn EuropeOdds + Send + Sync>;
+
+/// connect to the database backend of the configuration
+pub async fn connect(config: &DbConfig) -> Result<OddsStore, OddsError> {
+    let store: OddsStore = match config.backend {
+        DbBackend::Postgres => Box::new(OddsManager::from_config(config).await?),
+        DbBackend::Sqlite => Box::new(SqliteOddsManager::from_config(config).await?),
+    };
+    Ok(store)
+}
+
+/// the current utc time, the time of the rows written by the in-memory store and of exports
+pub(crate) fn now() -> NaiveDateTime {
+    let elapsed = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    DateTime::from_timestamp(elapsed.as_secs() as i64, elapsed.subsec_nanos())
+        .map(|time| time.naive_utc())
+        .unwrap_or_default()
+}
@@ -1,8 +1,23 @@
 use async_trait::async_trait;
-use data::{BookMaker, DbConfig, League, MatchInfoQuery, Matches, Odds, OddsError, Team};
-use sqlx::{postgres::PgPoolOptions, PgPool, Row};
-
-use crate::{BookMakerId, EuropeOdds, LeagueId, MatchId, OddId, OddsManager, TeamId};
+use chrono::NaiveDateTime;
+use data::{
+    Archive, AsianHandicapOdds, BookMaker, ConflictPolicy, DbConfig, ImportReport, ImportRowError,
+    League, MatchFilter, MatchImportRow, MatchInfo, Matches, Odds, OddsBand, OddsBandQuery,
+    OddsError, OddsSnapshot, OverUnderOdds, Page, PredictionScorecard, RestoreReport,
+    ScorecardQuery, SimilarOdds, SimilarOddsQuery, Team, Validate,
+};
+use sqlx::{
+    postgres::PgPoolOptions, PgConnection, PgExecutor, PgPool, Postgres, QueryBuilder, Row,
+};
+
+use crate::{
+    archive::{Restore, RowAction},
+    diff_odds, effective_tolerances,
+    filter::{match_count_query, match_infos, match_page, match_query, page_items},
+    import::{row_error, ImportNames},
+    now, odds_bands, prediction_scorecard, similar_odds, BookMakerId, EuropeOdds, LeagueId,
+    MatchId, OddId, OddsManager, TeamId,
+};
 
 impl OddsManager {
     pub fn new(conn: PgPool) -> Self {
@@ -31,21 +46,8 @@ impl EuropeOdds for OddsManager {
     }
 
     /// add bookmaker data to persistence
-    async fn create_bookermaker(
-        &self,
-        mut bookmaker: BookMaker,
-    ) -> Result<Vec<BookMaker>, OddsError> {
-        let id = sqlx::query(
-            "INSERT INTO euro.bookmakers (name, url, note) VALUES ($1, $2, $3) RETURNING id",
-        )
-        .bind(&bookmaker.name)
-        .bind(&bookmaker.url)
-        .bind(&bookmaker.note)
-        .fetch_one(&self.conn)
-        .await?
-        .get(0);
-
-        bookmaker.id = id;
+    async fn create_bookermaker(&self, bookmaker: BookMaker) -> Result<Vec<BookMaker>, OddsError> {
+        insert_bookmaker(&self.conn, &bookmaker).await?;
 
         let book_makers = self.list_bookermaker().await?;
         Ok(book_makers)
@@ -53,15 +55,7 @@ impl EuropeOdds for OddsManager {
 
     /// update bookmaker data to persistence
     async fn update_bookermaker(&self, bookmaker: BookMaker) -> Result<Vec<BookMaker>, OddsError> {
-        sqlx::query(
-            "UPDATE euro.bookmakers SET name = $1, url = $2, note = $3 WHERE id = $4 RETURNING *",
-        )
-        .bind(&bookmaker.name)
-        .bind(&bookmaker.url)
-        .bind(&bookmaker.note)
-        .bind(bookmaker.id)
-        .fetch_one(&self.conn)
-        .await?;
+        update_bookmaker(&self.conn, &bookmaker).await?;
 
         let book_makers = self.list_bookermaker().await?;
         Ok(book_makers)
@@ -108,27 +102,15 @@ impl EuropeOdds for OddsManager {
     }
 
     /// add league data to persistence
-    async fn create_league(&self, mut league: League) -> Result<Vec<League>, OddsError> {
-        let id = sqlx::query("INSERT INTO euro.leagues (name, note) VALUES ($1, $2) RETURNING id")
-            .bind(&league.name)
-            .bind(&league.note)
-            .fetch_one(&self.conn)
-            .await?
-            .get(0);
-
-        league.id = id;
+    async fn create_league(&self, league: League) -> Result<Vec<League>, OddsError> {
+        insert_league(&self.conn, &league).await?;
         let leagues = self.list_leagues().await?;
         Ok(leagues)
     }
 
     /// update league data to persistence
     async fn update_league(&self, league: League) -> Result<Vec<League>, OddsError> {
-        sqlx::query("UPDATE euro.leagues SET name = $1, note = $2 WHERE id = $3 RETURNING *")
-            .bind(&league.name)
-            .bind(&league.note)
-            .bind(league.id)
-            .fetch_one(&self.conn)
-            .await?;
+        update_league(&self.conn, &league).await?;
 
         let leagues = self.list_leagues().await?;
         Ok(leagues)
@@ -183,18 +165,8 @@ impl EuropeOdds for OddsManager {
     }
 
     /// add team data to persistence
-    async fn create_team(&self, mut team: Team) -> Result<Vec<Team>, OddsError> {
-        let id = sqlx::query(
-            "INSERT INTO euro.teams (name, league_id, note) VALUES ($1, $2, $3) RETURNING id",
-        )
-        .bind(&team.name)
-        .bind(team.league_id)
-        .bind(&team.note)
-        .fetch_one(&self.conn)
-        .await?
-        .get(0);
-
-        team.id = id;
+    async fn create_team(&self, team: Team) -> Result<Vec<Team>, OddsError> {
+        insert_team(&self.conn, &team).await?;
 
         let teams = self.list_teams().await?;
         Ok(teams)
@@ -202,15 +174,7 @@ impl EuropeOdds for OddsManager {
 
     /// update team data to persistence
     async fn update_team(&self, team: Team) -> Result<Vec<Team>, OddsError> {
-        sqlx::query(
-            "UPDATE euro.teams SET name = $1, league_id = $2, note = $3 WHERE id = $4 RETURNING *",
-        )
-        .bind(&team.name)
-        .bind(team.league_id)
-        .bind(&team.note)
-        .bind(team.id)
-        .fetch_one(&self.conn)
-        .await?;
+        update_team(&self.conn, &team).await?;
 
         let teams = self.list_teams().await?;
         Ok(teams)
@@ -229,61 +193,249 @@ impl EuropeOdds for OddsManager {
 
     /// add match data to persistence
     async fn create_match_info(&self, mut matches: Matches) -> Result<Matches, OddsError> {
-        // insert matches table
-        let id: i32 = sqlx::query(
-            "INSERT INTO euro.matches (league_id, league_name, home_team_id, home_team, away_team_id,
-                away_team, game_time, game_year, game_round, game_result, history_note, note, predict_game_result)
-                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13) RETURNING id",
-        )
-        .bind(matches.league_id)
-        .bind(&matches.league_name)
-        .bind(matches.home_team_id)
-        .bind(&matches.home_team)
-        .bind(matches.away_team_id)
-        .bind(&matches.away_team)
-        .bind(matches.game_time)
-        .bind(&matches.game_year)
-        .bind(&matches.game_round)
-        .bind(&matches.game_result)
-        .bind(&matches.history_note)
-        .bind(&matches.note)
-        .bind(&matches.predict_game_result)
-        .fetch_one(&self.conn)
-        .await?
-        .get(0);
-        // set the generated id
-        matches.id = id;
-
+        matches.id = insert_match(&mut *self.conn.acquire().await?, &matches).await?;
         Ok(matches)
     }
 
     /// update match data to persistence
     async fn update_match_info(&self, matches: Matches) -> Result<Matches, OddsError> {
-        // update matches table
-        let matches = sqlx::query_as::<_, Matches>(
-            "UPDATE euro.matches SET league_id = $1,
-        home_team_id = $2, home_team = $3, away_team_id = $4, away_team = $5, game_time = $6,
-        game_result = $7, note = $8, game_year = $9, game_round = $10,
-        league_name = $11, history_note = $12, predict_game_result = $13 WHERE id = $14 RETURNING *",
-        )
-        .bind(matches.league_id)
-        .bind(matches.home_team_id)
-        .bind(&matches.home_team)
-        .bind(matches.away_team_id)
-        .bind(&matches.away_team)
-        .bind(matches.game_time)
-        .bind(&matches.game_result)
-        .bind(&matches.note)
-        .bind(&matches.game_year)
-        .bind(&matches.game_round)
-        .bind(&matches.league_name)
-        .bind(&matches.history_note)
-        .bind(&matches.predict_game_result)
-        .bind(matches.id)
-        .fetch_one(&self.conn)
-        .await?;
+        update_match(&mut *self.conn.acquire().await?, &matches).await
+    }
 
-        Ok(matches)
+    /// save a match with its odds in one transaction
+    async fn save_match_info(&self, match_info: MatchInfo) -> Result<MatchInfo, OddsError> {
+        let MatchInfo { mut matches, odds } = match_info;
+        let mut tx = self.conn.begin().await?;
+        let stored: Vec<Odds> = if matches.id == 0 {
+            matches.id = insert_match(&mut tx, &matches).await?;
+            vec![]
+        } else {
+            matches = update_match(&mut tx, &matches).await?;
+            sqlx::query_as("SELECT * FROM euro.odds WHERE match_id = $1")
+                .bind(matches.id)
+                .fetch_all(&mut tx)
+                .await?
+        };
+
+        let changes = diff_odds(&stored, odds).known()?;
+        sqlx::query("DELETE FROM euro.odds WHERE id = ANY($1)")
+            .bind(&changes.delete)
+            .execute(&mut tx)
+            .await?;
+        let mut saved_odds = vec![];
+        for mut odd in changes.update {
+            odd.match_id = matches.id;
+            saved_odds.push(update_odds(&mut tx, &odd).await?);
+        }
+        for mut odd in changes.create {
+            odd.match_id = matches.id;
+            odd.id = insert_odds(&mut tx, matches.id, &odd).await?;
+            saved_odds.push(odd);
+        }
+        tx.commit().await?;
+
+        Ok(MatchInfo::new(matches, saved_odds))
+    }
+
+    /// import matches with their odds in one transaction
+    async fn import_matches(
+        &self,
+        rows: Vec<MatchImportRow>,
+        create_missing: bool,
+    ) -> Result<ImportReport, OddsError> {
+        let mut tx = self.conn.begin().await?;
+        let mut names = ImportNames::new(
+            sqlx::query_as("SELECT * FROM euro.leagues")
+                .fetch_all(&mut tx)
+                .await?,
+            sqlx::query_as(
+                "SELECT teams.*, leagues.name league_name FROM euro.teams teams,
+                euro.leagues leagues WHERE teams.league_id = leagues.id",
+            )
+            .fetch_all(&mut tx)
+            .await?,
+            sqlx::query_as("SELECT * FROM euro.bookmakers")
+                .fetch_all(&mut tx)
+                .await?,
+        );
+        let plan = match names.plan(rows, create_missing) {
+            Ok(plan) => plan,
+            Err(errors) => return Ok(ImportReport::failed(errors)),
+        };
+
+        let imported: Result<(), ImportRowError> = async {
+            for name in &plan.leagues {
+                let mut league = names.league(name);
+                league.id = insert_league(&mut tx, &league)
+                    .await
+                    .map_err(row_error(name.line))?;
+                names.add_league(league);
+            }
+            for name in &plan.teams {
+                let mut team = names.team(name);
+                team.id = insert_team(&mut tx, &team)
+                    .await
+                    .map_err(row_error(name.line))?;
+                names.add_team(team);
+            }
+            for name in &plan.bookmakers {
+                let mut bookmaker = names.bookmaker(name);
+                bookmaker.id = insert_bookmaker(&mut tx, &bookmaker)
+                    .await
+                    .map_err(row_error(name.line))?;
+                names.add_bookmaker(bookmaker);
+            }
+            for planned in &plan.matches {
+                let MatchInfo { matches, odds } = names.match_info(planned);
+                let id = insert_match(&mut tx, &matches)
+                    .await
+                    .map_err(row_error(planned.row.line))?;
+                for (odd, (line, _)) in odds.iter().zip(&planned.odds) {
+                    insert_odds(&mut tx, id, odd)
+                        .await
+                        .map_err(row_error(*line))?;
+                }
+            }
+            Ok(())
+        }
+        .await;
+
+        match imported {
+            Ok(()) => {
+                tx.commit().await?;
+                Ok(plan.report())
+            }
+            Err(error) => Ok(ImportReport::failed(vec![error])),
+        }
+    }
+
+    /// restore an archive in one transaction
+    async fn restore_archive(
+        &self,
+        archive: Archive,
+        policy: ConflictPolicy,
+    ) -> Result<RestoreReport, OddsError> {
+        let mut tx = self.conn.begin().await?;
+        let stored = Archive {
+            bookmakers: sqlx::query_as("SELECT * FROM euro.bookmakers")
+                .fetch_all(&mut tx)
+                .await?,
+            leagues: sqlx::query_as("SELECT * FROM euro.leagues")
+                .fetch_all(&mut tx)
+                .await?,
+            teams: sqlx::query_as(
+                "SELECT teams.*, leagues.name league_name FROM euro.teams teams,
+                euro.leagues leagues WHERE teams.league_id = leagues.id",
+            )
+            .fetch_all(&mut tx)
+            .await?,
+            matches: sqlx::query_as("SELECT * FROM euro.matches")
+                .fetch_all(&mut tx)
+                .await?,
+            odds: sqlx::query_as("SELECT * FROM euro.odds")
+                .fetch_all(&mut tx)
+                .await?,
+            snapshots: sqlx::query_as("SELECT * FROM euro.odds_snapshots")
+                .fetch_all(&mut tx)
+                .await?,
+            asian_handicaps: sqlx::query_as("SELECT * FROM euro.asian_handicap_odds")
+                .fetch_all(&mut tx)
+                .await?,
+            over_unders: sqlx::query_as("SELECT * FROM euro.over_under_odds")
+                .fetch_all(&mut tx)
+                .await?,
+            ..Archive::new(now())
+        };
+        let mut restore = Restore::new(policy, &stored);
+
+        for bookmaker in &archive.bookmakers {
+            let id = match restore.bookmaker(bookmaker)? {
+                RowAction::Create(row) => insert_bookmaker(&mut tx, &row).await?,
+                RowAction::Overwrite(row) => {
+                    update_bookmaker(&mut tx, &row).await?;
+                    row.id
+                }
+                RowAction::Skip(id) => id,
+            };
+            restore.bookmaker_restored(bookmaker, id);
+        }
+        for league in &archive.leagues {
+            let id = match restore.league(league)? {
+                RowAction::Create(row) => insert_league(&mut tx, &row).await?,
+                RowAction::Overwrite(row) => {
+                    update_league(&mut tx, &row).await?;
+                    row.id
+                }
+                RowAction::Skip(id) => id,
+            };
+            restore.league_restored(league, id);
+        }
+        for team in &archive.teams {
+            let id = match restore.team(team)? {
+                RowAction::Create(row) => insert_team(&mut tx, &row).await?,
+                RowAction::Overwrite(row) => {
+                    update_team(&mut tx, &row).await?;
+                    row.id
+                }
+                RowAction::Skip(id) => id,
+            };
+            restore.team_restored(team, id);
+        }
+        for matches in &archive.matches {
+            let id = match restore.matches(matches)? {
+                RowAction::Create(row) => insert_match(&mut tx, &row).await?,
+                RowAction::Overwrite(row) => update_match(&mut tx, &row).await?.id,
+                RowAction::Skip(id) => id,
+            };
+            restore.matches_restored(matches, id);
+        }
+        for odd in &archive.odds {
+            match restore.odds(odd)? {
+                RowAction::Create(row) => {
+                    insert_odds(&mut tx, row.match_id, &row).await?;
+                }
+                RowAction::Overwrite(row) => {
+                    update_odds(&mut tx, &row).await?;
+                }
+                RowAction::Skip(_) => {}
+            }
+        }
+        for snapshot in &archive.snapshots {
+            match restore.snapshot(snapshot)? {
+                RowAction::Create(row) => {
+                    insert_snapshot(&mut tx, &row).await?;
+                }
+                RowAction::Overwrite(row) => {
+                    update_snapshot(&mut tx, &row).await?;
+                }
+                RowAction::Skip(_) => {}
+            }
+        }
+        for odds in &archive.asian_handicaps {
+            match restore.asian_handicap(odds)? {
+                RowAction::Create(row) => {
+                    insert_asian_handicap(&mut tx, row.match_id, &row).await?;
+                }
+                RowAction::Overwrite(row) => {
+                    update_asian_handicap(&mut tx, &row).await?;
+                }
+                RowAction::Skip(_) => {}
+            }
+        }
+        for odds in &archive.over_unders {
+            match restore.over_under(odds)? {
+                RowAction::Create(row) => {
+                    insert_over_under(&mut tx, row.match_id, &row).await?;
+                }
+                RowAction::Overwrite(row) => {
+                    update_over_under(&mut tx, &row).await?;
+                }
+                RowAction::Skip(_) => {}
+            }
+        }
+
+        tx.commit().await?;
+        Ok(restore.report)
     }
 
     /// delete match data from persistence
@@ -296,21 +448,49 @@ impl EuropeOdds for OddsManager {
         Ok(count.rows_affected() as i32)
     }
 
-    /// query match data by conditions
-    async fn query_match_info(&self, query: MatchInfoQuery) -> Result<Vec<Matches>, OddsError> {
-        let match_infos =
-            sqlx::query_as("select * from euro.query($1, $2, $3, $4, $5, $6, $7, $8)")
-                .bind(query.book_maker_id)
-                .bind(query.league_id)
-                .bind(query.team_id)
-                .bind(query.game_year)
-                .bind(query.game_round)
-                .bind(query.is_desc)
-                .bind(query.cursor)
-                .bind(query.page_size)
+    /// query a page of match data meeting the filter
+    async fn query_match_info(&self, filter: MatchFilter) -> Result<Page<Matches>, OddsError> {
+        let cursor = filter.keyset()?;
+        let total: i64 = match_count_query::<Postgres>("euro.", &filter)
+            .build()
+            .fetch_one(&self.conn)
+            .await?
+            .try_get(0)?;
+        let match_infos = match_query::<Postgres>("euro.", &filter, cursor.as_ref())
+            .build_query_as()
+            .fetch_all(&self.conn)
+            .await?;
+        Ok(match_page(&filter, cursor.as_ref(), match_infos, total))
+    }
+
+    /// query the page of match data meeting the filter with the odds of every match
+    async fn query_match_info_with_odds(
+        &self,
+        filter: MatchFilter,
+    ) -> Result<Vec<MatchInfo>, OddsError> {
+        let cursor = filter.keyset()?;
+        let matches = match_query::<Postgres>("euro.", &filter, cursor.as_ref())
+            .build_query_as()
+            .fetch_all(&self.conn)
+            .await?;
+        let matches = page_items(&filter, cursor.as_ref(), matches);
+        let ids: Vec<MatchId> = matches.iter().map(|m| m.id).collect();
+        let odds =
+            sqlx::query_as("SELECT * FROM euro.odds WHERE match_id = ANY($1) ORDER BY id ASC")
+                .bind(&ids)
                 .fetch_all(&self.conn)
                 .await?;
-        Ok(match_infos)
+        Ok(match_infos(matches, odds))
+    }
+
+    /// query match data by match id
+    async fn query_match_info_by_id(&self, id: MatchId) -> Result<Matches, OddsError> {
+        let matches = sqlx::query_as("SELECT * FROM euro.matches WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&self.conn)
+            .await?
+            .ok_or(OddsError::NotFound)?;
+        Ok(matches)
     }
 
     /// query odds data by match id
@@ -324,50 +504,13 @@ impl EuropeOdds for OddsManager {
 
     /// add match data to persistence
     async fn create_odd_info(&self, id: MatchId, mut odd: Odds) -> Result<Odds, OddsError> {
-        // insert into odds table
-        let id = sqlx::query("INSERT INTO euro.odds (match_id, bookmaker_id, bookmaker_name,
-                home_win_start, draw_start, away_win_start,home_win_end, draw_end, away_win_end, note)
-                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10) RETURNING id")
-                    .bind(id)
-                    .bind(odd.bookmaker_id)
-                    .bind(&odd.bookmaker_name)
-                    .bind(&odd.home_win_start)
-                    .bind(&odd.draw_start)
-                    .bind(&odd.away_win_start)
-                    .bind(&odd.home_win_end)
-                    .bind(&odd.draw_end)
-                    .bind(&odd.away_win_end)
-                    .bind(&odd.note)
-                    .fetch_one(&self.conn)
-                    .await?
-                    .get(0);
-
-        odd.id = id;
+        odd.id = insert_odds(&self.conn, id, &odd).await?;
         Ok(odd)
     }
 
     /// update match data to persistence
     async fn update_odd_info(&self, odd: Odds) -> Result<Odds, OddsError> {
-        let odd_info = sqlx::query_as::<_, Odds>(
-            "UPDATE euro.odds SET bookmaker_id = $1, home_win_start = $2, draw_start = $3,
-             away_win_start = $4, home_win_end = $5, draw_end = $6, away_win_end = $7, note = $8,
-             bookmaker_name= $9 WHERE match_id = $10 and id = $11 RETURNING *",
-        )
-        .bind(odd.bookmaker_id)
-        .bind(odd.home_win_start)
-        .bind(odd.draw_start)
-        .bind(odd.away_win_start)
-        .bind(odd.home_win_end)
-        .bind(odd.draw_end)
-        .bind(odd.away_win_end)
-        .bind(&odd.note)
-        .bind(&odd.bookmaker_name)
-        .bind(odd.match_id)
-        .bind(odd.id)
-        .fetch_one(&self.conn)
-        .await?;
-
-        Ok(odd_info)
+        update_odds(&self.conn, &odd).await
     }
 
     /// delete match data from persistence
@@ -379,337 +522,754 @@ impl EuropeOdds for OddsManager {
 
         Ok(count.rows_affected() as i32)
     }
-}
 
-#[cfg(test)]
-mod tests {
+    /// query earlier matches whose odds from the same bookmaker are within the tolerances
+    async fn query_similar_odds(&self, query: SimilarOddsQuery) -> Result<SimilarOdds, OddsError> {
+        let odds: Odds =
+            sqlx::query_as("SELECT * FROM euro.odds WHERE match_id = $1 AND bookmaker_id = $2")
+                .bind(query.match_id)
+                .bind(query.bookmaker_id)
+                .fetch_optional(&self.conn)
+                .await?
+                .ok_or(OddsError::NotFound)?;
+        let game_time: NaiveDateTime =
+            sqlx::query("SELECT game_time FROM euro.matches WHERE id = $1")
+                .bind(query.match_id)
+                .fetch_one(&self.conn)
+                .await?
+                .get(0);
+
+        let (start_tolerance, end_tolerance) =
+            effective_tolerances(&odds, query.start_tolerance, query.end_tolerance);
+        let similar: Vec<Odds> = sqlx::query_as(
+            "SELECT odds.* FROM euro.odds odds, euro.matches matches
+            WHERE odds.match_id = matches.id AND odds.bookmaker_id = $1
+            AND matches.id <> $2 AND matches.game_time < $3
+            AND ($4 = 0 OR matches.league_id = $4)
+            AND ($5::VARCHAR IS NULL OR matches.game_year = $5)
+            AND ($6::NUMERIC IS NULL OR (ABS(odds.home_win_start - $7) <= $6
+                AND ABS(odds.draw_start - $8) <= $6 AND ABS(odds.away_win_start - $9) <= $6))
+            AND ($10::NUMERIC IS NULL OR (ABS(odds.home_win_end - $11) <= $10
+                AND ABS(odds.draw_end - $12) <= $10 AND ABS(odds.away_win_end - $13) <= $10))
+            ORDER BY matches.game_time DESC",
+        )
+        .bind(query.bookmaker_id)
+        .bind(query.match_id)
+        .bind(game_time)
+        .bind(query.league_id)
+        .bind(&query.game_year)
+        .bind(&start_tolerance)
+        .bind(&odds.home_win_start)
+        .bind(&odds.draw_start)
+        .bind(&odds.away_win_start)
+        .bind(&end_tolerance)
+        .bind(&odds.home_win_end)
+        .bind(&odds.draw_end)
+        .bind(&odds.away_win_end)
+        .fetch_all(&self.conn)
+        .await?;
 
-    use chrono::NaiveDateTime;
-    use data::{BookMakerBuilder, LeagueBuilder, MatchesBuilder, TeamBuilder};
+        let match_ids: Vec<MatchId> = similar.iter().map(|odd| odd.match_id).collect();
+        let matches: Vec<Matches> = sqlx::query_as("SELECT * FROM euro.matches WHERE id = ANY($1)")
+            .bind(&match_ids)
+            .fetch_all(&self.conn)
+            .await?;
 
-    use crate::test_util::TestConfig;
+        Ok(similar_odds(odds, similar, matches))
+    }
 
-    use super::*;
+    /// count the results of historical matches per band of closing home win prices
+    async fn query_odds_bands(&self, query: OddsBandQuery) -> Result<Vec<OddsBand>, OddsError> {
+        let matches: Vec<Matches> = sqlx::query_as(
+            "SELECT * FROM euro.matches WHERE ($1 = 0 OR league_id = $1)
+            AND ((home_goals IS NOT NULL AND away_goals IS NOT NULL) OR game_result IS NOT NULL)",
+        )
+        .bind(query.league_id)
+        .fetch_all(&self.conn)
+        .await?;
+        let odds: Vec<Odds> = sqlx::query_as(
+            "SELECT odds.* FROM euro.odds odds, euro.matches matches
+            WHERE odds.match_id = matches.id AND odds.home_win_end > 0
+            AND ($1 = 0 OR matches.league_id = $1) AND ($2 = 0 OR odds.bookmaker_id = $2)
+            AND ((matches.home_goals IS NOT NULL AND matches.away_goals IS NOT NULL)
+            OR matches.game_result IS NOT NULL)",
+        )
+        .bind(query.league_id)
+        .bind(query.bookmaker_id)
+        .fetch_all(&self.conn)
+        .await?;
 
-    #[tokio::test]
-    async fn add_bookmaker_should_be_work() {
-        let config = TestConfig::new().await;
-        let odds_manager = OddsManager::new(config.tps.get_pool().await);
-        let bm = odds_manager
-            .create_bookermaker(
-                BookMakerBuilder::default()
-                    .name("威廉希尔")
-                    .url("https://sports.williamhill.com/betting/en-gb")
-                    .note("第一参考的博彩网站")
-                    .build()
-                    .unwrap(),
-            )
-            .await
-            .unwrap();
-        assert_eq!(bm.len(), 1);
+        Ok(odds_bands(&matches, &odds, &query))
     }
 
-    #[tokio::test]
-    async fn update_bookmaker_should_be_work() {
-        let config = TestConfig::new().await;
-        let odds_manager = OddsManager::new(config.tps.get_pool().await);
-        // add bookmaker
-        let mut bms = odds_manager
-            .create_bookermaker(
-                BookMakerBuilder::default()
-                    .name("威廉希尔")
-                    .url("https://sports.williamhill.com/betting/en-gb")
-                    .note("第一参考的博彩网站")
-                    .build()
-                    .unwrap(),
-            )
-            .await
-            .unwrap();
-        // update bookmaker
-        let mut bm = bms.pop().unwrap();
-        bm.name = "威廉希尔1".into();
-        let mut bm1 = odds_manager.update_bookermaker(bm).await.unwrap();
-        assert_eq!(bm1.pop().unwrap().name, "威廉希尔1");
+    /// compare the predicted outcomes of the matches with their results
+    async fn query_prediction_scorecard(
+        &self,
+        query: ScorecardQuery,
+    ) -> Result<PredictionScorecard, OddsError> {
+        let mut builder = QueryBuilder::<Postgres>::new(
+            "SELECT * FROM euro.matches WHERE predict_game_result IS NOT NULL",
+        );
+        if let Some(league_id) = query.league_id {
+            builder.push(" AND league_id = ").push_bind(league_id);
+        }
+        let matches: Vec<Matches> = builder.build_query_as().fetch_all(&self.conn).await?;
+
+        Ok(prediction_scorecard(&matches, &query))
     }
 
-    #[tokio::test]
-    async fn delete_bookmaker_should_be_work() {
-        let config = TestConfig::new().await;
-        let odds_manager = OddsManager::new(config.tps.get_pool().await);
-        // add bookmaker
-        let mut bm = odds_manager
-            .create_bookermaker(
-                BookMakerBuilder::default()
-                    .name("威廉希尔")
-                    .url("https://sports.williamhill.com/betting/en-gb")
-                    .note("第一参考的博彩网站")
-                    .build()
-                    .unwrap(),
+    /// append a price snapshot and derive the start and end odds in the same transaction
+    async fn append_odds_snapshot(
+        &self,
+        mut snapshot: OddsSnapshot,
+    ) -> Result<OddsSnapshot, OddsError> {
+        snapshot.validate()?;
+        let mut tx = self.conn.begin().await?;
+        let bookmaker_name: String = sqlx::query("SELECT name FROM euro.bookmakers WHERE id = $1")
+            .bind(snapshot.bookmaker_id)
+            .fetch_optional(&mut tx)
+            .await?
+            .ok_or(OddsError::NotFound)?
+            .get(0);
+        snapshot = insert_snapshot(&mut tx, &snapshot).await?;
+
+        let timeline: Vec<OddsSnapshot> = sqlx::query_as(
+            "SELECT * FROM euro.odds_snapshots WHERE match_id = $1 AND bookmaker_id = $2
+            ORDER BY recorded_at ASC, id ASC",
+        )
+        .bind(snapshot.match_id)
+        .bind(snapshot.bookmaker_id)
+        .fetch_all(&mut tx)
+        .await?;
+        let (Some(first), Some(last)) = (timeline.first(), timeline.last()) else {
+            return Err(OddsError::NotFound);
+        };
+        let updated = sqlx::query(
+            "UPDATE euro.odds SET home_win_start = $1, draw_start = $2, away_win_start = $3,
+            home_win_end = $4, draw_end = $5, away_win_end = $6, bookmaker_name = $7
+            WHERE match_id = $8 AND bookmaker_id = $9",
+        )
+        .bind(&first.home_win)
+        .bind(&first.draw)
+        .bind(&first.away_win)
+        .bind(&last.home_win)
+        .bind(&last.draw)
+        .bind(&last.away_win)
+        .bind(&bookmaker_name)
+        .bind(snapshot.match_id)
+        .bind(snapshot.bookmaker_id)
+        .execute(&mut tx)
+        .await?;
+        if updated.rows_affected() == 0 {
+            sqlx::query(
+                "INSERT INTO euro.odds (match_id, bookmaker_id, bookmaker_name, home_win_start,
+                draw_start, away_win_start, home_win_end, draw_end, away_win_end, note)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, '')",
             )
-            .await
-            .unwrap();
-        // delete bookmaker
-        let bms = odds_manager
-            .delete_bookermaker(bm.pop().unwrap().id)
-            .await
-            .unwrap();
-        assert_eq!(bms.len(), 0);
-    }
-
-    #[tokio::test]
-    async fn create_league_should_be_work() {
-        let config = TestConfig::new().await;
-        let odds_manager = OddsManager::new(config.tps.get_pool().await);
-        // add league
-        let bm = odds_manager
-            .create_league(LeagueBuilder::default().name("英超").build().unwrap())
-            .await
-            .unwrap();
-        assert_eq!(bm.len(), 1);
+            .bind(snapshot.match_id)
+            .bind(snapshot.bookmaker_id)
+            .bind(&bookmaker_name)
+            .bind(&first.home_win)
+            .bind(&first.draw)
+            .bind(&first.away_win)
+            .bind(&last.home_win)
+            .bind(&last.draw)
+            .bind(&last.away_win)
+            .execute(&mut tx)
+            .await?;
+        }
+        tx.commit().await?;
+
+        Ok(snapshot)
     }
 
-    #[tokio::test]
-    async fn update_league_should_be_work() {
-        let config = TestConfig::new().await;
-        let odds_manager = OddsManager::new(config.tps.get_pool().await);
-        // add league
-        let mut bms = odds_manager
-            .create_league(LeagueBuilder::default().name("英超").build().unwrap())
-            .await
-            .unwrap();
-        // update league info
-        let mut bm = bms.pop().unwrap();
-        bm.name = "英超1".into();
-        let bm1 = odds_manager.update_league(bm).await.unwrap();
-        assert_eq!(bm1.get(0).unwrap().name, "英超1");
-    }
-
-    #[tokio::test]
-    async fn delete_league_should_be_work() {
-        let config = TestConfig::new().await;
-        let odds_manager = OddsManager::new(config.tps.get_pool().await);
-        // add league
-        let mut bms = odds_manager
-            .create_league(LeagueBuilder::default().name("英超").build().unwrap())
-            .await
-            .unwrap();
-        // delete league info
-        let bm = bms.pop().unwrap();
-        let bm1 = odds_manager.delete_league(bm.id).await.unwrap();
-        assert_eq!(bm1.len(), 0);
-    }
-
-    #[tokio::test]
-    async fn create_team_should_be_work() {
-        let config = TestConfig::new().await;
-        let odds_manager = OddsManager::new(config.tps.get_pool().await);
-        // add league
-        let mut leagues = odds_manager
-            .create_league(LeagueBuilder::default().name("英超").build().unwrap())
-            .await
-            .unwrap();
-        // add team
-        let mut teams = odds_manager
-            .create_team(
-                TeamBuilder::default()
-                    .name("曼联")
-                    .league_id(leagues.pop().unwrap().id)
-                    .build()
-                    .unwrap(),
-            )
-            .await
-            .unwrap();
-        assert_eq!(teams.len(), 1);
-        let team = teams.pop().unwrap();
-        assert_eq!(1, team.league_id);
-        assert_eq!("曼联", team.name);
+    /// query the price snapshots of a bookmaker for a match in recorded order
+    async fn query_odds_timeline(
+        &self,
+        match_id: MatchId,
+        bookmaker_id: BookMakerId,
+    ) -> Result<Vec<OddsSnapshot>, OddsError> {
+        let timeline = sqlx::query_as(
+            "SELECT * FROM euro.odds_snapshots WHERE match_id = $1 AND bookmaker_id = $2
+            ORDER BY recorded_at ASC, id ASC",
+        )
+        .bind(match_id)
+        .bind(bookmaker_id)
+        .fetch_all(&self.conn)
+        .await?;
+
+        Ok(timeline)
     }
 
-    #[tokio::test]
-    async fn update_team_should_be_work() {
-        let config = TestConfig::new().await;
-        let odds_manager = OddsManager::new(config.tps.get_pool().await);
-        // add league
-        let mut leagues = odds_manager
-            .create_league(LeagueBuilder::default().name("英超").build().unwrap())
-            .await
-            .unwrap();
-        // add team
-        let mut teams = odds_manager
-            .create_team(
-                TeamBuilder::default()
-                    .name("曼联")
-                    .league_id(leagues.pop().unwrap().id)
-                    .build()
-                    .unwrap(),
-            )
-            .await
-            .unwrap();
-        // update team info
-        let mut team = teams.pop().unwrap();
-        team.name = "利物浦".into();
-        let team1 = odds_manager.update_team(team).await.unwrap();
-        assert_eq!(team1.get(0).unwrap().name, "利物浦");
+    /// query the price snapshots of every bookmaker for a match in recorded order
+    async fn query_odds_snapshots_by_id(
+        &self,
+        id: MatchId,
+    ) -> Result<Vec<OddsSnapshot>, OddsError> {
+        let snapshots = sqlx::query_as(
+            "SELECT * FROM euro.odds_snapshots WHERE match_id = $1
+            ORDER BY bookmaker_id ASC, recorded_at ASC, id ASC",
+        )
+        .bind(id)
+        .fetch_all(&self.conn)
+        .await?;
+
+        Ok(snapshots)
     }
 
-    #[tokio::test]
-    async fn delete_team_should_be_work() {
-        let config = TestConfig::new().await;
-        let odds_manager = OddsManager::new(config.tps.get_pool().await);
-        // add league
-        let mut leagues = odds_manager
-            .create_league(LeagueBuilder::default().name("英超").build().unwrap())
-            .await
-            .unwrap();
-        // add team
-        let mut teams = odds_manager
-            .create_team(
-                TeamBuilder::default()
-                    .name("曼联")
-                    .league_id(leagues.pop().unwrap().id)
-                    .build()
-                    .unwrap(),
-            )
-            .await
-            .unwrap();
-        // delete team info
-        let team = teams.pop().unwrap();
-        let team1 = odds_manager.delete_team(team.id).await.unwrap();
-        assert_eq!(team1.len(), 0);
+    /// query asian handicap odds by match id
+    async fn query_asian_handicap_by_id(
+        &self,
+        id: MatchId,
+    ) -> Result<Vec<AsianHandicapOdds>, OddsError> {
+        let odds = sqlx::query_as("SELECT * FROM euro.asian_handicap_odds WHERE match_id = $1")
+            .bind(id)
+            .fetch_all(&self.conn)
+            .await?;
+        Ok(odds)
     }
 
-    #[tokio::test]
-    async fn create_matches_info_should_be_work() {
-        let config = TestConfig::new().await;
-        let odds_manager = OddsManager::new(config.tps.get_pool().await);
-        let matches = MatchesBuilder::default()
-            .league_id(1)
-            .league_name("英超")
-            .home_team_id(1)
-            .home_team("曼联")
-            .away_team_id(2)
-            .away_team("利物浦")
-            .game_time(NaiveDateTime::default())
-            .game_result("2:1")
-            .game_round("1")
-            .game_year("2022")
-            .build()
-            .unwrap();
-        // let odd_1 = OddsBuilder::default()
-        //     .bookmaker_id(1)
-        //     .home_win_start_setter("1.2")
-        //     .draw_start_setter("2.3")
-        //     .away_win_start_setter("3.4")
-        //     .home_win_end_setter("1.5")
-        //     .draw_end_setter("2.6")
-        //     .away_win_end_setter("3.7")
-        //     .build()
-        //     .unwrap();
-        // let odd_2 = OddsBuilder::default()
-        //     .bookmaker_id(2)
-        //     .home_win_start_setter("1.4")
-        //     .draw_start_setter("2.25")
-        //     .away_win_start_setter("3.45")
-        //     .home_win_end_setter("1.62")
-        //     .draw_end_setter("2.65")
-        //     .away_win_end_setter("3.72")
-        //     .build()
-        //     .unwrap();
-        // let odds = vec![odd_1, odd_2];
-
-        // add match info
-        let match_info = odds_manager.create_match_info(matches).await.unwrap();
-        assert!(match_info.id != 0);
-    }
-
-    #[tokio::test]
-    async fn update_matches_info_should_be_work() {
-        let config = TestConfig::new().await;
-        let odds_manager = OddsManager::new(config.tps.get_pool().await);
-        // add match info
-        let matches = MatchesBuilder::default()
-            .league_id(1)
-            .league_name("英超")
-            .home_team_id(1)
-            .home_team("曼联")
-            .away_team_id(2)
-            .away_team("利物浦")
-            .game_time(NaiveDateTime::default())
-            .game_result("2:1")
-            .build()
-            .unwrap();
-        // let odd_1 = OddsBuilder::default()
-        //     .bookmaker_id(1)
-        //     .home_win_start_setter("1.2")
-        //     .draw_start_setter("2.3")
-        //     .away_win_start_setter("3.4")
-        //     .home_win_end_setter("1.5")
-        //     .draw_end_setter("2.6")
-        //     .away_win_end_setter("3.7")
-        //     .build()
-        //     .unwrap();
-        // let odd_2 = OddsBuilder::default()
-        //     .bookmaker_id(2)
-        //     .home_win_start_setter("1.4")
-        //     .draw_start_setter("2.25")
-        //     .away_win_start_setter("3.45")
-        //     .home_win_end_setter("1.62")
-        //     .draw_end_setter("2.65")
-        //     .away_win_end_setter("3.72")
-        //     .build()
-        //     .unwrap();
-        // let odds = vec![odd_1, odd_2];
-        let mut match_info = odds_manager.create_match_info(matches).await.unwrap();
-        // update match info
-        match_info.game_result = Some("1:1".into());
-        let update_match = odds_manager.update_match_info(match_info).await.unwrap();
-        assert_eq!(update_match.game_result.unwrap(), "1:1");
-        // assert_eq!(match_info.odds[0].home_win_start, "3.01".parse().unwrap());
-        // assert_eq!(match_info.odds[1].home_win_start, "4.05".parse().unwrap());
-    }
-
-    #[tokio::test]
-    async fn delete_matches_info_should_be_work() {
+    /// add asian handicap odds of a match to persistence
+    async fn create_asian_handicap(
+        &self,
+        id: MatchId,
+        mut odds: AsianHandicapOdds,
+    ) -> Result<AsianHandicapOdds, OddsError> {
+        odds.id = insert_asian_handicap(&self.conn, id, &odds).await?;
+        Ok(odds)
+    }
+
+    /// update asian handicap odds to persistence
+    async fn update_asian_handicap(
+        &self,
+        odds: AsianHandicapOdds,
+    ) -> Result<AsianHandicapOdds, OddsError> {
+        update_asian_handicap(&self.conn, &odds).await
+    }
+
+    /// save the asian handicap odds of a match in one transaction
+    async fn save_asian_handicaps(
+        &self,
+        id: MatchId,
+        odds: Vec<AsianHandicapOdds>,
+    ) -> Result<Vec<AsianHandicapOdds>, OddsError> {
+        let mut tx = self.conn.begin().await?;
+        let stored: Vec<AsianHandicapOdds> =
+            sqlx::query_as("SELECT * FROM euro.asian_handicap_odds WHERE match_id = $1")
+                .bind(id)
+                .fetch_all(&mut tx)
+                .await?;
+
+        let changes = diff_odds(&stored, odds).known()?;
+        sqlx::query("DELETE FROM euro.asian_handicap_odds WHERE id = ANY($1)")
+            .bind(&changes.delete)
+            .execute(&mut tx)
+            .await?;
+        let mut saved_odds = vec![];
+        for mut odds in changes.update {
+            odds.match_id = id;
+            saved_odds.push(update_asian_handicap(&mut tx, &odds).await?);
+        }
+        for mut odds in changes.create {
+            odds.match_id = id;
+            odds.id = insert_asian_handicap(&mut tx, id, &odds).await?;
+            saved_odds.push(odds);
+        }
+        tx.commit().await?;
+
+        Ok(saved_odds)
+    }
+
+    /// delete asian handicap odds from persistence
+    async fn delete_asian_handicap(&self, id: OddId) -> Result<i32, OddsError> {
+        let count = sqlx::query("DELETE FROM euro.asian_handicap_odds WHERE id = $1")
+            .bind(id)
+            .execute(&self.conn)
+            .await?;
+
+        Ok(count.rows_affected() as i32)
+    }
+
+    /// query over/under odds by match id
+    async fn query_over_under_by_id(&self, id: MatchId) -> Result<Vec<OverUnderOdds>, OddsError> {
+        let odds = sqlx::query_as("SELECT * FROM euro.over_under_odds WHERE match_id = $1")
+            .bind(id)
+            .fetch_all(&self.conn)
+            .await?;
+        Ok(odds)
+    }
+
+    /// add over/under odds of a match to persistence
+    async fn create_over_under(
+        &self,
+        id: MatchId,
+        mut odds: OverUnderOdds,
+    ) -> Result<OverUnderOdds, OddsError> {
+        odds.id = insert_over_under(&self.conn, id, &odds).await?;
+        Ok(odds)
+    }
+
+    /// update over/under odds to persistence
+    async fn update_over_under(&self, odds: OverUnderOdds) -> Result<OverUnderOdds, OddsError> {
+        update_over_under(&self.conn, &odds).await
+    }
+
+    /// save the over/under odds of a match in one transaction
+    async fn save_over_unders(
+        &self,
+        id: MatchId,
+        odds: Vec<OverUnderOdds>,
+    ) -> Result<Vec<OverUnderOdds>, OddsError> {
+        let mut tx = self.conn.begin().await?;
+        let stored: Vec<OverUnderOdds> =
+            sqlx::query_as("SELECT * FROM euro.over_under_odds WHERE match_id = $1")
+                .bind(id)
+                .fetch_all(&mut tx)
+                .await?;
+
+        let changes = diff_odds(&stored, odds).known()?;
+        sqlx::query("DELETE FROM euro.over_under_odds WHERE id = ANY($1)")
+            .bind(&changes.delete)
+            .execute(&mut tx)
+            .await?;
+        let mut saved_odds = vec![];
+        for mut odds in changes.update {
+            odds.match_id = id;
+            saved_odds.push(update_over_under(&mut tx, &odds).await?);
+        }
+        for mut odds in changes.create {
+            odds.match_id = id;
+            odds.id = insert_over_under(&mut tx, id, &odds).await?;
+            saved_odds.push(odds);
+        }
+        tx.commit().await?;
+
+        Ok(saved_odds)
+    }
+
+    /// delete over/under odds from persistence
+    async fn delete_over_under(&self, id: OddId) -> Result<i32, OddsError> {
+        let count = sqlx::query("DELETE FROM euro.over_under_odds WHERE id = $1")
+            .bind(id)
+            .execute(&self.conn)
+            .await?;
+
+        Ok(count.rows_affected() as i32)
+    }
+}
+
+/// insert a bookmaker and return the generated id
+async fn insert_bookmaker<'e, E: PgExecutor<'e>>(
+    executor: E,
+    bookmaker: &BookMaker,
+) -> Result<BookMakerId, OddsError> {
+    bookmaker.validate()?;
+    let id = sqlx::query(
+        "INSERT INTO euro.bookmakers (name, url, note) VALUES ($1, $2, $3) RETURNING id",
+    )
+    .bind(&bookmaker.name)
+    .bind(&bookmaker.url)
+    .bind(&bookmaker.note)
+    .fetch_one(executor)
+    .await?
+    .get(0);
+
+    Ok(id)
+}
+
+/// update a bookmaker by id
+async fn update_bookmaker<'e, E: PgExecutor<'e>>(
+    executor: E,
+    bookmaker: &BookMaker,
+) -> Result<(), OddsError> {
+    bookmaker.validate()?;
+    sqlx::query(
+        "UPDATE euro.bookmakers SET name = $1, url = $2, note = $3 WHERE id = $4 RETURNING *",
+    )
+    .bind(&bookmaker.name)
+    .bind(&bookmaker.url)
+    .bind(&bookmaker.note)
+    .bind(bookmaker.id)
+    .fetch_one(executor)
+    .await?;
+
+    Ok(())
+}
+
+/// insert a league and return the generated id
+async fn insert_league<'e, E: PgExecutor<'e>>(
+    executor: E,
+    league: &League,
+) -> Result<LeagueId, OddsError> {
+    league.validate()?;
+    let id = sqlx::query("INSERT INTO euro.leagues (name, note) VALUES ($1, $2) RETURNING id")
+        .bind(&league.name)
+        .bind(&league.note)
+        .fetch_one(executor)
+        .await?
+        .get(0);
+
+    Ok(id)
+}
+
+/// update a league by id
+async fn update_league<'e, E: PgExecutor<'e>>(
+    executor: E,
+    league: &League,
+) -> Result<(), OddsError> {
+    league.validate()?;
+    sqlx::query("UPDATE euro.leagues SET name = $1, note = $2 WHERE id = $3 RETURNING *")
+        .bind(&league.name)
+        .bind(&league.note)
+        .bind(league.id)
+        .fetch_one(executor)
+        .await?;
+
+    Ok(())
+}
+
+/// insert a team and return the generated id
+async fn insert_team<'e, E: PgExecutor<'e>>(executor: E, team: &Team) -> Result<TeamId, OddsError> {
+    team.validate()?;
+    let id = sqlx::query(
+        "INSERT INTO euro.teams (name, league_id, note) VALUES ($1, $2, $3) RETURNING id",
+    )
+    .bind(&team.name)
+    .bind(team.league_id)
+    .bind(&team.note)
+    .fetch_one(executor)
+    .await?
+    .get(0);
+
+    Ok(id)
+}
+
+/// update a team by id
+async fn update_team<'e, E: PgExecutor<'e>>(executor: E, team: &Team) -> Result<(), OddsError> {
+    team.validate()?;
+    sqlx::query(
+        "UPDATE euro.teams SET name = $1, league_id = $2, note = $3 WHERE id = $4 RETURNING *",
+    )
+    .bind(&team.name)
+    .bind(team.league_id)
+    .bind(&team.note)
+    .bind(team.id)
+    .fetch_one(executor)
+    .await?;
+
+    Ok(())
+}
+
+/// validate a match along with its home and away teams as stored
+async fn validate_match(conn: &mut PgConnection, matches: &Matches) -> Result<(), OddsError> {
+    let teams: Vec<Team> = sqlx::query_as(
+        "SELECT teams.*, leagues.name league_name FROM euro.teams teams,
+        euro.leagues leagues WHERE teams.league_id = leagues.id
+        AND teams.id IN ($1, $2)",
+    )
+    .bind(matches.home_team_id)
+    .bind(matches.away_team_id)
+    .fetch_all(&mut *conn)
+    .await?;
+
+    matches.validate_with(&teams)
+}
+
+/// insert a match and return the generated id
+async fn insert_match(conn: &mut PgConnection, matches: &Matches) -> Result<MatchId, OddsError> {
+    validate_match(&mut *conn, matches).await?;
+    let (home_goals, away_goals) = matches.full_time_goals();
+    let id = sqlx::query(
+        "INSERT INTO euro.matches (league_id, league_name, home_team_id, home_team, away_team_id,
+            away_team, game_time, game_year, game_round, game_result, history_note, note, predict_game_result,
+            home_goals, away_goals, half_home_goals, half_away_goals)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17) RETURNING id",
+    )
+    .bind(matches.league_id)
+    .bind(&matches.league_name)
+    .bind(matches.home_team_id)
+    .bind(&matches.home_team)
+    .bind(matches.away_team_id)
+    .bind(&matches.away_team)
+    .bind(matches.game_time)
+    .bind(&matches.game_year)
+    .bind(&matches.game_round)
+    .bind(&matches.game_result)
+    .bind(&matches.history_note)
+    .bind(&matches.note)
+    .bind(&matches.predict_game_result)
+    .bind(home_goals)
+    .bind(away_goals)
+    .bind(matches.half_home_goals)
+    .bind(matches.half_away_goals)
+    .fetch_one(conn)
+    .await?
+    .get(0);
+
+    Ok(id)
+}
+
+/// update a match by id and return the stored row
+async fn update_match(conn: &mut PgConnection, matches: &Matches) -> Result<Matches, OddsError> {
+    validate_match(&mut *conn, matches).await?;
+    let (home_goals, away_goals) = matches.full_time_goals();
+    let matches = sqlx::query_as::<_, Matches>(
+        "UPDATE euro.matches SET league_id = $1,
+        home_team_id = $2, home_team = $3, away_team_id = $4, away_team = $5, game_time = $6,
+        game_result = $7, note = $8, game_year = $9, game_round = $10,
+        league_name = $11, history_note = $12, predict_game_result = $13, home_goals = $14,
+        away_goals = $15, half_home_goals = $16, half_away_goals = $17 WHERE id = $18 RETURNING *",
+    )
+    .bind(matches.league_id)
+    .bind(matches.home_team_id)
+    .bind(&matches.home_team)
+    .bind(matches.away_team_id)
+    .bind(&matches.away_team)
+    .bind(matches.game_time)
+    .bind(&matches.game_result)
+    .bind(&matches.note)
+    .bind(&matches.game_year)
+    .bind(&matches.game_round)
+    .bind(&matches.league_name)
+    .bind(&matches.history_note)
+    .bind(&matches.predict_game_result)
+    .bind(home_goals)
+    .bind(away_goals)
+    .bind(matches.half_home_goals)
+    .bind(matches.half_away_goals)
+    .bind(matches.id)
+    .fetch_one(conn)
+    .await?;
+
+    Ok(matches)
+}
+
+/// insert odds of a match and return the generated id
+async fn insert_odds<'e, E: PgExecutor<'e>>(
+    executor: E,
+    id: MatchId,
+    odd: &Odds,
+) -> Result<OddId, OddsError> {
+    odd.validate()?;
+    let id = sqlx::query(
+        "INSERT INTO euro.odds (match_id, bookmaker_id, bookmaker_name,
+            home_win_start, draw_start, away_win_start,home_win_end, draw_end, away_win_end, note)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10) RETURNING id",
+    )
+    .bind(id)
+    .bind(odd.bookmaker_id)
+    .bind(&odd.bookmaker_name)
+    .bind(&odd.home_win_start)
+    .bind(&odd.draw_start)
+    .bind(&odd.away_win_start)
+    .bind(&odd.home_win_end)
+    .bind(&odd.draw_end)
+    .bind(&odd.away_win_end)
+    .bind(&odd.note)
+    .fetch_one(executor)
+    .await?
+    .get(0);
+
+    Ok(id)
+}
+
+/// update odds by match id and id and return the stored row
+async fn update_odds<'e, E: PgExecutor<'e>>(executor: E, odd: &Odds) -> Result<Odds, OddsError> {
+    odd.validate()?;
+    let odd = sqlx::query_as::<_, Odds>(
+        "UPDATE euro.odds SET bookmaker_id = $1, home_win_start = $2, draw_start = $3,
+         away_win_start = $4, home_win_end = $5, draw_end = $6, away_win_end = $7, note = $8,
+         bookmaker_name= $9 WHERE match_id = $10 and id = $11 RETURNING *",
+    )
+    .bind(odd.bookmaker_id)
+    .bind(&odd.home_win_start)
+    .bind(&odd.draw_start)
+    .bind(&odd.away_win_start)
+    .bind(&odd.home_win_end)
+    .bind(&odd.draw_end)
+    .bind(&odd.away_win_end)
+    .bind(&odd.note)
+    .bind(&odd.bookmaker_name)
+    .bind(odd.match_id)
+    .bind(odd.id)
+    .fetch_one(executor)
+    .await?;
+
+    Ok(odd)
+}
+
+/// insert a price snapshot and return the stored row
+async fn insert_snapshot<'e, E: PgExecutor<'e>>(
+    executor: E,
+    snapshot: &OddsSnapshot,
+) -> Result<OddsSnapshot, OddsError> {
+    snapshot.validate()?;
+    // the snapshot is recorded now when no time is given
+    let recorded_at =
+        (snapshot.recorded_at != NaiveDateTime::default()).then_some(snapshot.recorded_at);
+    let snapshot = sqlx::query_as(
+        "INSERT INTO euro.odds_snapshots (match_id, bookmaker_id, home_win, draw, away_win,
+        recorded_at) VALUES ($1, $2, $3, $4, $5, COALESCE($6, NOW())) RETURNING *",
+    )
+    .bind(snapshot.match_id)
+    .bind(snapshot.bookmaker_id)
+    .bind(&snapshot.home_win)
+    .bind(&snapshot.draw)
+    .bind(&snapshot.away_win)
+    .bind(recorded_at)
+    .fetch_one(executor)
+    .await?;
+
+    Ok(snapshot)
+}
+
+/// update the prices of a price snapshot by id and return the stored row
+async fn update_snapshot<'e, E: PgExecutor<'e>>(
+    executor: E,
+    snapshot: &OddsSnapshot,
+) -> Result<OddsSnapshot, OddsError> {
+    snapshot.validate()?;
+    let snapshot = sqlx::query_as(
+        "UPDATE euro.odds_snapshots SET home_win = $1, draw = $2, away_win = $3
+        WHERE id = $4 RETURNING *",
+    )
+    .bind(&snapshot.home_win)
+    .bind(&snapshot.draw)
+    .bind(&snapshot.away_win)
+    .bind(snapshot.id)
+    .fetch_one(executor)
+    .await?;
+
+    Ok(snapshot)
+}
+
+/// insert asian handicap odds of a match and return the generated id
+async fn insert_asian_handicap<'e, E: PgExecutor<'e>>(
+    executor: E,
+    id: MatchId,
+    odds: &AsianHandicapOdds,
+) -> Result<OddId, OddsError> {
+    odds.validate()?;
+    let id = sqlx::query(
+        "INSERT INTO euro.asian_handicap_odds (match_id, bookmaker_id, bookmaker_name,
+        line_start, home_price_start, away_price_start, line_end, home_price_end,
+        away_price_end, note) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10) RETURNING id",
+    )
+    .bind(id)
+    .bind(odds.bookmaker_id)
+    .bind(&odds.bookmaker_name)
+    .bind(&odds.line_start)
+    .bind(&odds.home_price_start)
+    .bind(&odds.away_price_start)
+    .bind(&odds.line_end)
+    .bind(&odds.home_price_end)
+    .bind(&odds.away_price_end)
+    .bind(&odds.note)
+    .fetch_one(executor)
+    .await?
+    .get(0);
+
+    Ok(id)
+}
+
+/// update asian handicap odds by match id and id and return the stored row
+async fn update_asian_handicap<'e, E: PgExecutor<'e>>(
+    executor: E,
+    odds: &AsianHandicapOdds,
+) -> Result<AsianHandicapOdds, OddsError> {
+    odds.validate()?;
+    let odds = sqlx::query_as(
+        "UPDATE euro.asian_handicap_odds SET bookmaker_id = $1, bookmaker_name = $2,
+        line_start = $3, home_price_start = $4, away_price_start = $5, line_end = $6,
+        home_price_end = $7, away_price_end = $8, note = $9
+        WHERE match_id = $10 AND id = $11 RETURNING *",
+    )
+    .bind(odds.bookmaker_id)
+    .bind(&odds.bookmaker_name)
+    .bind(&odds.line_start)
+    .bind(&odds.home_price_start)
+    .bind(&odds.away_price_start)
+    .bind(&odds.line_end)
+    .bind(&odds.home_price_end)
+    .bind(&odds.away_price_end)
+    .bind(&odds.note)
+    .bind(odds.match_id)
+    .bind(odds.id)
+    .fetch_one(executor)
+    .await?;
+
+    Ok(odds)
+}
+
+/// insert over/under odds of a match and return the generated id
+async fn insert_over_under<'e, E: PgExecutor<'e>>(
+    executor: E,
+    id: MatchId,
+    odds: &OverUnderOdds,
+) -> Result<OddId, OddsError> {
+    odds.validate()?;
+    let id = sqlx::query(
+        "INSERT INTO euro.over_under_odds (match_id, bookmaker_id, bookmaker_name,
+        line_start, over_price_start, under_price_start, line_end, over_price_end,
+        under_price_end, note) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10) RETURNING id",
+    )
+    .bind(id)
+    .bind(odds.bookmaker_id)
+    .bind(&odds.bookmaker_name)
+    .bind(&odds.line_start)
+    .bind(&odds.over_price_start)
+    .bind(&odds.under_price_start)
+    .bind(&odds.line_end)
+    .bind(&odds.over_price_end)
+    .bind(&odds.under_price_end)
+    .bind(&odds.note)
+    .fetch_one(executor)
+    .await?
+    .get(0);
+
+    Ok(id)
+}
+
+/// update over/under odds by match id and id and return the stored row
+async fn update_over_under<'e, E: PgExecutor<'e>>(
+    executor: E,
+    odds: &OverUnderOdds,
+) -> Result<OverUnderOdds, OddsError> {
+    odds.validate()?;
+    let odds = sqlx::query_as(
+        "UPDATE euro.over_under_odds SET bookmaker_id = $1, bookmaker_name = $2,
+        line_start = $3, over_price_start = $4, under_price_start = $5, line_end = $6,
+        over_price_end = $7, under_price_end = $8, note = $9
+        WHERE match_id = $10 AND id = $11 RETURNING *",
+    )
+    .bind(odds.bookmaker_id)
+    .bind(&odds.bookmaker_name)
+    .bind(&odds.line_start)
+    .bind(&odds.over_price_start)
+    .bind(&odds.under_price_start)
+    .bind(&odds.line_end)
+    .bind(&odds.over_price_end)
+    .bind(&odds.under_price_end)
+    .bind(&odds.note)
+    .bind(odds.match_id)
+    .bind(odds.id)
+    .fetch_one(executor)
+    .await?;
+
+    Ok(odds)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_util::TestConfig;
+
+    use super::*;
+
+    async fn setup() -> (TestConfig, OddsManager) {
         let config = TestConfig::new().await;
         let odds_manager = OddsManager::new(config.tps.get_pool().await);
-        // add match info
-        let matches = MatchesBuilder::default()
-            .league_id(1)
-            .league_name("英超")
-            .home_team_id(1)
-            .home_team("曼联")
-            .away_team_id(2)
-            .away_team("利物浦")
-            .game_time(NaiveDateTime::default())
-            .game_result("2:1")
-            .build()
-            .unwrap();
-        // let odd_1 = OddsBuilder::default()
-        //     .bookmaker_id(1)
-        //     .home_win_start_setter("1.2")
-        //     .draw_start_setter("2.3")
-        //     .away_win_start_setter("3.4")
-        //     .home_win_end_setter("1.5")
-        //     .draw_end_setter("2.6")
-        //     .away_win_end_setter("3.7")
-        //     .build()
-        //     .unwrap();
-        // let odd_2 = OddsBuilder::default()
-        //     .bookmaker_id(2)
-        //     .home_win_start_setter("1.4")
-        //     .draw_start_setter("2.25")
-        //     .away_win_start_setter("3.45")
-        //     .home_win_end_setter("1.62")
-        //     .draw_end_setter("2.65")
-        //     .away_win_end_setter("3.72")
-        //     .build()
-        //     .unwrap();
-        // let odds = vec![odd_1, odd_2];
-        let match_info = odds_manager.create_match_info(matches).await.unwrap();
-
-        // delete match info
-        let delete_count = odds_manager.delete_match_info(match_info.id).await.unwrap();
-
-        assert_eq!(1, delete_count);
+        (config, odds_manager)
     }
+
+    crate::test_suite::europe_odds_test_suite!(setup);
 }
@@ -0,0 +1,1526 @@
+use async_trait::async_trait;
+use bigdecimal::BigDecimal;
+use chrono::NaiveDateTime;
+use data::{
+    Archive, AsianHandicapOdds, BookMaker, ConflictPolicy, DbConfig, ImportReport, ImportRowError,
+    League, MatchFilter, MatchImportRow, MatchInfo, Matches, Odds, OddsBand, OddsBandQuery,
+    OddsError, OddsSnapshot, OverUnderOdds, Page, PredictionScorecard, RestoreReport,
+    ScorecardQuery, SimilarOdds, SimilarOddsQuery, Team, Validate,
+};
+use sqlx::{
+    sqlite::{SqlitePoolOptions, SqliteRow},
+    QueryBuilder, Row, Sqlite, SqliteConnection, SqliteExecutor, SqlitePool,
+};
+
+use crate::{
+    archive::{Restore, RowAction},
+    diff_odds, effective_tolerances,
+    filter::{match_count_query, match_infos, match_page, match_query, page_items},
+    import::{row_error, ImportNames},
+    now, odds_bands, prediction_scorecard, similar_odds, within_tolerance, BookMakerId, EuropeOdds,
+    LeagueId, MatchId, OddId, SqliteOddsManager, TeamId,
+};
+
+impl SqliteOddsManager {
+    pub fn new(conn: SqlitePool) -> Self {
+        Self { conn }
+    }
+
+    /// connect to the database file and bring its schema up to date
+    pub async fn from_config(config: &DbConfig) -> Result<Self, OddsError> {
+        let conn = SqlitePoolOptions::new()
+            .max_connections(config.max_connections)
+            .connect(&config.url())
+            .await?;
+        let manager = Self::new(conn);
+        manager.migrate().await?;
+        Ok(manager)
+    }
+
+    /// run the embedded sqlite migrations
+    pub async fn migrate(&self) -> Result<(), OddsError> {
+        sqlx::migrate!("../migrations-sqlite")
+            .run(&self.conn)
+            .await
+            .map_err(|err| OddsError::DbError(err.to_string()))
+    }
+}
+
+#[async_trait]
+impl EuropeOdds for SqliteOddsManager {
+    /// get all bookmaker data
+    async fn list_bookermaker(&self) -> Result<Vec<BookMaker>, OddsError> {
+        let book_makers =
+            sqlx::query_as("SELECT * FROM bookmakers ORDER BY created_at ASC, id ASC")
+                .fetch_all(&self.conn)
+                .await?;
+
+        Ok(book_makers)
+    }
+
+    /// add bookmaker data to persistence
+    async fn create_bookermaker(&self, bookmaker: BookMaker) -> Result<Vec<BookMaker>, OddsError> {
+        insert_bookmaker(&self.conn, &bookmaker).await?;
+
+        self.list_bookermaker().await
+    }
+
+    /// update bookmaker data to persistence
+    async fn update_bookermaker(&self, bookmaker: BookMaker) -> Result<Vec<BookMaker>, OddsError> {
+        update_bookmaker(&self.conn, &bookmaker).await?;
+
+        self.list_bookermaker().await
+    }
+
+    /// delete bookmaker data from persistence
+    async fn delete_bookermaker(&self, id: BookMakerId) -> Result<Vec<BookMaker>, OddsError> {
+        sqlx::query("DELETE FROM bookmakers WHERE id = $1")
+            .bind(id)
+            .execute(&self.conn)
+            .await?;
+
+        self.list_bookermaker().await
+    }
+
+    /// query bookmaker data by id
+    async fn query_bookermaker_with_id(&self, id: BookMakerId) -> Result<BookMaker, OddsError> {
+        let book_maker = sqlx::query_as("SELECT * FROM bookmakers WHERE id = $1")
+            .bind(id)
+            .fetch_one(&self.conn)
+            .await?;
+
+        Ok(book_maker)
+    }
+
+    /// get all league data
+    async fn list_leagues(&self) -> Result<Vec<League>, OddsError> {
+        let leagues = sqlx::query_as("SELECT * FROM leagues ORDER BY created_at ASC, id ASC")
+            .fetch_all(&self.conn)
+            .await?;
+
+        Ok(leagues)
+    }
+
+    /// query league data by id
+    async fn query_league_with_id(&self, id: LeagueId) -> Result<League, OddsError> {
+        let league = sqlx::query_as("SELECT * FROM leagues WHERE id = $1")
+            .bind(id)
+            .fetch_one(&self.conn)
+            .await?;
+
+        Ok(league)
+    }
+
+    /// add league data to persistence
+    async fn create_league(&self, league: League) -> Result<Vec<League>, OddsError> {
+        insert_league(&self.conn, &league).await?;
+
+        self.list_leagues().await
+    }
+
+    /// update league data to persistence
+    async fn update_league(&self, league: League) -> Result<Vec<League>, OddsError> {
+        update_league(&self.conn, &league).await?;
+
+        self.list_leagues().await
+    }
+
+    /// delete league data from persistence
+    async fn delete_league(&self, id: LeagueId) -> Result<Vec<League>, OddsError> {
+        sqlx::query("DELETE FROM leagues WHERE id = $1")
+            .bind(id)
+            .execute(&self.conn)
+            .await?;
+
+        self.list_leagues().await
+    }
+
+    /// get all team data
+    async fn list_teams(&self) -> Result<Vec<Team>, OddsError> {
+        let teams = sqlx::query_as(
+            "SELECT teams.*, leagues.name league_name FROM teams, leagues
+            WHERE teams.league_id = leagues.id ORDER BY teams.created_at ASC, teams.id ASC",
+        )
+        .fetch_all(&self.conn)
+        .await?;
+
+        Ok(teams)
+    }
+
+    /// query team data by id
+    async fn query_team_with_id(&self, id: TeamId) -> Result<Team, OddsError> {
+        let team = sqlx::query_as(
+            "SELECT teams.*, leagues.name league_name FROM teams, leagues
+            WHERE teams.league_id = leagues.id AND teams.id = $1",
+        )
+        .bind(id)
+        .fetch_one(&self.conn)
+        .await?;
+
+        Ok(team)
+    }
+
+    /// query team data by league id
+    async fn query_teams_with_condition(&self, id: LeagueId) -> Result<Vec<Team>, OddsError> {
+        let teams = sqlx::query_as(
+            "SELECT teams.*, leagues.name league_name FROM teams, leagues
+            WHERE teams.league_id = leagues.id AND leagues.id = $1
+            ORDER BY teams.created_at DESC, teams.id DESC",
+        )
+        .bind(id)
+        .fetch_all(&self.conn)
+        .await?;
+
+        Ok(teams)
+    }
+
+    /// add team data to persistence
+    async fn create_team(&self, team: Team) -> Result<Vec<Team>, OddsError> {
+        insert_team(&self.conn, &team).await?;
+
+        self.list_teams().await
+    }
+
+    /// update team data to persistence
+    async fn update_team(&self, team: Team) -> Result<Vec<Team>, OddsError> {
+        update_team(&self.conn, &team).await?;
+
+        self.list_teams().await
+    }
+
+    /// delete team data from persistence
+    async fn delete_team(&self, id: TeamId) -> Result<Vec<Team>, OddsError> {
+        sqlx::query("DELETE FROM teams WHERE id = $1")
+            .bind(id)
+            .execute(&self.conn)
+            .await?;
+
+        self.list_teams().await
+    }
+
+    /// query a page of match data meeting the filter
+    async fn query_match_info(&self, filter: MatchFilter) -> Result<Page<Matches>, OddsError> {
+        let cursor = filter.keyset()?;
+        let total: i64 = match_count_query::<Sqlite>("", &filter)
+            .build()
+            .fetch_one(&self.conn)
+            .await?
+            .try_get(0)?;
+        let match_infos = match_query::<Sqlite>("", &filter, cursor.as_ref())
+            .build_query_as()
+            .fetch_all(&self.conn)
+            .await?;
+
+        Ok(match_page(&filter, cursor.as_ref(), match_infos, total))
+    }
+
+    /// query the page of match data meeting the filter with the odds of every match
+    async fn query_match_info_with_odds(
+        &self,
+        filter: MatchFilter,
+    ) -> Result<Vec<MatchInfo>, OddsError> {
+        let cursor = filter.keyset()?;
+        let matches = match_query::<Sqlite>("", &filter, cursor.as_ref())
+            .build_query_as()
+            .fetch_all(&self.conn)
+            .await?;
+        let matches = page_items(&filter, cursor.as_ref(), matches);
+        let odds = if matches.is_empty() {
+            vec![]
+        } else {
+            let mut builder = QueryBuilder::<Sqlite>::new("SELECT * FROM odds WHERE match_id IN (");
+            let mut ids = builder.separated(", ");
+            for m in matches.iter() {
+                ids.push_bind(m.id);
+            }
+            builder.push(") ORDER BY id ASC");
+            builder
+                .build()
+                .fetch_all(&self.conn)
+                .await?
+                .iter()
+                .map(odds_from_row)
+                .collect::<Result<_, _>>()?
+        };
+
+        Ok(match_infos(matches, odds))
+    }
+
+    /// query match data by match id
+    async fn query_match_info_by_id(&self, id: MatchId) -> Result<Matches, OddsError> {
+        let matches = sqlx::query_as("SELECT * FROM matches WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&self.conn)
+            .await?
+            .ok_or(OddsError::NotFound)?;
+
+        Ok(matches)
+    }
+
+    /// query odds data by match id
+    async fn query_odds_info_by_id(&self, id: i32) -> Result<Vec<Odds>, OddsError> {
+        sqlx::query("SELECT * FROM odds WHERE match_id = $1 ORDER BY id ASC")
+            .bind(id)
+            .fetch_all(&self.conn)
+            .await?
+            .iter()
+            .map(odds_from_row)
+            .collect()
+    }
+
+    /// add match data to persistence
+    async fn create_match_info(&self, mut matches: Matches) -> Result<Matches, OddsError> {
+        matches.id = insert_match(&mut *self.conn.acquire().await?, &matches).await?;
+        Ok(matches)
+    }
+
+    /// update match data to persistence
+    async fn update_match_info(&self, matches: Matches) -> Result<Matches, OddsError> {
+        update_match(&mut *self.conn.acquire().await?, &matches).await
+    }
+
+    /// save a match with its odds in one transaction
+    async fn save_match_info(&self, match_info: MatchInfo) -> Result<MatchInfo, OddsError> {
+        let MatchInfo { mut matches, odds } = match_info;
+        let mut tx = self.conn.begin().await?;
+        let stored: Vec<Odds> = if matches.id == 0 {
+            matches.id = insert_match(&mut tx, &matches).await?;
+            vec![]
+        } else {
+            matches = update_match(&mut tx, &matches).await?;
+            sqlx::query("SELECT * FROM odds WHERE match_id = $1")
+                .bind(matches.id)
+                .fetch_all(&mut tx)
+                .await?
+                .iter()
+                .map(odds_from_row)
+                .collect::<Result<_, _>>()?
+        };
+
+        let changes = diff_odds(&stored, odds).known()?;
+        for id in changes.delete {
+            sqlx::query("DELETE FROM odds WHERE id = $1")
+                .bind(id)
+                .execute(&mut tx)
+                .await?;
+        }
+        let mut saved_odds = vec![];
+        for mut odd in changes.update {
+            odd.match_id = matches.id;
+            saved_odds.push(update_odds(&mut tx, &odd).await?);
+        }
+        for mut odd in changes.create {
+            odd.match_id = matches.id;
+            odd.id = insert_odds(&mut tx, matches.id, &odd).await?;
+            saved_odds.push(odd);
+        }
+        tx.commit().await?;
+
+        Ok(MatchInfo::new(matches, saved_odds))
+    }
+
+    /// import matches with their odds in one transaction
+    async fn import_matches(
+        &self,
+        rows: Vec<MatchImportRow>,
+        create_missing: bool,
+    ) -> Result<ImportReport, OddsError> {
+        let mut tx = self.conn.begin().await?;
+        let mut names = ImportNames::new(
+            sqlx::query_as("SELECT * FROM leagues")
+                .fetch_all(&mut tx)
+                .await?,
+            sqlx::query_as(
+                "SELECT teams.*, leagues.name league_name FROM teams, leagues
+                WHERE teams.league_id = leagues.id",
+            )
+            .fetch_all(&mut tx)
+            .await?,
+            sqlx::query_as("SELECT * FROM bookmakers")
+                .fetch_all(&mut tx)
+                .await?,
+        );
+        let plan = match names.plan(rows, create_missing) {
+            Ok(plan) => plan,
+            Err(errors) => return Ok(ImportReport::failed(errors)),
+        };
+
+        let imported: Result<(), ImportRowError> = async {
+            for name in &plan.leagues {
+                let mut league = names.league(name);
+                league.id = insert_league(&mut tx, &league)
+                    .await
+                    .map_err(row_error(name.line))?;
+                names.add_league(league);
+            }
+            for name in &plan.teams {
+                let mut team = names.team(name);
+                team.id = insert_team(&mut tx, &team)
+                    .await
+                    .map_err(row_error(name.line))?;
+                names.add_team(team);
+            }
+            for name in &plan.bookmakers {
+                let mut bookmaker = names.bookmaker(name);
+                bookmaker.id = insert_bookmaker(&mut tx, &bookmaker)
+                    .await
+                    .map_err(row_error(name.line))?;
+                names.add_bookmaker(bookmaker);
+            }
+            for planned in &plan.matches {
+                let MatchInfo { matches, odds } = names.match_info(planned);
+                let id = insert_match(&mut tx, &matches)
+                    .await
+                    .map_err(row_error(planned.row.line))?;
+                for (odd, (line, _)) in odds.iter().zip(&planned.odds) {
+                    insert_odds(&mut tx, id, odd)
+                        .await
+                        .map_err(row_error(*line))?;
+                }
+            }
+            Ok(())
+        }
+        .await;
+
+        match imported {
+            Ok(()) => {
+                tx.commit().await?;
+                Ok(plan.report())
+            }
+            Err(error) => Ok(ImportReport::failed(vec![error])),
+        }
+    }
+
+    /// restore an archive in one transaction
+    async fn restore_archive(
+        &self,
+        archive: Archive,
+        policy: ConflictPolicy,
+    ) -> Result<RestoreReport, OddsError> {
+        let mut tx = self.conn.begin().await?;
+        let stored = Archive {
+            bookmakers: sqlx::query_as("SELECT * FROM bookmakers")
+                .fetch_all(&mut tx)
+                .await?,
+            leagues: sqlx::query_as("SELECT * FROM leagues")
+                .fetch_all(&mut tx)
+                .await?,
+            teams: sqlx::query_as(
+                "SELECT teams.*, leagues.name league_name FROM teams, leagues
+                WHERE teams.league_id = leagues.id",
+            )
+            .fetch_all(&mut tx)
+            .await?,
+            matches: sqlx::query_as("SELECT * FROM matches")
+                .fetch_all(&mut tx)
+                .await?,
+            odds: sqlx::query("SELECT * FROM odds")
+                .fetch_all(&mut tx)
+                .await?
+                .iter()
+                .map(odds_from_row)
+                .collect::<Result<Vec<_>, _>>()?,
+            snapshots: sqlx::query("SELECT * FROM odds_snapshots")
+                .fetch_all(&mut tx)
+                .await?
+                .iter()
+                .map(snapshot_from_row)
+                .collect::<Result<Vec<_>, _>>()?,
+            asian_handicaps: sqlx::query("SELECT * FROM asian_handicap_odds")
+                .fetch_all(&mut tx)
+                .await?
+                .iter()
+                .map(asian_handicap_from_row)
+                .collect::<Result<Vec<_>, _>>()?,
+            over_unders: sqlx::query("SELECT * FROM over_under_odds")
+                .fetch_all(&mut tx)
+                .await?
+                .iter()
+                .map(over_under_from_row)
+                .collect::<Result<Vec<_>, _>>()?,
+            ..Archive::new(now())
+        };
+        let mut restore = Restore::new(policy, &stored);
+
+        for bookmaker in &archive.bookmakers {
+            let id = match restore.bookmaker(bookmaker)? {
+                RowAction::Create(row) => insert_bookmaker(&mut tx, &row).await?,
+                RowAction::Overwrite(row) => {
+                    update_bookmaker(&mut tx, &row).await?;
+                    row.id
+                }
+                RowAction::Skip(id) => id,
+            };
+            restore.bookmaker_restored(bookmaker, id);
+        }
+        for league in &archive.leagues {
+            let id = match restore.league(league)? {
+                RowAction::Create(row) => insert_league(&mut tx, &row).await?,
+                RowAction::Overwrite(row) => {
+                    update_league(&mut tx, &row).await?;
+                    row.id
+                }
+                RowAction::Skip(id) => id,
+            };
+            restore.league_restored(league, id);
+        }
+        for team in &archive.teams {
+            let id = match restore.team(team)? {
+                RowAction::Create(row) => insert_team(&mut tx, &row).await?,
+                RowAction::Overwrite(row) => {
+                    update_team(&mut tx, &row).await?;
+                    row.id
+                }
+                RowAction::Skip(id) => id,
+            };
+            restore.team_restored(team, id);
+        }
+        for matches in &archive.matches {
+            let id = match restore.matches(matches)? {
+                RowAction::Create(row) => insert_match(&mut tx, &row).await?,
+                RowAction::Overwrite(row) => update_match(&mut tx, &row).await?.id,
+                RowAction::Skip(id) => id,
+            };
+            restore.matches_restored(matches, id);
+        }
+        for odd in &archive.odds {
+            match restore.odds(odd)? {
+                RowAction::Create(row) => {
+                    insert_odds(&mut tx, row.match_id, &row).await?;
+                }
+                RowAction::Overwrite(row) => {
+                    update_odds(&mut tx, &row).await?;
+                }
+                RowAction::Skip(_) => {}
+            }
+        }
+        for snapshot in &archive.snapshots {
+            match restore.snapshot(snapshot)? {
+                RowAction::Create(row) => {
+                    insert_snapshot(&mut tx, &row).await?;
+                }
+                RowAction::Overwrite(row) => {
+                    update_snapshot(&mut tx, &row).await?;
+                }
+                RowAction::Skip(_) => {}
+            }
+        }
+        for odds in &archive.asian_handicaps {
+            match restore.asian_handicap(odds)? {
+                RowAction::Create(row) => {
+                    insert_asian_handicap(&mut tx, row.match_id, &row).await?;
+                }
+                RowAction::Overwrite(row) => {
+                    update_asian_handicap(&mut tx, &row).await?;
+                }
+                RowAction::Skip(_) => {}
+            }
+        }
+        for odds in &archive.over_unders {
+            match restore.over_under(odds)? {
+                RowAction::Create(row) => {
+                    insert_over_under(&mut tx, row.match_id, &row).await?;
+                }
+                RowAction::Overwrite(row) => {
+                    update_over_under(&mut tx, &row).await?;
+                }
+                RowAction::Skip(_) => {}
+            }
+        }
+
+        tx.commit().await?;
+        Ok(restore.report)
+    }
+
+    /// delete match data from persistence, the odds of the match are deleted with it
+    async fn delete_match_info(&self, id: MatchId) -> Result<i32, OddsError> {
+        let count = sqlx::query("DELETE FROM matches WHERE id = $1")
+            .bind(id)
+            .execute(&self.conn)
+            .await?;
+
+        Ok(count.rows_affected() as i32)
+    }
+
+    /// add odds data of a match to persistence
+    async fn create_odd_info(&self, id: MatchId, mut odd: Odds) -> Result<Odds, OddsError> {
+        odd.id = insert_odds(&self.conn, id, &odd).await?;
+        Ok(odd)
+    }
+
+    /// update odds data to persistence
+    async fn update_odd_info(&self, odd: Odds) -> Result<Odds, OddsError> {
+        update_odds(&self.conn, &odd).await
+    }
+
+    /// delete odds data from persistence
+    async fn delete_odds_info(&self, id: OddId) -> Result<i32, OddsError> {
+        let count = sqlx::query("DELETE FROM odds WHERE id = $1")
+            .bind(id)
+            .execute(&self.conn)
+            .await?;
+
+        Ok(count.rows_affected() as i32)
+    }
+
+    /// query earlier matches whose odds from the same bookmaker are within the tolerances
+    async fn query_similar_odds(&self, query: SimilarOddsQuery) -> Result<SimilarOdds, OddsError> {
+        let odds = sqlx::query("SELECT * FROM odds WHERE match_id = $1 AND bookmaker_id = $2")
+            .bind(query.match_id)
+            .bind(query.bookmaker_id)
+            .fetch_optional(&self.conn)
+            .await?
+            .ok_or(OddsError::NotFound)
+            .and_then(|row| odds_from_row(&row))?;
+        let game_time: NaiveDateTime = sqlx::query("SELECT game_time FROM matches WHERE id = $1")
+            .bind(query.match_id)
+            .fetch_one(&self.conn)
+            .await?
+            .try_get(0)?;
+
+        let (start_tolerance, end_tolerance) =
+            effective_tolerances(&odds, query.start_tolerance, query.end_tolerance);
+        // the prices are stored as text, so the tolerances are compared after loading
+        let similar: Vec<Odds> = sqlx::query(
+            "SELECT odds.* FROM odds, matches
+            WHERE odds.match_id = matches.id AND odds.bookmaker_id = $1
+            AND matches.id <> $2 AND matches.game_time < $3
+            AND ($4 = 0 OR matches.league_id = $4)
+            AND ($5 IS NULL OR matches.game_year = $5)
+            ORDER BY matches.game_time DESC",
+        )
+        .bind(query.bookmaker_id)
+        .bind(query.match_id)
+        .bind(game_time)
+        .bind(query.league_id)
+        .bind(&query.game_year)
+        .fetch_all(&self.conn)
+        .await?
+        .iter()
+        .map(odds_from_row)
+        .collect::<Result<Vec<Odds>, _>>()?
+        .into_iter()
+        .filter(|candidate| {
+            within_tolerance(
+                &odds,
+                candidate,
+                start_tolerance.as_ref(),
+                end_tolerance.as_ref(),
+            )
+        })
+        .collect();
+
+        let matches = if similar.is_empty() {
+            vec![]
+        } else {
+            let mut builder = QueryBuilder::<Sqlite>::new("SELECT * FROM matches WHERE id IN (");
+            let mut ids = builder.separated(", ");
+            for odd in similar.iter() {
+                ids.push_bind(odd.match_id);
+            }
+            builder.push(")");
+            builder.build_query_as().fetch_all(&self.conn).await?
+        };
+
+        Ok(similar_odds(odds, similar, matches))
+    }
+
+    /// count the results of historical matches per band of closing home win prices
+    async fn query_odds_bands(&self, query: OddsBandQuery) -> Result<Vec<OddsBand>, OddsError> {
+        let matches: Vec<Matches> = sqlx::query_as(
+            "SELECT * FROM matches WHERE ($1 = 0 OR league_id = $1)
+            AND ((home_goals IS NOT NULL AND away_goals IS NOT NULL) OR game_result IS NOT NULL)",
+        )
+        .bind(query.league_id)
+        .fetch_all(&self.conn)
+        .await?;
+        let odds: Vec<Odds> = sqlx::query(
+            "SELECT odds.* FROM odds, matches
+            WHERE odds.match_id = matches.id
+            AND ($1 = 0 OR matches.league_id = $1) AND ($2 = 0 OR odds.bookmaker_id = $2)
+            AND ((matches.home_goals IS NOT NULL AND matches.away_goals IS NOT NULL)
+            OR matches.game_result IS NOT NULL)",
+        )
+        .bind(query.league_id)
+        .bind(query.bookmaker_id)
+        .fetch_all(&self.conn)
+        .await?
+        .iter()
+        .map(odds_from_row)
+        .collect::<Result<_, _>>()?;
+
+        Ok(odds_bands(&matches, &odds, &query))
+    }
+
+    /// compare the predicted outcomes of the matches with their results
+    async fn query_prediction_scorecard(
+        &self,
+        query: ScorecardQuery,
+    ) -> Result<PredictionScorecard, OddsError> {
+        let mut builder = QueryBuilder::<Sqlite>::new(
+            "SELECT * FROM matches WHERE predict_game_result IS NOT NULL",
+        );
+        if let Some(league_id) = query.league_id {
+            builder.push(" AND league_id = ").push_bind(league_id);
+        }
+        let matches: Vec<Matches> = builder.build_query_as().fetch_all(&self.conn).await?;
+
+        Ok(prediction_scorecard(&matches, &query))
+    }
+
+    /// append a price snapshot and derive the start and end odds in the same transaction
+    async fn append_odds_snapshot(
+        &self,
+        snapshot: OddsSnapshot,
+    ) -> Result<OddsSnapshot, OddsError> {
+        snapshot.validate()?;
+        let mut tx = self.conn.begin().await?;
+        let bookmaker_name: String = sqlx::query("SELECT name FROM bookmakers WHERE id = $1")
+            .bind(snapshot.bookmaker_id)
+            .fetch_optional(&mut tx)
+            .await?
+            .ok_or(OddsError::NotFound)?
+            .try_get(0)?;
+        let snapshot = insert_snapshot(&mut tx, &snapshot).await?;
+
+        let timeline = sqlx::query(
+            "SELECT * FROM odds_snapshots WHERE match_id = $1 AND bookmaker_id = $2
+            ORDER BY recorded_at ASC, id ASC",
+        )
+        .bind(snapshot.match_id)
+        .bind(snapshot.bookmaker_id)
+        .fetch_all(&mut tx)
+        .await?
+        .iter()
+        .map(snapshot_from_row)
+        .collect::<Result<Vec<_>, _>>()?;
+        let (Some(first), Some(last)) = (timeline.first(), timeline.last()) else {
+            return Err(OddsError::NotFound);
+        };
+        let updated = sqlx::query(
+            "UPDATE odds SET home_win_start = $1, draw_start = $2, away_win_start = $3,
+            home_win_end = $4, draw_end = $5, away_win_end = $6, bookmaker_name = $7
+            WHERE match_id = $8 AND bookmaker_id = $9",
+        )
+        .bind(first.home_win.to_string())
+        .bind(first.draw.to_string())
+        .bind(first.away_win.to_string())
+        .bind(last.home_win.to_string())
+        .bind(last.draw.to_string())
+        .bind(last.away_win.to_string())
+        .bind(&bookmaker_name)
+        .bind(snapshot.match_id)
+        .bind(snapshot.bookmaker_id)
+        .execute(&mut tx)
+        .await?;
+        if updated.rows_affected() == 0 {
+            sqlx::query(
+                "INSERT INTO odds (match_id, bookmaker_id, bookmaker_name, home_win_start,
+                draw_start, away_win_start, home_win_end, draw_end, away_win_end, note)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, '')",
+            )
+            .bind(snapshot.match_id)
+            .bind(snapshot.bookmaker_id)
+            .bind(&bookmaker_name)
+            .bind(first.home_win.to_string())
+            .bind(first.draw.to_string())
+            .bind(first.away_win.to_string())
+            .bind(last.home_win.to_string())
+            .bind(last.draw.to_string())
+            .bind(last.away_win.to_string())
+            .execute(&mut tx)
+            .await?;
+        }
+        tx.commit().await?;
+
+        Ok(snapshot)
+    }
+
+    /// query the price snapshots of a bookmaker for a match in recorded order
+    async fn query_odds_timeline(
+        &self,
+        match_id: MatchId,
+        bookmaker_id: BookMakerId,
+    ) -> Result<Vec<OddsSnapshot>, OddsError> {
+        sqlx::query(
+            "SELECT * FROM odds_snapshots WHERE match_id = $1 AND bookmaker_id = $2
+            ORDER BY recorded_at ASC, id ASC",
+        )
+        .bind(match_id)
+        .bind(bookmaker_id)
+        .fetch_all(&self.conn)
+        .await?
+        .iter()
+        .map(snapshot_from_row)
+        .collect()
+    }
+
+    /// query the price snapshots of every bookmaker for a match in recorded order
+    async fn query_odds_snapshots_by_id(
+        &self,
+        id: MatchId,
+    ) -> Result<Vec<OddsSnapshot>, OddsError> {
+        sqlx::query(
+            "SELECT * FROM odds_snapshots WHERE match_id = $1
+            ORDER BY bookmaker_id ASC, recorded_at ASC, id ASC",
+        )
+        .bind(id)
+        .fetch_all(&self.conn)
+        .await?
+        .iter()
+        .map(snapshot_from_row)
+        .collect()
+    }
+
+    /// query asian handicap odds by match id
+    async fn query_asian_handicap_by_id(
+        &self,
+        id: MatchId,
+    ) -> Result<Vec<AsianHandicapOdds>, OddsError> {
+        sqlx::query("SELECT * FROM asian_handicap_odds WHERE match_id = $1 ORDER BY id ASC")
+            .bind(id)
+            .fetch_all(&self.conn)
+            .await?
+            .iter()
+            .map(asian_handicap_from_row)
+            .collect()
+    }
+
+    /// add asian handicap odds of a match to persistence
+    async fn create_asian_handicap(
+        &self,
+        id: MatchId,
+        mut odds: AsianHandicapOdds,
+    ) -> Result<AsianHandicapOdds, OddsError> {
+        odds.id = insert_asian_handicap(&self.conn, id, &odds).await?;
+        Ok(odds)
+    }
+
+    /// update asian handicap odds to persistence
+    async fn update_asian_handicap(
+        &self,
+        odds: AsianHandicapOdds,
+    ) -> Result<AsianHandicapOdds, OddsError> {
+        update_asian_handicap(&self.conn, &odds).await
+    }
+
+    /// save the asian handicap odds of a match in one transaction
+    async fn save_asian_handicaps(
+        &self,
+        id: MatchId,
+        odds: Vec<AsianHandicapOdds>,
+    ) -> Result<Vec<AsianHandicapOdds>, OddsError> {
+        let mut tx = self.conn.begin().await?;
+        let stored: Vec<AsianHandicapOdds> =
+            sqlx::query("SELECT * FROM asian_handicap_odds WHERE match_id = $1")
+                .bind(id)
+                .fetch_all(&mut tx)
+                .await?
+                .iter()
+                .map(asian_handicap_from_row)
+                .collect::<Result<_, _>>()?;
+
+        let changes = diff_odds(&stored, odds).known()?;
+        for id in changes.delete {
+            sqlx::query("DELETE FROM asian_handicap_odds WHERE id = $1")
+                .bind(id)
+                .execute(&mut tx)
+                .await?;
+        }
+        let mut saved_odds = vec![];
+        for mut odds in changes.update {
+            odds.match_id = id;
+            saved_odds.push(update_asian_handicap(&mut tx, &odds).await?);
+        }
+        for mut odds in changes.create {
+            odds.match_id = id;
+            odds.id = insert_asian_handicap(&mut tx, id, &odds).await?;
+            saved_odds.push(odds);
+        }
+        tx.commit().await?;
+
+        Ok(saved_odds)
+    }
+
+    /// delete asian handicap odds from persistence
+    async fn delete_asian_handicap(&self, id: OddId) -> Result<i32, OddsError> {
+        let count = sqlx::query("DELETE FROM asian_handicap_odds WHERE id = $1")
+            .bind(id)
+            .execute(&self.conn)
+            .await?;
+
+        Ok(count.rows_affected() as i32)
+    }
+
+    /// query over/under odds by match id
+    async fn query_over_under_by_id(&self, id: MatchId) -> Result<Vec<OverUnderOdds>, OddsError> {
+        sqlx::query("SELECT * FROM over_under_odds WHERE match_id = $1 ORDER BY id ASC")
+            .bind(id)
+            .fetch_all(&self.conn)
+            .await?
+            .iter()
+            .map(over_under_from_row)
+            .collect()
+    }
+
+    /// add over/under odds of a match to persistence
+    async fn create_over_under(
+        &self,
+        id: MatchId,
+        mut odds: OverUnderOdds,
+    ) -> Result<OverUnderOdds, OddsError> {
+        odds.id = insert_over_under(&self.conn, id, &odds).await?;
+        Ok(odds)
+    }
+
+    /// update over/under odds to persistence
+    async fn update_over_under(&self, odds: OverUnderOdds) -> Result<OverUnderOdds, OddsError> {
+        update_over_under(&self.conn, &odds).await
+    }
+
+    /// save the over/under odds of a match in one transaction
+    async fn save_over_unders(
+        &self,
+        id: MatchId,
+        odds: Vec<OverUnderOdds>,
+    ) -> Result<Vec<OverUnderOdds>, OddsError> {
+        let mut tx = self.conn.begin().await?;
+        let stored: Vec<OverUnderOdds> =
+            sqlx::query("SELECT * FROM over_under_odds WHERE match_id = $1")
+                .bind(id)
+                .fetch_all(&mut tx)
+                .await?
+                .iter()
+                .map(over_under_from_row)
+                .collect::<Result<_, _>>()?;
+
+        let changes = diff_odds(&stored, odds).known()?;
+        for id in changes.delete {
+            sqlx::query("DELETE FROM over_under_odds WHERE id = $1")
+                .bind(id)
+                .execute(&mut tx)
+                .await?;
+        }
+        let mut saved_odds = vec![];
+        for mut odds in changes.update {
+            odds.match_id = id;
+            saved_odds.push(update_over_under(&mut tx, &odds).await?);
+        }
+        for mut odds in changes.create {
+            odds.match_id = id;
+            odds.id = insert_over_under(&mut tx, id, &odds).await?;
+            saved_odds.push(odds);
+        }
+        tx.commit().await?;
+
+        Ok(saved_odds)
+    }
+
+    /// delete over/under odds from persistence
+    async fn delete_over_under(&self, id: OddId) -> Result<i32, OddsError> {
+        let count = sqlx::query("DELETE FROM over_under_odds WHERE id = $1")
+            .bind(id)
+            .execute(&self.conn)
+            .await?;
+
+        Ok(count.rows_affected() as i32)
+    }
+}
+
+/// insert a bookmaker and return the generated id
+async fn insert_bookmaker<'e, E: SqliteExecutor<'e>>(
+    executor: E,
+    bookmaker: &BookMaker,
+) -> Result<BookMakerId, OddsError> {
+    bookmaker.validate()?;
+    let id =
+        sqlx::query("INSERT INTO bookmakers (name, url, note) VALUES ($1, $2, $3) RETURNING id")
+            .bind(&bookmaker.name)
+            .bind(&bookmaker.url)
+            .bind(&bookmaker.note)
+            .fetch_all(executor)
+            .await
+            .map_err(OddsError::from)
+            .and_then(returned)?
+            .try_get(0)?;
+
+    Ok(id)
+}
+
+/// update a bookmaker by id
+async fn update_bookmaker<'e, E: SqliteExecutor<'e>>(
+    executor: E,
+    bookmaker: &BookMaker,
+) -> Result<(), OddsError> {
+    bookmaker.validate()?;
+    sqlx::query("UPDATE bookmakers SET name = $1, url = $2, note = $3 WHERE id = $4 RETURNING id")
+        .bind(&bookmaker.name)
+        .bind(&bookmaker.url)
+        .bind(&bookmaker.note)
+        .bind(bookmaker.id)
+        .fetch_all(executor)
+        .await
+        .map_err(OddsError::from)
+        .and_then(returned)?;
+
+    Ok(())
+}
+
+/// insert a league and return the generated id
+async fn insert_league<'e, E: SqliteExecutor<'e>>(
+    executor: E,
+    league: &League,
+) -> Result<LeagueId, OddsError> {
+    league.validate()?;
+    let id = sqlx::query("INSERT INTO leagues (name, note) VALUES ($1, $2) RETURNING id")
+        .bind(&league.name)
+        .bind(&league.note)
+        .fetch_all(executor)
+        .await
+        .map_err(OddsError::from)
+        .and_then(returned)?
+        .try_get(0)?;
+
+    Ok(id)
+}
+
+/// update a league by id
+async fn update_league<'e, E: SqliteExecutor<'e>>(
+    executor: E,
+    league: &League,
+) -> Result<(), OddsError> {
+    league.validate()?;
+    sqlx::query("UPDATE leagues SET name = $1, note = $2 WHERE id = $3 RETURNING id")
+        .bind(&league.name)
+        .bind(&league.note)
+        .bind(league.id)
+        .fetch_all(executor)
+        .await
+        .map_err(OddsError::from)
+        .and_then(returned)?;
+
+    Ok(())
+}
+
+/// insert a team and return the generated id
+async fn insert_team<'e, E: SqliteExecutor<'e>>(
+    executor: E,
+    team: &Team,
+) -> Result<TeamId, OddsError> {
+    team.validate()?;
+    let id =
+        sqlx::query("INSERT INTO teams (name, league_id, note) VALUES ($1, $2, $3) RETURNING id")
+            .bind(&team.name)
+            .bind(team.league_id)
+            .bind(&team.note)
+            .fetch_all(executor)
+            .await
+            .map_err(OddsError::from)
+            .and_then(returned)?
+            .try_get(0)?;
+
+    Ok(id)
+}
+
+/// update a team by id
+async fn update_team<'e, E: SqliteExecutor<'e>>(executor: E, team: &Team) -> Result<(), OddsError> {
+    team.validate()?;
+    sqlx::query("UPDATE teams SET name = $1, league_id = $2, note = $3 WHERE id = $4 RETURNING id")
+        .bind(&team.name)
+        .bind(team.league_id)
+        .bind(&team.note)
+        .bind(team.id)
+        .fetch_all(executor)
+        .await
+        .map_err(OddsError::from)
+        .and_then(returned)?;
+
+    Ok(())
+}
+
+/// validate a match along with its home and away teams as stored
+async fn validate_match(conn: &mut SqliteConnection, matches: &Matches) -> Result<(), OddsError> {
+    let teams: Vec<Team> = sqlx::query_as(
+        "SELECT teams.*, leagues.name league_name FROM teams, leagues
+        WHERE teams.league_id = leagues.id AND teams.id IN ($1, $2)",
+    )
+    .bind(matches.home_team_id)
+    .bind(matches.away_team_id)
+    .fetch_all(&mut *conn)
+    .await?;
+
+    matches.validate_with(&teams)
+}
+
+/// insert a match and return the generated id
+async fn insert_match(
+    conn: &mut SqliteConnection,
+    matches: &Matches,
+) -> Result<MatchId, OddsError> {
+    validate_match(&mut *conn, matches).await?;
+    let (home_goals, away_goals) = matches.full_time_goals();
+    let id = sqlx::query(
+        "INSERT INTO matches (league_id, league_name, home_team_id, home_team, away_team_id,
+            away_team, game_time, game_year, game_round, game_result, history_note, note,
+            predict_game_result, home_goals, away_goals, half_home_goals, half_away_goals)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17)
+            RETURNING id",
+    )
+    .bind(matches.league_id)
+    .bind(&matches.league_name)
+    .bind(matches.home_team_id)
+    .bind(&matches.home_team)
+    .bind(matches.away_team_id)
+    .bind(&matches.away_team)
+    .bind(matches.game_time)
+    .bind(&matches.game_year)
+    .bind(&matches.game_round)
+    .bind(&matches.game_result)
+    .bind(&matches.history_note)
+    .bind(&matches.note)
+    .bind(&matches.predict_game_result)
+    .bind(home_goals)
+    .bind(away_goals)
+    .bind(matches.half_home_goals)
+    .bind(matches.half_away_goals)
+    .fetch_all(conn)
+    .await
+    .map_err(OddsError::from)
+    .and_then(returned)?
+    .try_get(0)?;
+
+    Ok(id)
+}
+
+/// update a match by id and return the stored row
+async fn update_match(
+    conn: &mut SqliteConnection,
+    matches: &Matches,
+) -> Result<Matches, OddsError> {
+    validate_match(&mut *conn, matches).await?;
+    let (home_goals, away_goals) = matches.full_time_goals();
+    let matches = sqlx::query_as::<_, Matches>(
+        "UPDATE matches SET league_id = $1,
+        home_team_id = $2, home_team = $3, away_team_id = $4, away_team = $5, game_time = $6,
+        game_result = $7, note = $8, game_year = $9, game_round = $10,
+        league_name = $11, history_note = $12, predict_game_result = $13, home_goals = $14,
+        away_goals = $15, half_home_goals = $16, half_away_goals = $17,
+        updated_at = strftime('%Y-%m-%d %H:%M:%f', 'now') WHERE id = $18 RETURNING *",
+    )
+    .bind(matches.league_id)
+    .bind(matches.home_team_id)
+    .bind(&matches.home_team)
+    .bind(matches.away_team_id)
+    .bind(&matches.away_team)
+    .bind(matches.game_time)
+    .bind(&matches.game_result)
+    .bind(&matches.note)
+    .bind(&matches.game_year)
+    .bind(&matches.game_round)
+    .bind(&matches.league_name)
+    .bind(&matches.history_note)
+    .bind(&matches.predict_game_result)
+    .bind(home_goals)
+    .bind(away_goals)
+    .bind(matches.half_home_goals)
+    .bind(matches.half_away_goals)
+    .bind(matches.id)
+    .fetch_all(conn)
+    .await
+    .map_err(OddsError::from)
+    .and_then(returned)?;
+
+    Ok(matches)
+}
+
+/// insert odds of a match and return the generated id
+async fn insert_odds<'e, E: SqliteExecutor<'e>>(
+    executor: E,
+    id: MatchId,
+    odd: &Odds,
+) -> Result<OddId, OddsError> {
+    odd.validate()?;
+    let id = sqlx::query(
+        "INSERT INTO odds (match_id, bookmaker_id, bookmaker_name,
+            home_win_start, draw_start, away_win_start, home_win_end, draw_end, away_win_end, note)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10) RETURNING id",
+    )
+    .bind(id)
+    .bind(odd.bookmaker_id)
+    .bind(&odd.bookmaker_name)
+    .bind(odd.home_win_start.to_string())
+    .bind(odd.draw_start.to_string())
+    .bind(odd.away_win_start.to_string())
+    .bind(odd.home_win_end.to_string())
+    .bind(odd.draw_end.to_string())
+    .bind(odd.away_win_end.to_string())
+    .bind(&odd.note)
+    .fetch_all(executor)
+    .await
+    .map_err(OddsError::from)
+    .and_then(returned)?
+    .try_get(0)?;
+
+    Ok(id)
+}
+
+/// update odds by match id and id and return the stored row
+async fn update_odds<'e, E: SqliteExecutor<'e>>(
+    executor: E,
+    odd: &Odds,
+) -> Result<Odds, OddsError> {
+    odd.validate()?;
+    let row = sqlx::query(
+        "UPDATE odds SET bookmaker_id = $1, home_win_start = $2, draw_start = $3,
+         away_win_start = $4, home_win_end = $5, draw_end = $6, away_win_end = $7, note = $8,
+         bookmaker_name = $9 WHERE match_id = $10 AND id = $11 RETURNING *",
+    )
+    .bind(odd.bookmaker_id)
+    .bind(odd.home_win_start.to_string())
+    .bind(odd.draw_start.to_string())
+    .bind(odd.away_win_start.to_string())
+    .bind(odd.home_win_end.to_string())
+    .bind(odd.draw_end.to_string())
+    .bind(odd.away_win_end.to_string())
+    .bind(&odd.note)
+    .bind(&odd.bookmaker_name)
+    .bind(odd.match_id)
+    .bind(odd.id)
+    .fetch_all(executor)
+    .await
+    .map_err(OddsError::from)
+    .and_then(returned)?;
+
+    odds_from_row(&row)
+}
+
+/// insert a price snapshot and return the stored row
+async fn insert_snapshot<'e, E: SqliteExecutor<'e>>(
+    executor: E,
+    snapshot: &OddsSnapshot,
+) -> Result<OddsSnapshot, OddsError> {
+    snapshot.validate()?;
+    // the snapshot is recorded now when no time is given
+    let recorded_at =
+        (snapshot.recorded_at != NaiveDateTime::default()).then_some(snapshot.recorded_at);
+    sqlx::query(
+        "INSERT INTO odds_snapshots (match_id, bookmaker_id, home_win, draw, away_win,
+        recorded_at) VALUES ($1, $2, $3, $4, $5,
+        COALESCE($6, strftime('%Y-%m-%d %H:%M:%f', 'now'))) RETURNING *",
+    )
+    .bind(snapshot.match_id)
+    .bind(snapshot.bookmaker_id)
+    .bind(snapshot.home_win.to_string())
+    .bind(snapshot.draw.to_string())
+    .bind(snapshot.away_win.to_string())
+    .bind(recorded_at)
+    .fetch_all(executor)
+    .await
+    .map_err(OddsError::from)
+    .and_then(returned)
+    .and_then(|row| snapshot_from_row(&row))
+}
+
+/// update the prices of a price snapshot by id and return the stored row
+async fn update_snapshot<'e, E: SqliteExecutor<'e>>(
+    executor: E,
+    snapshot: &OddsSnapshot,
+) -> Result<OddsSnapshot, OddsError> {
+    snapshot.validate()?;
+    sqlx::query(
+        "UPDATE odds_snapshots SET home_win = $1, draw = $2, away_win = $3
+        WHERE id = $4 RETURNING *",
+    )
+    .bind(snapshot.home_win.to_string())
+    .bind(snapshot.draw.to_string())
+    .bind(snapshot.away_win.to_string())
+    .bind(snapshot.id)
+    .fetch_all(executor)
+    .await
+    .map_err(OddsError::from)
+    .and_then(returned)
+    .and_then(|row| snapshot_from_row(&row))
+}
+
+/// insert asian handicap odds of a match and return the generated id
+async fn insert_asian_handicap<'e, E: SqliteExecutor<'e>>(
+    executor: E,
+    id: MatchId,
+    odds: &AsianHandicapOdds,
+) -> Result<OddId, OddsError> {
+    odds.validate()?;
+    let id = sqlx::query(
+        "INSERT INTO asian_handicap_odds (match_id, bookmaker_id, bookmaker_name,
+        line_start, home_price_start, away_price_start, line_end, home_price_end,
+        away_price_end, note) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10) RETURNING id",
+    )
+    .bind(id)
+    .bind(odds.bookmaker_id)
+    .bind(&odds.bookmaker_name)
+    .bind(odds.line_start.to_string())
+    .bind(odds.home_price_start.to_string())
+    .bind(odds.away_price_start.to_string())
+    .bind(odds.line_end.to_string())
+    .bind(odds.home_price_end.to_string())
+    .bind(odds.away_price_end.to_string())
+    .bind(&odds.note)
+    .fetch_all(executor)
+    .await
+    .map_err(OddsError::from)
+    .and_then(returned)?
+    .try_get(0)?;
+
+    Ok(id)
+}
+
+/// update asian handicap odds by match id and id and return the stored row
+async fn update_asian_handicap<'e, E: SqliteExecutor<'e>>(
+    executor: E,
+    odds: &AsianHandicapOdds,
+) -> Result<AsianHandicapOdds, OddsError> {
+    odds.validate()?;
+    let row = sqlx::query(
+        "UPDATE asian_handicap_odds SET bookmaker_id = $1, bookmaker_name = $2,
+        line_start = $3, home_price_start = $4, away_price_start = $5, line_end = $6,
+        home_price_end = $7, away_price_end = $8, note = $9
+        WHERE match_id = $10 AND id = $11 RETURNING *",
+    )
+    .bind(odds.bookmaker_id)
+    .bind(&odds.bookmaker_name)
+    .bind(odds.line_start.to_string())
+    .bind(odds.home_price_start.to_string())
+    .bind(odds.away_price_start.to_string())
+    .bind(odds.line_end.to_string())
+    .bind(odds.home_price_end.to_string())
+    .bind(odds.away_price_end.to_string())
+    .bind(&odds.note)
+    .bind(odds.match_id)
+    .bind(odds.id)
+    .fetch_all(executor)
+    .await
+    .map_err(OddsError::from)
+    .and_then(returned)?;
+
+    asian_handicap_from_row(&row)
+}
+
+/// insert over/under odds of a match and return the generated id
+async fn insert_over_under<'e, E: SqliteExecutor<'e>>(
+    executor: E,
+    id: MatchId,
+    odds: &OverUnderOdds,
+) -> Result<OddId, OddsError> {
+    odds.validate()?;
+    let id = sqlx::query(
+        "INSERT INTO over_under_odds (match_id, bookmaker_id, bookmaker_name,
+        line_start, over_price_start, under_price_start, line_end, over_price_end,
+        under_price_end, note) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10) RETURNING id",
+    )
+    .bind(id)
+    .bind(odds.bookmaker_id)
+    .bind(&odds.bookmaker_name)
+    .bind(odds.line_start.to_string())
+    .bind(odds.over_price_start.to_string())
+    .bind(odds.under_price_start.to_string())
+    .bind(odds.line_end.to_string())
+    .bind(odds.over_price_end.to_string())
+    .bind(odds.under_price_end.to_string())
+    .bind(&odds.note)
+    .fetch_all(executor)
+    .await
+    .map_err(OddsError::from)
+    .and_then(returned)?
+    .try_get(0)?;
+
+    Ok(id)
+}
+
+/// update over/under odds by match id and id and return the stored row
+async fn update_over_under<'e, E: SqliteExecutor<'e>>(
+    executor: E,
+    odds: &OverUnderOdds,
+) -> Result<OverUnderOdds, OddsError> {
+    odds.validate()?;
+    let row = sqlx::query(
+        "UPDATE over_under_odds SET bookmaker_id = $1, bookmaker_name = $2,
+        line_start = $3, over_price_start = $4, under_price_start = $5, line_end = $6,
+        over_price_end = $7, under_price_end = $8, note = $9
+        WHERE match_id = $10 AND id = $11 RETURNING *",
+    )
+    .bind(odds.bookmaker_id)
+    .bind(&odds.bookmaker_name)
+    .bind(odds.line_start.to_string())
+    .bind(odds.over_price_start.to_string())
+    .bind(odds.under_price_start.to_string())
+    .bind(odds.line_end.to_string())
+    .bind(odds.over_price_end.to_string())
+    .bind(odds.under_price_end.to_string())
+    .bind(&odds.note)
+    .bind(odds.match_id)
+    .bind(odds.id)
+    .fetch_all(executor)
+    .await
+    .map_err(OddsError::from)
+    .and_then(returned)?;
+
+    over_under_from_row(&row)
+}
+
+/// the row of a write returning one, every row is fetched so the statement runs to
+/// completion and the write is committed before the connection serves another query
+fn returned<T>(rows: Vec<T>) -> Result<T, OddsError> {
+    rows.into_iter().next().ok_or(OddsError::NotFound)
+}
+
+/// read a price stored as text
+fn decimal(row: &SqliteRow, column: &str) -> Result<BigDecimal, OddsError> {
+    let value: String = row.try_get(column)?;
+    value
+        .parse()
+        .map_err(|_| OddsError::DbError(format!("invalid decimal {value} in column {column}")))
+}
+
+fn odds_from_row(row: &SqliteRow) -> Result<Odds, OddsError> {
+    Ok(Odds {
+        id: row.try_get("id")?,
+        match_id: row.try_get("match_id")?,
+        bookmaker_id: row.try_get("bookmaker_id")?,
+        bookmaker_name: row.try_get("bookmaker_name")?,
+        home_win_start: decimal(row, "home_win_start")?,
+        draw_start: decimal(row, "draw_start")?,
+        away_win_start: decimal(row, "away_win_start")?,
+        home_win_end: decimal(row, "home_win_end")?,
+        draw_end: decimal(row, "draw_end")?,
+        away_win_end: decimal(row, "away_win_end")?,
+        note: row.try_get("note")?,
+    })
+}
+
+fn snapshot_from_row(row: &SqliteRow) -> Result<OddsSnapshot, OddsError> {
+    Ok(OddsSnapshot {
+        id: row.try_get("id")?,
+        match_id: row.try_get("match_id")?,
+        bookmaker_id: row.try_get("bookmaker_id")?,
+        home_win: decimal(row, "home_win")?,
+        draw: decimal(row, "draw")?,
+        away_win: decimal(row, "away_win")?,
+        recorded_at: row.try_get("recorded_at")?,
+        created_at: row.try_get("created_at")?,
+    })
+}
+
+fn asian_handicap_from_row(row: &SqliteRow) -> Result<AsianHandicapOdds, OddsError> {
+    Ok(AsianHandicapOdds {
+        id: row.try_get("id")?,
+        match_id: row.try_get("match_id")?,
+        bookmaker_id: row.try_get("bookmaker_id")?,
+        bookmaker_name: row.try_get("bookmaker_name")?,
+        line_start: decimal(row, "line_start")?,
+        home_price_start: decimal(row, "home_price_start")?,
+        away_price_start: decimal(row, "away_price_start")?,
+        line_end: decimal(row, "line_end")?,
+        home_price_end: decimal(row, "home_price_end")?,
+        away_price_end: decimal(row, "away_price_end")?,
+        note: row.try_get("note")?,
+    })
+}
+
+fn over_under_from_row(row: &SqliteRow) -> Result<OverUnderOdds, OddsError> {
+    Ok(OverUnderOdds {
+        id: row.try_get("id")?,
+        match_id: row.try_get("match_id")?,
+        bookmaker_id: row.try_get("bookmaker_id")?,
+        bookmaker_name: row.try_get("bookmaker_name")?,
+        line_start: decimal(row, "line_start")?,
+        over_price_start: decimal(row, "over_price_start")?,
+        under_price_start: decimal(row, "under_price_start")?,
+        line_end: decimal(row, "line_end")?,
+        over_price_end: decimal(row, "over_price_end")?,
+        under_price_end: decimal(row, "under_price_end")?,
+        note: row.try_get("note")?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn setup() -> ((), SqliteOddsManager) {
+        // a single connection keeps the in-memory database alive for the whole test
+        let conn = SqlitePoolOptions::new()
+            .max_connections(1)
+            .idle_timeout(None)
+            .max_lifetime(None)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        let odds_manager = SqliteOddsManager::new(conn);
+        odds_manager.migrate().await.unwrap();
+        ((), odds_manager)
+    }
+
+    crate::test_suite::europe_odds_test_suite!(setup);
+
+    #[tokio::test]
+    async fn writes_should_be_seen_by_other_connections() {
+        let path = std::env::temp_dir().join(format!("odds-{}.db", std::process::id()));
+        let config = DbConfig {
+            backend: data::DbBackend::Sqlite,
+            host: String::new(),
+            port: 0,
+            user: String::new(),
+            password: String::new(),
+            dbname: path.display().to_string(),
+            max_connections: 5,
+        };
+        let odds_manager = SqliteOddsManager::from_config(&config).await.unwrap();
+        for name in ["英超", "西甲", "德甲"] {
+            let league = LeagueBuilder::default().name(name).build().unwrap();
+            let leagues = odds_manager.create_league(league).await.unwrap();
+            assert_eq!(leagues.last().map(|l| l.name.as_str()), Some(name));
+        }
+        let mut league = odds_manager.query_league_with_id(2).await.unwrap();
+        league.note = Some("西班牙".to_string());
+        let leagues = odds_manager.update_league(league).await.unwrap();
+        assert_eq!(leagues[1].note.as_deref(), Some("西班牙"));
+
+        odds_manager.conn.close().await;
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn lines_should_be_checked_by_the_schema() {
+        let (_, odds_manager) = setup().await;
+        create_teams(&odds_manager, &[2]).await;
+        let matches = MatchesBuilder::default()
+            .league_id(1)
+            .league_name("英超")
+            .home_team_id(1)
+            .home_team("曼联")
+            .away_team_id(2)
+            .away_team("利物浦")
+            .game_time(NaiveDateTime::default())
+            .build()
+            .unwrap();
+        let match_id = odds_manager.create_match_info(matches).await.unwrap().id;
+        // the rows are written past the validation of the backend
+        for (table, line_start, line_end, valid) in [
+            ("asian_handicap_odds", "-0.25", "-1.75", true),
+            ("asian_handicap_odds", "-0.25", "-0.6", false),
+            ("over_under_odds", "2.5", "2.25", true),
+            ("over_under_odds", "2.4", "2.5", false),
+            ("over_under_odds", "-0.5", "2.5", false),
+        ] {
+            let result = sqlx::query(&format!(
+                "INSERT INTO {table} (match_id, bookmaker_id, bookmaker_name, line_start, line_end)
+                VALUES ($1, 1, '威廉希尔', $2, $3)"
+            ))
+            .bind(match_id)
+            .bind(line_start)
+            .bind(line_end)
+            .execute(&odds_manager.conn)
+            .await;
+            assert_eq!(result.is_ok(), valid, "{table} {line_start} {line_end}");
+        }
+    }
+}
@@ -0,0 +1,1666 @@
+/// the tests every `EuropeOdds` backend has to pass, `$setup` is an async fn returning
+/// a guard that keeps the test database alive and the backend under test
+macro_rules! europe_odds_test_suite {
+    ($setup:path) => {
+        use bigdecimal::BigDecimal;
+        use chrono::NaiveDateTime;
+        use data::{
+            Archive, AsianHandicapOddsBuilder, BookMakerBuilder, ConflictPolicy, FootballDataConfig, LeagueBuilder, MatchFilter,
+            MatchFilterBuilder, MatchInfo, MatchesBuilder, OddsBandQuery, OddsBuilder, OddsError,
+            OddsSnapshotBuilder, Outcome, OverUnderOddsBuilder, PredictionFilter, RestoreCount, ScorecardGroup, ScorecardQuery, Settlement,
+            SimilarOddsQuery, TeamBuilder,
+        };
+
+        use $crate::EuropeOdds;
+
+        /// store leagues with the given numbers of teams, the ids of a fresh store start at 1
+        /// so the fixtures refer to the leagues and then the teams by their order
+        async fn create_teams(odds_manager: &impl EuropeOdds, teams_per_league: &[i32]) {
+            let mut team_id = 0;
+            for (league_id, teams) in (1..).zip(teams_per_league) {
+                let league = LeagueBuilder::default().name(format!("联赛{league_id}")).build().unwrap();
+                let leagues = odds_manager.create_league(league).await.unwrap();
+                assert!(leagues.iter().any(|league| league.id == league_id));
+                for _ in 0..*teams {
+                    team_id += 1;
+                    let team = TeamBuilder::default()
+                        .name(format!("球队{team_id}"))
+                        .league_id(league_id)
+                        .build()
+                        .unwrap();
+                    let teams = odds_manager.create_team(team).await.unwrap();
+                    assert!(teams.iter().any(|team| team.id == team_id));
+                }
+            }
+        }
+
+        #[tokio::test]
+        async fn add_bookmaker_should_be_work() {
+            let (_guard, odds_manager) = $setup().await;
+            let bm = odds_manager
+                .create_bookermaker(
+                    BookMakerBuilder::default()
+                        .name("威廉希尔")
+                        .url("https://sports.williamhill.com/betting/en-gb")
+                        .note("第一参考的博彩网站")
+                        .build()
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(bm.len(), 1);
+        }
+
+        #[tokio::test]
+        async fn update_bookmaker_should_be_work() {
+            let (_guard, odds_manager) = $setup().await;
+            // add bookmaker
+            let mut bms = odds_manager
+                .create_bookermaker(
+                    BookMakerBuilder::default()
+                        .name("威廉希尔")
+                        .url("https://sports.williamhill.com/betting/en-gb")
+                        .note("第一参考的博彩网站")
+                        .build()
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            // update bookmaker
+            let mut bm = bms.pop().unwrap();
+            bm.name = "威廉希尔1".into();
+            let mut bm1 = odds_manager.update_bookermaker(bm).await.unwrap();
+            assert_eq!(bm1.pop().unwrap().name, "威廉希尔1");
+        }
+
+        #[tokio::test]
+        async fn delete_bookmaker_should_be_work() {
+            let (_guard, odds_manager) = $setup().await;
+            // add bookmaker
+            let mut bm = odds_manager
+                .create_bookermaker(
+                    BookMakerBuilder::default()
+                        .name("威廉希尔")
+                        .url("https://sports.williamhill.com/betting/en-gb")
+                        .note("第一参考的博彩网站")
+                        .build()
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            // delete bookmaker
+            let bms = odds_manager
+                .delete_bookermaker(bm.pop().unwrap().id)
+                .await
+                .unwrap();
+            assert_eq!(bms.len(), 0);
+        }
+
+        #[tokio::test]
+        async fn create_league_should_be_work() {
+            let (_guard, odds_manager) = $setup().await;
+            // add league
+            let bm = odds_manager
+                .create_league(LeagueBuilder::default().name("英超").build().unwrap())
+                .await
+                .unwrap();
+            assert_eq!(bm.len(), 1);
+        }
+
+        #[tokio::test]
+        async fn update_league_should_be_work() {
+            let (_guard, odds_manager) = $setup().await;
+            // add league
+            let mut bms = odds_manager
+                .create_league(LeagueBuilder::default().name("英超").build().unwrap())
+                .await
+                .unwrap();
+            // update league info
+            let mut bm = bms.pop().unwrap();
+            bm.name = "英超1".into();
+            let bm1 = odds_manager.update_league(bm).await.unwrap();
+            assert_eq!(bm1.get(0).unwrap().name, "英超1");
+        }
+
+        #[tokio::test]
+        async fn delete_league_should_be_work() {
+            let (_guard, odds_manager) = $setup().await;
+            // add league
+            let mut bms = odds_manager
+                .create_league(LeagueBuilder::default().name("英超").build().unwrap())
+                .await
+                .unwrap();
+            // delete league info
+            let bm = bms.pop().unwrap();
+            let bm1 = odds_manager.delete_league(bm.id).await.unwrap();
+            assert_eq!(bm1.len(), 0);
+        }
+
+        #[tokio::test]
+        async fn create_team_should_be_work() {
+            let (_guard, odds_manager) = $setup().await;
+            // add league
+            let mut leagues = odds_manager
+                .create_league(LeagueBuilder::default().name("英超").build().unwrap())
+                .await
+                .unwrap();
+            // add team
+            let mut teams = odds_manager
+                .create_team(
+                    TeamBuilder::default()
+                        .name("曼联")
+                        .league_id(leagues.pop().unwrap().id)
+                        .build()
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(teams.len(), 1);
+            let team = teams.pop().unwrap();
+            assert_eq!(1, team.league_id);
+            assert_eq!("曼联", team.name);
+        }
+
+        #[tokio::test]
+        async fn update_team_should_be_work() {
+            let (_guard, odds_manager) = $setup().await;
+            // add league
+            let mut leagues = odds_manager
+                .create_league(LeagueBuilder::default().name("英超").build().unwrap())
+                .await
+                .unwrap();
+            // add team
+            let mut teams = odds_manager
+                .create_team(
+                    TeamBuilder::default()
+                        .name("曼联")
+                        .league_id(leagues.pop().unwrap().id)
+                        .build()
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            // update team info
+            let mut team = teams.pop().unwrap();
+            team.name = "利物浦".into();
+            let team1 = odds_manager.update_team(team).await.unwrap();
+            assert_eq!(team1.get(0).unwrap().name, "利物浦");
+        }
+
+        #[tokio::test]
+        async fn delete_team_should_be_work() {
+            let (_guard, odds_manager) = $setup().await;
+            // add league
+            let mut leagues = odds_manager
+                .create_league(LeagueBuilder::default().name("英超").build().unwrap())
+                .await
+                .unwrap();
+            // add team
+            let mut teams = odds_manager
+                .create_team(
+                    TeamBuilder::default()
+                        .name("曼联")
+                        .league_id(leagues.pop().unwrap().id)
+                        .build()
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            // delete team info
+            let team = teams.pop().unwrap();
+            let team1 = odds_manager.delete_team(team.id).await.unwrap();
+            assert_eq!(team1.len(), 0);
+        }
+
+        #[tokio::test]
+        async fn create_matches_info_should_be_work() {
+            let (_guard, odds_manager) = $setup().await;
+            create_teams(&odds_manager, &[2]).await;
+            let matches = MatchesBuilder::default()
+                .league_id(1)
+                .league_name("英超")
+                .home_team_id(1)
+                .home_team("曼联")
+                .away_team_id(2)
+                .away_team("利物浦")
+                .game_time(NaiveDateTime::default())
+                .game_result("2:1")
+                .game_round("1")
+                .game_year("2022")
+                .build()
+                .unwrap();
+            // let odd_1 = OddsBuilder::default()
+            //     .bookmaker_id(1)
+            //     .home_win_start_setter("1.2")
+            //     .draw_start_setter("2.3")
+            //     .away_win_start_setter("3.4")
+            //     .home_win_end_setter("1.5")
+            //     .draw_end_setter("2.6")
+            //     .away_win_end_setter("3.7")
+            //     .build()
+            //     .unwrap();
+            // let odd_2 = OddsBuilder::default()
+            //     .bookmaker_id(2)
+            //     .home_win_start_setter("1.4")
+            //     .draw_start_setter("2.25")
+            //     .away_win_start_setter("3.45")
+            //     .home_win_end_setter("1.62")
+            //     .draw_end_setter("2.65")
+            //     .away_win_end_setter("3.72")
+            //     .build()
+            //     .unwrap();
+            // let odds = vec![odd_1, odd_2];
+
+            // add match info
+            let match_info = odds_manager.create_match_info(matches).await.unwrap();
+            assert!(match_info.id != 0);
+        }
+
+        #[tokio::test]
+        async fn update_matches_info_should_be_work() {
+            let (_guard, odds_manager) = $setup().await;
+            create_teams(&odds_manager, &[2]).await;
+            // add match info
+            let matches = MatchesBuilder::default()
+                .league_id(1)
+                .league_name("英超")
+                .home_team_id(1)
+                .home_team("曼联")
+                .away_team_id(2)
+                .away_team("利物浦")
+                .game_time(NaiveDateTime::default())
+                .game_result("2:1")
+                .build()
+                .unwrap();
+            // let odd_1 = OddsBuilder::default()
+            //     .bookmaker_id(1)
+            //     .home_win_start_setter("1.2")
+            //     .draw_start_setter("2.3")
+            //     .away_win_start_setter("3.4")
+            //     .home_win_end_setter("1.5")
+            //     .draw_end_setter("2.6")
+            //     .away_win_end_setter("3.7")
+            //     .build()
+            //     .unwrap();
+            // let odd_2 = OddsBuilder::default()
+            //     .bookmaker_id(2)
+            //     .home_win_start_setter("1.4")
+            //     .draw_start_setter("2.25")
+            //     .away_win_start_setter("3.45")
+            //     .home_win_end_setter("1.62")
+            //     .draw_end_setter("2.65")
+            //     .away_win_end_setter("3.72")
+            //     .build()
+            //     .unwrap();
+            // let odds = vec![odd_1, odd_2];
+            let mut match_info = odds_manager.create_match_info(matches).await.unwrap();
+            // update match info
+            match_info.game_result = Some("1:1".into());
+            match_info.home_goals = Some(1);
+            match_info.away_goals = Some(1);
+            let mut update_match = odds_manager.update_match_info(match_info).await.unwrap();
+            assert_eq!(update_match.outcome(), Some(Outcome::Draw));
+            assert_eq!(update_match.game_result.as_deref(), Some("1:1"));
+            // a game result that disagrees with the goals is rejected
+            update_match.game_result = Some("3".into());
+            let err = odds_manager.update_match_info(update_match).await.unwrap_err();
+            assert_eq!(err.code(), "validation");
+            // assert_eq!(match_info.odds[0].home_win_start, "3.01".parse().unwrap());
+            // assert_eq!(match_info.odds[1].home_win_start, "4.05".parse().unwrap());
+        }
+
+        #[tokio::test]
+        async fn delete_matches_info_should_be_work() {
+            let (_guard, odds_manager) = $setup().await;
+            create_teams(&odds_manager, &[2]).await;
+            // add match info
+            let matches = MatchesBuilder::default()
+                .league_id(1)
+                .league_name("英超")
+                .home_team_id(1)
+                .home_team("曼联")
+                .away_team_id(2)
+                .away_team("利物浦")
+                .game_time(NaiveDateTime::default())
+                .game_result("2:1")
+                .build()
+                .unwrap();
+            // let odd_1 = OddsBuilder::default()
+            //     .bookmaker_id(1)
+            //     .home_win_start_setter("1.2")
+            //     .draw_start_setter("2.3")
+            //     .away_win_start_setter("3.4")
+            //     .home_win_end_setter("1.5")
+            //     .draw_end_setter("2.6")
+            //     .away_win_end_setter("3.7")
+            //     .build()
+            //     .unwrap();
+            // let odd_2 = OddsBuilder::default()
+            //     .bookmaker_id(2)
+            //     .home_win_start_setter("1.4")
+            //     .draw_start_setter("2.25")
+            //     .away_win_start_setter("3.45")
+            //     .home_win_end_setter("1.62")
+            //     .draw_end_setter("2.65")
+            //     .away_win_end_setter("3.72")
+            //     .build()
+            //     .unwrap();
+            // let odds = vec![odd_1, odd_2];
+            let match_info = odds_manager.create_match_info(matches).await.unwrap();
+
+            // delete match info
+            let delete_count = odds_manager.delete_match_info(match_info.id).await.unwrap();
+
+            assert_eq!(1, delete_count);
+        }
+
+        #[tokio::test]
+        async fn query_odds_probability_should_be_work() {
+            let (_guard, odds_manager) = $setup().await;
+            create_teams(&odds_manager, &[2]).await;
+            // add match info
+            let matches = MatchesBuilder::default()
+                .league_id(1)
+                .league_name("英超")
+                .home_team_id(1)
+                .home_team("曼联")
+                .away_team_id(2)
+                .away_team("利物浦")
+                .game_time(NaiveDateTime::default())
+                .build()
+                .unwrap();
+            let match_info = odds_manager.create_match_info(matches).await.unwrap();
+            // add odds info
+            let odd = OddsBuilder::default()
+                .bookmaker_id(1)
+                .bookmaker_name("威廉希尔".into())
+                .home_win_start_setter("2.00")
+                .draw_start_setter("4.00")
+                .away_win_start_setter("4.00")
+                .home_win_end_setter("1.91")
+                .draw_end_setter("3.40")
+                .away_win_end_setter("4.20")
+                .build()
+                .unwrap();
+            odds_manager
+                .create_odd_info(match_info.id, odd)
+                .await
+                .unwrap();
+
+            // query implied probabilities
+            let probabilities = odds_manager
+                .query_odds_probability_by_id(match_info.id)
+                .await
+                .unwrap();
+            assert_eq!(probabilities.len(), 1);
+            let start = probabilities[0].start.clone().unwrap();
+            assert_eq!(start.home_win, "0.5".parse().unwrap());
+            let end = probabilities[0].end.clone().unwrap();
+            assert_eq!(end.overround, "0.0558".parse().unwrap());
+            assert_eq!(end.payout_rate, "0.9472".parse().unwrap());
+        }
+
+        #[tokio::test]
+        async fn query_match_info_should_be_filtered() {
+            let (_guard, odds_manager) = $setup().await;
+            create_teams(&odds_manager, &[3, 2]).await;
+            // league, home and away team, game time, goals, game result, prediction
+            let games = [
+                (1, 1, 2, "2023-04-01 20:00:00", Some((2, 1)), "3", "3"),
+                (1, 3, 1, "2023-04-08 20:00:00", None, "1", "3"),
+                (2, 4, 5, "2023-04-15 20:00:00", Some((0, 2)), "0", ""),
+                (1, 2, 3, "2023-05-01 20:00:00", None, "", "1"),
+            ];
+            let mut match_ids = vec![];
+            for (league_id, home_team_id, away_team_id, game_time, goals, result, predict) in games {
+                let mut matches = MatchesBuilder::default()
+                    .league_id(league_id)
+                    .league_name("英超")
+                    .home_team_id(home_team_id)
+                    .home_team("曼联")
+                    .away_team_id(away_team_id)
+                    .away_team("利物浦")
+                    .game_time(NaiveDateTime::parse_from_str(game_time, "%Y-%m-%d %H:%M:%S").unwrap())
+                    .game_result(result)
+                    .predict_game_result(predict)
+                    .build()
+                    .unwrap();
+                matches.home_goals = goals.map(|(home, _)| home);
+                matches.away_goals = goals.map(|(_, away)| away);
+                let matches = odds_manager.create_match_info(matches).await.unwrap();
+                match_ids.push(matches.id);
+            }
+            // the third match has two odds of the same bookmaker
+            for (index, bookmaker_id) in [(0, 1), (1, 2), (2, 1), (2, 1)] {
+                let odd = OddsBuilder::default()
+                    .bookmaker_id(bookmaker_id)
+                    .bookmaker_name("威廉希尔".into())
+                    .home_win_start_setter("2.10")
+                    .draw_start_setter("3.40")
+                    .away_win_start_setter("3.30")
+                    .home_win_end_setter("2.05")
+                    .draw_end_setter("3.50")
+                    .away_win_end_setter("3.40")
+                    .build()
+                    .unwrap();
+                odds_manager
+                    .create_odd_info(match_ids[index], odd)
+                    .await
+                    .unwrap();
+            }
+
+            let query = |filter: MatchFilter| {
+                let odds_manager = &odds_manager;
+                async move {
+                    odds_manager
+                        .query_match_info(filter)
+                        .await
+                        .unwrap()
+                        .items
+                        .iter()
+                        .map(|m| m.id)
+                        .collect::<Vec<i32>>()
+                }
+            };
+            let time = |time: &str| NaiveDateTime::parse_from_str(time, "%Y-%m-%d %H:%M:%S").unwrap();
+
+            assert_eq!(query(MatchFilter::default()).await, match_ids);
+            let filter = MatchFilterBuilder::default().bookmaker_id(1).build().unwrap();
+            assert_eq!(query(filter).await, vec![match_ids[0], match_ids[2]]);
+            let filter = MatchFilterBuilder::default()
+                .league_id(1)
+                .team_id(1)
+                .build()
+                .unwrap();
+            assert_eq!(query(filter).await, vec![match_ids[0], match_ids[1]]);
+            let filter = MatchFilterBuilder::default()
+                .start_time(time("2023-04-08 20:00:00"))
+                .end_time(time("2023-05-01 20:00:00"))
+                .build()
+                .unwrap();
+            assert_eq!(query(filter).await, vec![match_ids[1], match_ids[2]]);
+            let filter = MatchFilterBuilder::default().result(Outcome::HomeWin).build().unwrap();
+            assert_eq!(query(filter).await, vec![match_ids[0]]);
+            let filter = MatchFilterBuilder::default().result(Outcome::Draw).build().unwrap();
+            assert_eq!(query(filter).await, vec![match_ids[1]]);
+            let filter = MatchFilterBuilder::default()
+                .prediction(PredictionFilter::Hit)
+                .build()
+                .unwrap();
+            assert_eq!(query(filter).await, vec![match_ids[0]]);
+            let filter = MatchFilterBuilder::default()
+                .prediction(PredictionFilter::Miss)
+                .build()
+                .unwrap();
+            assert_eq!(query(filter).await, vec![match_ids[1]]);
+
+            // walk the pages of two from the latest kickoff and back
+            let filter = MatchFilterBuilder::default()
+                .is_desc(true)
+                .page_size(2)
+                .build()
+                .unwrap();
+            let first = odds_manager.query_match_info(filter.clone()).await.unwrap();
+            let ids: Vec<i32> = first.items.iter().map(|m| m.id).collect();
+            assert_eq!(ids, vec![match_ids[3], match_ids[2]]);
+            assert_eq!(first.total, 4);
+            assert!(first.prev_cursor.is_none());
+
+            let second = odds_manager
+                .query_match_info(MatchFilter {
+                    cursor: first.next_cursor,
+                    ..filter.clone()
+                })
+                .await
+                .unwrap();
+            let ids: Vec<i32> = second.items.iter().map(|m| m.id).collect();
+            assert_eq!(ids, vec![match_ids[1], match_ids[0]]);
+            assert_eq!(second.total, 4);
+            assert!(second.next_cursor.is_none());
+
+            let back = odds_manager
+                .query_match_info(MatchFilter {
+                    cursor: second.prev_cursor,
+                    ..filter.clone()
+                })
+                .await
+                .unwrap();
+            let ids: Vec<i32> = back.items.iter().map(|m| m.id).collect();
+            assert_eq!(ids, vec![match_ids[3], match_ids[2]]);
+            assert!(back.prev_cursor.is_none());
+            assert!(back.next_cursor.is_some());
+
+            let invalid = odds_manager
+                .query_match_info(MatchFilter {
+                    cursor: Some("not a cursor".into()),
+                    ..filter
+                })
+                .await;
+            assert!(matches!(invalid, Err(OddsError::InvalidCursor(_))));
+        }
+
+        #[tokio::test]
+        async fn score_game_result_should_be_stored_as_goals() {
+            let (_guard, odds_manager) = $setup().await;
+            create_teams(&odds_manager, &[4]).await;
+            // a result and a prediction recorded as a score
+            let mut match_ids = vec![];
+            for (home_team_id, away_team_id, result, predict) in [(1, 2, "2:1", "3"), (3, 4, "1", "2:1")] {
+                let matches = MatchesBuilder::default()
+                    .league_id(1)
+                    .league_name("英超")
+                    .home_team_id(home_team_id)
+                    .home_team("曼联")
+                    .away_team_id(away_team_id)
+                    .away_team("利物浦")
+                    .game_time(NaiveDateTime::default())
+                    .game_result(result)
+                    .predict_game_result(predict)
+                    .build()
+                    .unwrap();
+                let matches = odds_manager.create_match_info(matches).await.unwrap();
+                match_ids.push(matches.id);
+            }
+            let stored = odds_manager.query_match_info_by_id(match_ids[0]).await.unwrap();
+            assert_eq!((stored.home_goals, stored.away_goals), (Some(2), Some(1)));
+            assert_eq!(stored.game_result.as_deref(), Some("2:1"));
+
+            let query = |filter: MatchFilter| {
+                let odds_manager = &odds_manager;
+                async move {
+                    odds_manager
+                        .query_match_info(filter)
+                        .await
+                        .unwrap()
+                        .items
+                        .iter()
+                        .map(|m| m.id)
+                        .collect::<Vec<i32>>()
+                }
+            };
+            let filter = MatchFilterBuilder::default().result(Outcome::HomeWin).build().unwrap();
+            assert_eq!(query(filter).await, vec![match_ids[0]]);
+            let filter = MatchFilterBuilder::default()
+                .prediction(PredictionFilter::Hit)
+                .build()
+                .unwrap();
+            assert_eq!(query(filter).await, vec![match_ids[0]]);
+            // a score is not a prediction
+            let filter = MatchFilterBuilder::default()
+                .prediction(PredictionFilter::Miss)
+                .build()
+                .unwrap();
+            assert!(query(filter).await.is_empty());
+            let scorecard = odds_manager
+                .query_prediction_scorecard(ScorecardQuery::default())
+                .await
+                .unwrap();
+            assert_eq!(scorecard.overall.settled, 1);
+            assert_eq!(scorecard.overall.hits, 1);
+        }
+
+        #[tokio::test]
+        async fn query_match_info_with_odds_should_be_work() {
+            let (_guard, odds_manager) = $setup().await;
+            create_teams(&odds_manager, &[2]).await;
+            let mut match_ids = vec![];
+            for game_time in ["2023-04-01 20:00:00", "2023-04-02 20:00:00", "2023-04-03 20:00:00"] {
+                let matches = MatchesBuilder::default()
+                    .league_id(1)
+                    .league_name("英超")
+                    .home_team_id(1)
+                    .home_team("曼联")
+                    .away_team_id(2)
+                    .away_team("利物浦")
+                    .game_time(NaiveDateTime::parse_from_str(game_time, "%Y-%m-%d %H:%M:%S").unwrap())
+                    .build()
+                    .unwrap();
+                let matches = odds_manager.create_match_info(matches).await.unwrap();
+                match_ids.push(matches.id);
+            }
+            // the second match has no odds
+            for (index, bookmaker_id, home_win_end) in [(0, 1, "2.10"), (0, 2, "2.20"), (2, 1, "1.80")] {
+                let odd = OddsBuilder::default()
+                    .bookmaker_id(bookmaker_id)
+                    .bookmaker_name("威廉希尔".into())
+                    .home_win_start_setter("2.00")
+                    .draw_start_setter("3.40")
+                    .away_win_start_setter("3.60")
+                    .home_win_end_setter(home_win_end)
+                    .draw_end_setter("3.40")
+                    .away_win_end_setter("3.60")
+                    .build()
+                    .unwrap();
+                odds_manager
+                    .create_odd_info(match_ids[index], odd)
+                    .await
+                    .unwrap();
+            }
+
+            let filter = MatchFilterBuilder::default().page_size(2).build().unwrap();
+            let match_infos = odds_manager
+                .query_match_info_with_odds(filter.clone())
+                .await
+                .unwrap();
+            let ids: Vec<i32> = match_infos.iter().map(|info| info.matches.id).collect();
+            assert_eq!(ids, vec![match_ids[0], match_ids[1]]);
+            let bookmakers: Vec<i32> = match_infos[0].odds.iter().map(|o| o.bookmaker_id).collect();
+            assert_eq!(bookmakers, vec![1, 2]);
+            assert!(match_infos[1].odds.is_empty());
+
+            // the next page by the cursor of the match query
+            let page = odds_manager.query_match_info(filter.clone()).await.unwrap();
+            let match_infos = odds_manager
+                .query_match_info_with_odds(MatchFilter {
+                    cursor: page.next_cursor,
+                    ..filter
+                })
+                .await
+                .unwrap();
+            assert_eq!(match_infos.len(), 1);
+            assert_eq!(match_infos[0].matches.id, match_ids[2]);
+            assert_eq!(match_infos[0].odds[0].home_win_end, "1.80".parse::<BigDecimal>().unwrap());
+        }
+
+        #[tokio::test]
+        async fn import_match_csv_should_be_work() {
+            let (_guard, odds_manager) = $setup().await;
+            let leagues = odds_manager
+                .create_league(LeagueBuilder::default().name("英超").build().unwrap())
+                .await
+                .unwrap();
+            odds_manager
+                .create_team(TeamBuilder::default().name("阿森纳").league_id(leagues[0].id).build().unwrap())
+                .await
+                .unwrap();
+            odds_manager
+                .create_bookermaker(BookMakerBuilder::default().name("威廉希尔").build().unwrap())
+                .await
+                .unwrap();
+            let csv = "league,game_time,home_team,away_team,game_year,game_round,home_goals,away_goals,bookmaker,home_win_start,draw_start,away_win_start,home_win_end,draw_end,away_win_end
+英超,2023-04-01 20:00,阿森纳,利物浦,22/23,29,2,1,威廉希尔,2.10,3.40,3.30,2.05,3.50,3.40
+英超,2023-04-01 20:00,阿森纳,利物浦,22/23,29,2,1,立博,2.15,3.30,3.25,2.00,3.50,3.50
+西甲,2023-04-02 21:00,皇家马德里,巴塞罗那,22/23,27,,,,,,,,,
+";
+
+            // missing names are errors of their rows unless they may be created
+            let report = odds_manager.import_match_csv(csv, false).await.unwrap();
+            assert!(!report.is_imported());
+            let lines: Vec<usize> = report.errors.iter().map(|error| error.line).collect();
+            assert_eq!(lines, vec![2, 3, 3, 4, 4, 4]);
+            assert_eq!(odds_manager.query_match_info(MatchFilter::default()).await.unwrap().total, 0);
+
+            let report = odds_manager.import_match_csv(csv, true).await.unwrap();
+            assert!(report.is_imported());
+            assert_eq!((report.matches, report.odds), (2, 2));
+            assert_eq!(report.created_leagues, vec!["西甲"]);
+            assert_eq!(report.created_teams, vec!["利物浦", "皇家马德里", "巴塞罗那"]);
+            assert_eq!(report.created_bookmakers, vec!["立博"]);
+
+            let match_infos = odds_manager
+                .query_match_info_with_odds(MatchFilter::default())
+                .await
+                .unwrap();
+            assert_eq!(match_infos.len(), 2);
+            assert_eq!(match_infos[0].matches.outcome(), Some(Outcome::HomeWin));
+            assert_eq!(match_infos[0].matches.game_round.as_deref(), Some("29"));
+            let bookmakers: Vec<&str> = match_infos[0].odds.iter().map(|o| o.bookmaker_name.as_str()).collect();
+            assert_eq!(bookmakers, vec!["威廉希尔", "立博"]);
+            assert_eq!(match_infos[0].odds[1].draw_start, "3.30".parse::<BigDecimal>().unwrap());
+            assert_eq!(match_infos[1].matches.league_name.as_deref(), Some("西甲"));
+            assert!(match_infos[1].odds.is_empty());
+
+            // a row the store rejects rolls back the teams created before it
+            let csv = "league,game_time,home_team,away_team
+英超,2023-04-08 20:00,阿森纳,布莱顿与霍夫阿尔比恩足球俱乐部一队
+";
+            let report = odds_manager.import_match_csv(csv, true).await.unwrap();
+            assert_eq!(report.errors.len(), 1);
+            assert_eq!(report.errors[0].line, 2);
+            assert_eq!(report.matches, 0);
+            assert_eq!(odds_manager.list_teams().await.unwrap().len(), 4);
+            assert_eq!(odds_manager.query_match_info(MatchFilter::default()).await.unwrap().total, 2);
+        }
+
+        #[tokio::test]
+        async fn import_football_data_csv_should_be_work() {
+            let (_guard, odds_manager) = $setup().await;
+            let csv = "Div,Date,Time,HomeTeam,AwayTeam,FTHG,FTAG,FTR,HTHG,HTAG,B365H,B365D,B365A,PSH,PSD,PSA,PSCH,PSCD,PSCA
+E0,05/08/2022,20:00,Crystal Palace,Arsenal,0,2,A,0,1,4.2,3.6,1.85,4.5,3.65,1.89,4.6,3.6,1.88
+E0,06/08/2022,12:30,Fulham,Liverpool,,,D,,,11,6,1.25,,,,,,
+";
+            let mut config = FootballDataConfig::default();
+            config.leagues.insert("E0".into(), "英超".into());
+            config.bookmakers.remove("B365");
+
+            let report = odds_manager
+                .import_football_data_csv(csv, &config, true)
+                .await
+                .unwrap();
+            assert!(report.is_imported());
+            assert_eq!((report.matches, report.odds), (2, 1));
+            assert_eq!(report.created_leagues, vec!["英超"]);
+            assert_eq!(report.created_bookmakers, vec!["Pinnacle"]);
+
+            let match_infos = odds_manager
+                .query_match_info_with_odds(MatchFilter::default())
+                .await
+                .unwrap();
+            let matches = &match_infos[0].matches;
+            assert_eq!(matches.game_year.as_deref(), Some("22/23"));
+            assert_eq!(matches.outcome(), Some(Outcome::AwayWin));
+            assert_eq!(matches.half_time_outcome(), Some(Outcome::AwayWin));
+            assert_eq!(match_infos[0].odds[0].home_win_end, "4.6".parse::<BigDecimal>().unwrap());
+            // the result of a match without goals is taken from FTR
+            assert_eq!(match_infos[1].matches.home_goals, None);
+            assert_eq!(match_infos[1].matches.outcome(), Some(Outcome::Draw));
+            assert!(match_infos[1].odds.is_empty());
+        }
+
+        #[tokio::test]
+        async fn restore_archive_should_be_work() {
+            let (_guard, odds_manager) = $setup().await;
+            let csv = "Div,Date,Time,HomeTeam,AwayTeam,FTHG,FTAG,B365H,B365D,B365A,PSH,PSD,PSA
+E0,05/08/2022,20:00,Crystal Palace,Arsenal,0,2,4.2,3.6,1.85,4.5,3.65,1.89
+E0,06/08/2022,12:30,Fulham,Liverpool,2,2,11,6,1.25,,,
+";
+            let report = odds_manager
+                .import_football_data_csv(csv, &FootballDataConfig::default(), true)
+                .await
+                .unwrap();
+            assert!(report.is_imported());
+            // a snapshot, asian handicap and over/under odds of a bookmaker of a match
+            let odd = odds_manager.export_archive().await.unwrap().odds[0].clone();
+            let snapshot = OddsSnapshotBuilder::default()
+                .match_id(odd.match_id)
+                .bookmaker_id(odd.bookmaker_id)
+                .home_win(odd.home_win_end.clone())
+                .draw(odd.draw_end.clone())
+                .away_win(odd.away_win_end.clone())
+                .recorded_at(
+                    NaiveDateTime::parse_from_str("2022-08-05 18:00:00", "%Y-%m-%d %H:%M:%S").unwrap(),
+                )
+                .build()
+                .unwrap();
+            odds_manager.append_odds_snapshot(snapshot).await.unwrap();
+            let odds = AsianHandicapOddsBuilder::default()
+                .bookmaker_id(odd.bookmaker_id)
+                .bookmaker_name(odd.bookmaker_name.clone())
+                .line_start("0.5".parse::<BigDecimal>().unwrap())
+                .home_price_start("1.95".parse::<BigDecimal>().unwrap())
+                .away_price_start("1.90".parse::<BigDecimal>().unwrap())
+                .line_end("0.75".parse::<BigDecimal>().unwrap())
+                .home_price_end("2.02".parse::<BigDecimal>().unwrap())
+                .away_price_end("1.84".parse::<BigDecimal>().unwrap())
+                .build()
+                .unwrap();
+            odds_manager.create_asian_handicap(odd.match_id, odds).await.unwrap();
+            let odds = OverUnderOddsBuilder::default()
+                .bookmaker_id(odd.bookmaker_id)
+                .bookmaker_name(odd.bookmaker_name.clone())
+                .line_start("2.5".parse::<BigDecimal>().unwrap())
+                .over_price_start("1.90".parse::<BigDecimal>().unwrap())
+                .under_price_start("1.95".parse::<BigDecimal>().unwrap())
+                .line_end("2.25".parse::<BigDecimal>().unwrap())
+                .over_price_end("1.85".parse::<BigDecimal>().unwrap())
+                .under_price_end("2.00".parse::<BigDecimal>().unwrap())
+                .build()
+                .unwrap();
+            odds_manager.create_over_under(odd.match_id, odds).await.unwrap();
+
+            let archive = odds_manager.export_archive().await.unwrap();
+            let counts = (
+                archive.bookmakers.len(),
+                archive.leagues.len(),
+                archive.teams.len(),
+                archive.matches.len(),
+                archive.odds.len(),
+                archive.snapshots.len(),
+                archive.asian_handicaps.len(),
+                archive.over_unders.len(),
+            );
+            assert_eq!(counts, (2, 1, 4, 2, 3, 1, 1, 1));
+            let archive = Archive::from_json(&archive.to_json().unwrap()).unwrap();
+
+            // every row is stored already
+            let report = odds_manager
+                .restore_archive(archive.clone(), ConflictPolicy::Skip)
+                .await
+                .unwrap();
+            let skipped = |skipped| RestoreCount { skipped, ..RestoreCount::default() };
+            assert_eq!(report.teams, skipped(4));
+            assert_eq!(report.odds, skipped(3));
+            assert_eq!(report.snapshots, skipped(1));
+            assert_eq!(report.asian_handicaps, skipped(1));
+            assert_eq!(report.over_unders, skipped(1));
+            let err = odds_manager
+                .restore_archive(archive.clone(), ConflictPolicy::Fail)
+                .await
+                .unwrap_err();
+            assert!(matches!(err, OddsError::Conflict(_)));
+
+            let mut changed = archive.clone();
+            changed.leagues[0].note = Some("英格兰超级联赛".into());
+            changed.odds[0].draw_end = "3.70".parse().unwrap();
+            changed.over_unders[0].over_price_end = "1.80".parse().unwrap();
+            let report = odds_manager
+                .restore_archive(changed, ConflictPolicy::Overwrite)
+                .await
+                .unwrap();
+            assert_eq!(report.leagues.overwritten, 1);
+            assert_eq!(report.matches.overwritten, 2);
+            let leagues = odds_manager.list_leagues().await.unwrap();
+            assert_eq!(leagues[0].note.as_deref(), Some("英格兰超级联赛"));
+            let odds = odds_manager.query_odds_info_by_id(archive.odds[0].match_id).await.unwrap();
+            assert!(odds.iter().any(|o| o.draw_end == "3.70".parse::<BigDecimal>().unwrap()));
+            assert_eq!(report.snapshots.overwritten, 1);
+            let odds = odds_manager.query_over_under_by_id(odd.match_id).await.unwrap();
+            assert_eq!(odds.len(), 1);
+            assert_eq!(odds[0].over_price_end, "1.80".parse().unwrap());
+
+            // a store with rows of its own is given new ids
+            let (_fresh_guard, fresh) = $setup().await;
+            fresh
+                .create_bookermaker(BookMakerBuilder::default().name("威廉希尔").build().unwrap())
+                .await
+                .unwrap();
+            let report = fresh.restore_archive(archive.clone(), ConflictPolicy::Fail).await.unwrap();
+            assert_eq!(report.bookmakers.created, 2);
+            assert_eq!(report.odds.created, 3);
+            assert_eq!(report.snapshots.created, 1);
+            assert_eq!(report.asian_handicaps.created, 1);
+            assert_eq!(report.over_unders.created, 1);
+            let restored = fresh.export_archive().await.unwrap();
+            assert_eq!(restored.bookmakers.len(), 3);
+            let teams: Vec<(&str, Option<&str>)> = restored
+                .teams
+                .iter()
+                .map(|t| (t.name.as_str(), t.league_name.as_deref()))
+                .collect();
+            assert!(teams.contains(&("Arsenal", Some("E0"))));
+            for (matches, restored) in archive.matches.iter().zip(&restored.matches) {
+                assert_eq!(matches.home_team, restored.home_team);
+                assert_eq!(matches.home_goals, restored.home_goals);
+            }
+            assert_eq!(restored.odds.len(), 3);
+            let pinnacle = restored.bookmakers.iter().find(|b| b.name == "Pinnacle").unwrap();
+            assert!(restored
+                .odds
+                .iter()
+                .filter(|o| o.bookmaker_name == "Pinnacle")
+                .all(|o| o.bookmaker_id == pinnacle.id));
+            // the snapshots and odds of the match are restored with the ids of the fresh store
+            let bookmaker = restored
+                .bookmakers
+                .iter()
+                .find(|b| b.name == odd.bookmaker_name)
+                .unwrap();
+            let home_team = &archive.matches.iter().find(|m| m.id == odd.match_id).unwrap().home_team;
+            let matches = restored.matches.iter().find(|m| m.home_team == *home_team).unwrap();
+            assert_eq!(restored.snapshots.len(), 1);
+            assert_eq!(restored.snapshots[0].recorded_at, archive.snapshots[0].recorded_at);
+            assert_eq!(restored.snapshots[0].home_win, archive.snapshots[0].home_win);
+            assert_eq!(restored.asian_handicaps.len(), 1);
+            assert_eq!(restored.asian_handicaps[0].line_end, "0.75".parse().unwrap());
+            assert_eq!(restored.over_unders.len(), 1);
+            assert_eq!(restored.over_unders[0].under_price_end, "2.00".parse().unwrap());
+            assert!([
+                (restored.snapshots[0].match_id, restored.snapshots[0].bookmaker_id),
+                (restored.asian_handicaps[0].match_id, restored.asian_handicaps[0].bookmaker_id),
+                (restored.over_unders[0].match_id, restored.over_unders[0].bookmaker_id),
+            ]
+            .iter()
+            .all(|ids| *ids == (matches.id, bookmaker.id)));
+        }
+
+        #[tokio::test]
+        async fn query_similar_odds_should_be_work() {
+            let (_guard, odds_manager) = $setup().await;
+            create_teams(&odds_manager, &[2]).await;
+            // add matches with the odds of one bookmaker
+            let games = [
+                ("2022-12-10 20:00:00", "", ["2.10", "3.30", "3.40"]),
+                ("2022-11-10 20:00:00", "3", ["2.15", "3.25", "3.40"]),
+                ("2022-10-10 20:00:00", "2:2", ["2.05", "3.35", "3.50"]),
+                ("2022-09-10 20:00:00", "0", ["2.50", "3.30", "2.80"]),
+                ("2022-12-20 20:00:00", "3", ["2.10", "3.30", "3.40"]),
+            ];
+            let mut match_ids = vec![];
+            for (game_time, game_result, prices) in games {
+                let matches = MatchesBuilder::default()
+                    .league_id(1)
+                    .league_name("英超")
+                    .home_team_id(1)
+                    .home_team("曼联")
+                    .away_team_id(2)
+                    .away_team("利物浦")
+                    .game_time(NaiveDateTime::parse_from_str(game_time, "%Y-%m-%d %H:%M:%S").unwrap())
+                    .game_result(game_result)
+                    .game_year("2022")
+                    .build()
+                    .unwrap();
+                let match_info = odds_manager.create_match_info(matches).await.unwrap();
+                let odd = OddsBuilder::default()
+                    .bookmaker_id(1)
+                    .bookmaker_name("威廉希尔".into())
+                    .home_win_start_setter(prices[0])
+                    .draw_start_setter(prices[1])
+                    .away_win_start_setter(prices[2])
+                    .home_win_end_setter(prices[0])
+                    .draw_end_setter(prices[1])
+                    .away_win_end_setter(prices[2])
+                    .build()
+                    .unwrap();
+                odds_manager
+                    .create_odd_info(match_info.id, odd)
+                    .await
+                    .unwrap();
+                match_ids.push(match_info.id);
+            }
+
+            // query matches before the first one within 0.10 of its opening prices
+            let similar = odds_manager
+                .query_similar_odds(SimilarOddsQuery {
+                    match_id: match_ids[0],
+                    bookmaker_id: 1,
+                    start_tolerance: Some("0.10".parse().unwrap()),
+                    end_tolerance: None,
+                    league_id: 1,
+                    game_year: Some("2022".into()),
+                })
+                .await
+                .unwrap();
+            let ids: Vec<i32> = similar.matches.iter().map(|m| m.matches.id).collect();
+            assert_eq!(ids, vec![match_ids[1], match_ids[2]]);
+            assert_eq!(similar.distribution.home_win, 1);
+            assert_eq!(similar.distribution.draw, 1);
+            assert_eq!(similar.distribution.away_win, 0);
+
+            // the looked up odds should exist
+            let not_found = odds_manager
+                .query_similar_odds(SimilarOddsQuery {
+                    match_id: match_ids[0],
+                    bookmaker_id: 2,
+                    start_tolerance: None,
+                    end_tolerance: None,
+                    league_id: 0,
+                    game_year: None,
+                })
+                .await;
+            assert!(matches!(not_found, Err(OddsError::NotFound)));
+        }
+
+        #[tokio::test]
+        async fn query_odds_bands_should_be_work() {
+            let (_guard, odds_manager) = $setup().await;
+            create_teams(&odds_manager, &[2]).await;
+            // add matches with the odds of one bookmaker
+            let games = [("3", "1.25"), ("1", "1.28"), ("0", "1.35")];
+            for (game_result, home_win_end) in games {
+                let matches = MatchesBuilder::default()
+                    .league_id(1)
+                    .league_name("英超")
+                    .home_team_id(1)
+                    .home_team("曼联")
+                    .away_team_id(2)
+                    .away_team("利物浦")
+                    .game_time(NaiveDateTime::default())
+                    .game_result(game_result)
+                    .build()
+                    .unwrap();
+                let match_info = odds_manager.create_match_info(matches).await.unwrap();
+                let odd = OddsBuilder::default()
+                    .bookmaker_id(1)
+                    .bookmaker_name("威廉希尔".into())
+                    .home_win_start_setter("2.00")
+                    .draw_start_setter("3.40")
+                    .away_win_start_setter("3.60")
+                    .home_win_end_setter(home_win_end)
+                    .draw_end_setter("3.40")
+                    .away_win_end_setter("3.60")
+                    .build()
+                    .unwrap();
+                odds_manager
+                    .create_odd_info(match_info.id, odd)
+                    .await
+                    .unwrap();
+            }
+
+            let bands = odds_manager
+                .query_odds_bands(OddsBandQuery {
+                    band_width: None,
+                    league_id: 1,
+                    bookmaker_id: 1,
+                    per_league: true,
+                    per_bookmaker: false,
+                })
+                .await
+                .unwrap();
+            assert_eq!(bands.len(), 2);
+            assert_eq!(bands[0].league_name, Some("英超".into()));
+            assert_eq!(bands[0].distribution.settled(), 2);
+            assert_eq!(bands[1].distribution.away_win, 1);
+        }
+
+        #[tokio::test]
+        async fn query_odds_bands_should_settle_matches_by_goals() {
+            let (_guard, odds_manager) = $setup().await;
+            create_teams(&odds_manager, &[2]).await;
+            // the goals of a match without a game result
+            let matches = MatchesBuilder::default()
+                .league_id(1)
+                .league_name("英超")
+                .home_team_id(1)
+                .home_team("曼联")
+                .away_team_id(2)
+                .away_team("利物浦")
+                .game_time(NaiveDateTime::default())
+                .home_goals(2)
+                .away_goals(0)
+                .build()
+                .unwrap();
+            let match_info = odds_manager.create_match_info(matches).await.unwrap();
+            assert_eq!(match_info.game_result, None);
+            let odd = OddsBuilder::default()
+                .bookmaker_id(1)
+                .bookmaker_name("威廉希尔".into())
+                .home_win_start_setter("2.00")
+                .draw_start_setter("3.40")
+                .away_win_start_setter("3.60")
+                .home_win_end_setter("1.85")
+                .draw_end_setter("3.40")
+                .away_win_end_setter("3.60")
+                .build()
+                .unwrap();
+            odds_manager.create_odd_info(match_info.id, odd).await.unwrap();
+
+            let bands = odds_manager
+                .query_odds_bands(OddsBandQuery {
+                    band_width: None,
+                    league_id: 0,
+                    bookmaker_id: 0,
+                    per_league: false,
+                    per_bookmaker: false,
+                })
+                .await
+                .unwrap();
+            assert_eq!(bands.len(), 1);
+            assert_eq!(bands[0].distribution.settled(), 1);
+            assert_eq!(bands[0].distribution.home_win, 1);
+        }
+
+        #[tokio::test]
+        async fn append_odds_snapshot_should_be_work() {
+            let (_guard, odds_manager) = $setup().await;
+            create_teams(&odds_manager, &[2]).await;
+            let bookmaker = odds_manager
+                .create_bookermaker(BookMakerBuilder::default().name("立博").build().unwrap())
+                .await
+                .unwrap()
+                .remove(0);
+            let matches = MatchesBuilder::default()
+                .league_id(1)
+                .league_name("英超")
+                .home_team_id(1)
+                .home_team("曼联")
+                .away_team_id(2)
+                .away_team("利物浦")
+                .game_time(
+                    NaiveDateTime::parse_from_str("2023-02-20 20:00:00", "%Y-%m-%d %H:%M:%S").unwrap(),
+                )
+                .build()
+                .unwrap();
+            let match_info = odds_manager.create_match_info(matches).await.unwrap();
+
+            // snapshots are appended out of order
+            let snapshots = [
+                ("2023-02-19 10:00:00", ["2.10", "3.30", "3.40"]),
+                ("2023-02-18 10:00:00", ["2.20", "3.25", "3.30"]),
+                ("2023-02-20 19:00:00", ["1.95", "3.40", "3.80"]),
+            ];
+            for (recorded_at, prices) in snapshots {
+                let snapshot = OddsSnapshotBuilder::default()
+                    .match_id(match_info.id)
+                    .bookmaker_id(bookmaker.id)
+                    .home_win(prices[0].parse::<BigDecimal>().unwrap())
+                    .draw(prices[1].parse::<BigDecimal>().unwrap())
+                    .away_win(prices[2].parse::<BigDecimal>().unwrap())
+                    .recorded_at(
+                        NaiveDateTime::parse_from_str(recorded_at, "%Y-%m-%d %H:%M:%S").unwrap(),
+                    )
+                    .build()
+                    .unwrap();
+                let snapshot = odds_manager.append_odds_snapshot(snapshot).await.unwrap();
+                assert!(snapshot.id > 0);
+            }
+
+            let timeline = odds_manager
+                .query_odds_timeline(match_info.id, bookmaker.id)
+                .await
+                .unwrap();
+            let prices: Vec<BigDecimal> = timeline.iter().map(|s| s.home_win.clone()).collect();
+            let expected: Vec<BigDecimal> = ["2.20", "2.10", "1.95"]
+                .iter()
+                .map(|price| price.parse().unwrap())
+                .collect();
+            assert_eq!(prices, expected);
+
+            // the odds are derived from the first and the last snapshot
+            let odds = odds_manager
+                .query_odds_info_by_id(match_info.id)
+                .await
+                .unwrap();
+            assert_eq!(odds.len(), 1);
+            assert_eq!(odds[0].bookmaker_name, "立博");
+            assert_eq!(odds[0].home_win_start, "2.20".parse().unwrap());
+            assert_eq!(odds[0].away_win_start, "3.30".parse().unwrap());
+            assert_eq!(odds[0].home_win_end, "1.95".parse().unwrap());
+            assert_eq!(odds[0].away_win_end, "3.80".parse().unwrap());
+
+            // snapshots are deleted with the match
+            odds_manager.delete_match_info(match_info.id).await.unwrap();
+            let timeline = odds_manager
+                .query_odds_timeline(match_info.id, bookmaker.id)
+                .await
+                .unwrap();
+            assert!(timeline.is_empty());
+        }
+
+        #[tokio::test]
+        async fn asian_handicap_should_be_work() {
+            let (_guard, odds_manager) = $setup().await;
+            create_teams(&odds_manager, &[2]).await;
+            let matches = MatchesBuilder::default()
+                .league_id(1)
+                .league_name("英超")
+                .home_team_id(1)
+                .home_team("曼联")
+                .away_team_id(2)
+                .away_team("利物浦")
+                .game_time(
+                    NaiveDateTime::parse_from_str("2023-03-01 20:00:00", "%Y-%m-%d %H:%M:%S").unwrap(),
+                )
+                .build()
+                .unwrap();
+            let match_info = odds_manager.create_match_info(matches).await.unwrap();
+            let odds = AsianHandicapOddsBuilder::default()
+                .bookmaker_id(1)
+                .bookmaker_name("威廉希尔".into())
+                .line_start("-0.25".parse::<BigDecimal>().unwrap())
+                .home_price_start("1.95".parse::<BigDecimal>().unwrap())
+                .away_price_start("1.90".parse::<BigDecimal>().unwrap())
+                .line_end("-0.25".parse::<BigDecimal>().unwrap())
+                .home_price_end("1.95".parse::<BigDecimal>().unwrap())
+                .away_price_end("1.90".parse::<BigDecimal>().unwrap())
+                .build()
+                .unwrap();
+            let mut odds = odds_manager
+                .create_asian_handicap(match_info.id, odds)
+                .await
+                .unwrap();
+            assert!(odds.id > 0);
+
+            odds.match_id = match_info.id;
+            odds.line_end = "-0.75".parse().unwrap();
+            odds.home_price_end = "2.02".parse().unwrap();
+            odds.away_price_end = "1.84".parse().unwrap();
+            let updated = odds_manager
+                .update_asian_handicap(odds.clone())
+                .await
+                .unwrap();
+            assert_eq!(updated.line_end, "-0.75".parse().unwrap());
+
+            // lines that are not quarter lines are rejected
+            odds.line_end = "-0.8".parse().unwrap();
+            let invalid = odds_manager.update_asian_handicap(odds.clone()).await.unwrap_err();
+            assert_eq!(invalid.fields()[0].field, "line_end");
+
+            let list = odds_manager
+                .query_asian_handicap_by_id(match_info.id)
+                .await
+                .unwrap();
+            assert_eq!(list.len(), 1);
+            assert_eq!(list[0].home_price_end, "2.02".parse().unwrap());
+
+            let count = odds_manager.delete_asian_handicap(odds.id).await.unwrap();
+            assert_eq!(count, 1);
+        }
+
+        #[tokio::test]
+        async fn save_asian_handicaps_should_be_work() {
+            let (_guard, odds_manager) = $setup().await;
+            create_teams(&odds_manager, &[2]).await;
+            let matches = MatchesBuilder::default()
+                .league_id(1)
+                .league_name("英超")
+                .home_team_id(1)
+                .home_team("曼联")
+                .away_team_id(2)
+                .away_team("利物浦")
+                .game_time(NaiveDateTime::default())
+                .build()
+                .unwrap();
+            let match_id = odds_manager.create_match_info(matches).await.unwrap().id;
+            let handicap = |bookmaker_id: i32, line: &str| {
+                AsianHandicapOddsBuilder::default()
+                    .bookmaker_id(bookmaker_id)
+                    .bookmaker_name("威廉希尔".into())
+                    .line_start(line.parse::<BigDecimal>().unwrap())
+                    .home_price_start("1.95".parse::<BigDecimal>().unwrap())
+                    .away_price_start("1.90".parse::<BigDecimal>().unwrap())
+                    .line_end(line.parse::<BigDecimal>().unwrap())
+                    .home_price_end("1.95".parse::<BigDecimal>().unwrap())
+                    .away_price_end("1.90".parse::<BigDecimal>().unwrap())
+                    .build()
+                    .unwrap()
+            };
+            let saved = odds_manager
+                .save_asian_handicaps(match_id, vec![handicap(1, "-0.25"), handicap(2, "-0.5")])
+                .await
+                .unwrap();
+            assert!(saved.iter().all(|odds| odds.id > 0 && odds.match_id == match_id));
+
+            // update the first odds, drop the second one and add a third one
+            let mut first = saved[0].clone();
+            first.line_end = "-0.75".parse().unwrap();
+            odds_manager
+                .save_asian_handicaps(match_id, vec![first.clone(), handicap(3, "0")])
+                .await
+                .unwrap();
+            let mut stored = odds_manager.query_asian_handicap_by_id(match_id).await.unwrap();
+            stored.sort_by_key(|odds| odds.bookmaker_id);
+            let bookmakers: Vec<i32> = stored.iter().map(|odds| odds.bookmaker_id).collect();
+            assert_eq!(bookmakers, vec![1, 3]);
+            assert_eq!(stored[0].line_end, "-0.75".parse().unwrap());
+
+            // an id of another match and an invalid line roll back the whole save
+            let mut foreign = handicap(4, "-1");
+            foreign.id = stored[1].id + 100;
+            let result = odds_manager
+                .save_asian_handicaps(match_id, vec![first.clone(), foreign])
+                .await;
+            assert!(matches!(result, Err(OddsError::NotFound)));
+            let result = odds_manager
+                .save_asian_handicaps(match_id, vec![handicap(4, "-1"), handicap(5, "-0.6")])
+                .await;
+            assert_eq!(result.unwrap_err().code(), "validation");
+            let stored = odds_manager.query_asian_handicap_by_id(match_id).await.unwrap();
+            assert_eq!(stored.len(), 2);
+        }
+
+        #[tokio::test]
+        async fn over_under_should_be_work() {
+            let (_guard, odds_manager) = $setup().await;
+            create_teams(&odds_manager, &[2]).await;
+            let matches = MatchesBuilder::default()
+                .league_id(1)
+                .league_name("英超")
+                .home_team_id(1)
+                .home_team("曼联")
+                .away_team_id(2)
+                .away_team("利物浦")
+                .game_time(
+                    NaiveDateTime::parse_from_str("2023-03-10 20:00:00", "%Y-%m-%d %H:%M:%S").unwrap(),
+                )
+                .build()
+                .unwrap();
+            let mut match_info = odds_manager.create_match_info(matches).await.unwrap();
+            let odds = OverUnderOddsBuilder::default()
+                .bookmaker_id(1)
+                .bookmaker_name("威廉希尔".into())
+                .line_start("2.5".parse::<BigDecimal>().unwrap())
+                .over_price_start("1.90".parse::<BigDecimal>().unwrap())
+                .under_price_start("1.95".parse::<BigDecimal>().unwrap())
+                .line_end("2.5".parse::<BigDecimal>().unwrap())
+                .over_price_end("1.90".parse::<BigDecimal>().unwrap())
+                .under_price_end("1.95".parse::<BigDecimal>().unwrap())
+                .build()
+                .unwrap();
+            let mut odds = odds_manager
+                .create_over_under(match_info.id, odds)
+                .await
+                .unwrap();
+            assert!(odds.id > 0);
+
+            odds.match_id = match_info.id;
+            odds.line_end = "2.25".parse().unwrap();
+            odds.over_price_end = "1.85".parse().unwrap();
+            odds.under_price_end = "2.00".parse().unwrap();
+            odds_manager.update_over_under(odds.clone()).await.unwrap();
+
+            // negative lines are rejected
+            let mut invalid = odds.clone();
+            invalid.line_end = "-1".parse().unwrap();
+            let invalid = odds_manager.update_over_under(invalid).await.unwrap_err();
+            assert_eq!(invalid.fields()[0].field, "line_end");
+
+            // no settlement until the score is recorded
+            let settlements = odds_manager
+                .query_over_under_settlement_by_id(match_info.id)
+                .await
+                .unwrap();
+            assert_eq!(settlements.len(), 1);
+            assert!(settlements[0].end.is_none());
+
+            match_info.home_goals = Some(1);
+            match_info.away_goals = Some(1);
+            odds_manager
+                .update_match_info(match_info.clone())
+                .await
+                .unwrap();
+            let settlements = odds_manager
+                .query_over_under_settlement_by_id(match_info.id)
+                .await
+                .unwrap();
+            assert_eq!(settlements[0].total_goals, Some(2));
+            assert_eq!(settlements[0].start.clone().unwrap().under, Settlement::Win);
+            assert_eq!(
+                settlements[0].end.clone().unwrap().over,
+                Settlement::HalfLoss
+            );
+
+            let count = odds_manager.delete_over_under(odds.id).await.unwrap();
+            assert_eq!(count, 1);
+            let not_found = odds_manager.query_match_info_by_id(0).await;
+            assert!(matches!(not_found, Err(OddsError::NotFound)));
+        }
+
+        #[tokio::test]
+        async fn save_over_unders_should_be_work() {
+            let (_guard, odds_manager) = $setup().await;
+            create_teams(&odds_manager, &[2]).await;
+            let matches = MatchesBuilder::default()
+                .league_id(1)
+                .league_name("英超")
+                .home_team_id(1)
+                .home_team("曼联")
+                .away_team_id(2)
+                .away_team("利物浦")
+                .game_time(NaiveDateTime::default())
+                .build()
+                .unwrap();
+            let match_id = odds_manager.create_match_info(matches).await.unwrap().id;
+            let total = |bookmaker_id: i32, line: &str| {
+                OverUnderOddsBuilder::default()
+                    .bookmaker_id(bookmaker_id)
+                    .bookmaker_name("威廉希尔".into())
+                    .line_start(line.parse::<BigDecimal>().unwrap())
+                    .over_price_start("1.90".parse::<BigDecimal>().unwrap())
+                    .under_price_start("1.95".parse::<BigDecimal>().unwrap())
+                    .line_end(line.parse::<BigDecimal>().unwrap())
+                    .over_price_end("1.90".parse::<BigDecimal>().unwrap())
+                    .under_price_end("1.95".parse::<BigDecimal>().unwrap())
+                    .build()
+                    .unwrap()
+            };
+            let saved = odds_manager
+                .save_over_unders(match_id, vec![total(1, "2.5"), total(2, "2.25")])
+                .await
+                .unwrap();
+            assert!(saved.iter().all(|odds| odds.id > 0 && odds.match_id == match_id));
+
+            // update the first odds, drop the second one and add a third one
+            let mut first = saved[0].clone();
+            first.line_end = "2.75".parse().unwrap();
+            odds_manager
+                .save_over_unders(match_id, vec![first.clone(), total(3, "3")])
+                .await
+                .unwrap();
+            let mut stored = odds_manager.query_over_under_by_id(match_id).await.unwrap();
+            stored.sort_by_key(|odds| odds.bookmaker_id);
+            let bookmakers: Vec<i32> = stored.iter().map(|odds| odds.bookmaker_id).collect();
+            assert_eq!(bookmakers, vec![1, 3]);
+            assert_eq!(stored[0].line_end, "2.75".parse().unwrap());
+
+            // an id of another match and a negative line roll back the whole save
+            let mut foreign = total(4, "2");
+            foreign.id = stored[1].id + 100;
+            let result = odds_manager
+                .save_over_unders(match_id, vec![first.clone(), foreign])
+                .await;
+            assert!(matches!(result, Err(OddsError::NotFound)));
+            let result = odds_manager
+                .save_over_unders(match_id, vec![total(4, "2"), total(5, "-0.5")])
+                .await;
+            assert_eq!(result.unwrap_err().code(), "validation");
+            let stored = odds_manager.query_over_under_by_id(match_id).await.unwrap();
+            assert_eq!(stored.len(), 2);
+        }
+
+        #[tokio::test]
+        async fn save_match_info_should_be_work() {
+            let (_guard, odds_manager) = $setup().await;
+            create_teams(&odds_manager, &[2]).await;
+            let matches = MatchesBuilder::default()
+                .league_id(1)
+                .league_name("英超")
+                .home_team_id(1)
+                .home_team("曼联")
+                .away_team_id(2)
+                .away_team("利物浦")
+                .game_time(
+                    NaiveDateTime::parse_from_str("2023-03-20 20:00:00", "%Y-%m-%d %H:%M:%S").unwrap(),
+                )
+                .build()
+                .unwrap();
+            let odd = |bookmaker_id: i32, home_win_start: &str| {
+                OddsBuilder::default()
+                    .bookmaker_id(bookmaker_id)
+                    .bookmaker_name(format!("bookmaker{bookmaker_id}"))
+                    .home_win_start_setter(home_win_start)
+                    .draw_start_setter("3.40")
+                    .away_win_start_setter("3.30")
+                    .home_win_end_setter(home_win_start)
+                    .draw_end_setter("3.40")
+                    .away_win_end_setter("3.30")
+                    .build()
+                    .unwrap()
+            };
+            let saved = odds_manager
+                .save_match_info(MatchInfo::new(
+                    matches,
+                    vec![odd(1, "2.10"), odd(2, "2.20")],
+                ))
+                .await
+                .unwrap();
+            let match_id = saved.matches.id;
+            assert!(match_id > 0);
+            assert!(saved
+                .odds
+                .iter()
+                .all(|odd| odd.id > 0 && odd.match_id == match_id));
+
+            // update the first odds, drop the second one and add a third one
+            let mut matches = saved.matches.clone();
+            matches.game_result = Some("2:1".into());
+            let mut first = saved.odds[0].clone();
+            first.home_win_start = "2.05".parse().unwrap();
+            let saved = odds_manager
+                .save_match_info(MatchInfo::new(matches.clone(), vec![first, odd(3, "2.30")]))
+                .await
+                .unwrap();
+            assert_eq!(saved.matches.game_result, Some("2:1".into()));
+            let mut odds = odds_manager.query_odds_info_by_id(match_id).await.unwrap();
+            odds.sort_by_key(|odd| odd.bookmaker_id);
+            let bookmakers: Vec<i32> = odds.iter().map(|odd| odd.bookmaker_id).collect();
+            assert_eq!(bookmakers, vec![1, 3]);
+            assert_eq!(odds[0].home_win_start, "2.05".parse().unwrap());
+
+            // a failing odds row rolls back the whole save
+            matches.game_result = Some("0:0".into());
+            let mut invalid = odd(4, "2.40");
+            invalid.bookmaker_name = "a bookmaker name longer than thirty characters".into();
+            let result = odds_manager
+                .save_match_info(MatchInfo::new(matches, vec![invalid]))
+                .await;
+            assert!(result.is_err());
+            let stored = odds_manager.query_match_info_by_id(match_id).await.unwrap();
+            assert_eq!(stored.game_result, Some("2:1".into()));
+            let odds = odds_manager.query_odds_info_by_id(match_id).await.unwrap();
+            assert_eq!(odds.len(), 2);
+
+            // odds with an id that is not stored for the match are not found and
+            // nothing of the save is kept
+            let mut matches = stored;
+            matches.note = Some("主队降盘".into());
+            let mut stale = odds[0].clone();
+            stale.id = odds.iter().map(|odd| odd.id).max().unwrap() + 100;
+            let result = odds_manager
+                .save_match_info(MatchInfo::new(matches, vec![stale, odd(5, "2.50")]))
+                .await;
+            assert!(matches!(result, Err(OddsError::NotFound)));
+            let stored = odds_manager.query_match_info_by_id(match_id).await.unwrap();
+            assert_ne!(stored.note.as_deref(), Some("主队降盘"));
+            let odds = odds_manager.query_odds_info_by_id(match_id).await.unwrap();
+            assert_eq!(odds.len(), 2);
+        }
+
+        #[tokio::test]
+        async fn errors_should_be_typed() {
+            let (_guard, odds_manager) = $setup().await;
+            let bookmaker = || BookMakerBuilder::default().name("威廉希尔").build().unwrap();
+            odds_manager.create_bookermaker(bookmaker()).await.unwrap();
+            let duplicate = odds_manager.create_bookermaker(bookmaker()).await;
+            assert!(matches!(duplicate, Err(OddsError::UniqueViolation(_))));
+
+            let missing = odds_manager.query_bookermaker_with_id(999).await;
+            assert!(matches!(missing, Err(OddsError::NotFound)));
+            let mut missing = bookmaker();
+            missing.id = 999;
+            let missing = odds_manager.update_bookermaker(missing).await;
+            assert!(matches!(missing, Err(OddsError::NotFound)));
+
+            let too_long = LeagueBuilder::default()
+                .name("a league name longer than thirty characters")
+                .build()
+                .unwrap();
+            let too_long = odds_manager.create_league(too_long).await;
+            assert!(matches!(too_long, Err(OddsError::Validation(_))));
+
+            let odd = OddsBuilder::default()
+                .bookmaker_id(1)
+                .bookmaker_name("威廉希尔".to_string())
+                .home_win_start_setter("2.10")
+                .draw_start_setter("3.40")
+                .away_win_start_setter("3.30")
+                .home_win_end_setter("2.05")
+                .draw_end_setter("3.50")
+                .away_win_end_setter("3.40")
+                .build()
+                .unwrap();
+            let orphan = odds_manager.create_odd_info(999, odd).await;
+            assert!(matches!(orphan, Err(OddsError::ForeignKeyViolation(_))));
+        }
+
+        #[tokio::test]
+        async fn query_prediction_scorecard_should_be_work() {
+            let (_guard, odds_manager) = $setup().await;
+            create_teams(&odds_manager, &[2, 2]).await;
+            // league, game time, game result, prediction
+            let games = [
+                (1, "2023-04-01 20:00:00", Some("2:0"), Some("3")),
+                (1, "2023-04-08 20:00:00", Some("1:1"), Some("3")),
+                (2, "2023-05-06 20:00:00", Some("0:1"), Some("0")),
+                (1, "2023-05-13 20:00:00", None, Some("1")),
+                (1, "2023-05-20 20:00:00", Some("3:1"), None),
+            ];
+            for (league_id, game_time, result, predict) in games {
+                let mut matches = MatchesBuilder::default()
+                    .league_id(league_id)
+                    .league_name(if league_id == 1 { "英超" } else { "西甲" })
+                    .home_team_id(league_id * 2 - 1)
+                    .home_team("主队")
+                    .away_team_id(league_id * 2)
+                    .away_team("客队")
+                    .game_time(NaiveDateTime::parse_from_str(game_time, "%Y-%m-%d %H:%M:%S").unwrap())
+                    .build()
+                    .unwrap();
+                matches.game_result = result.map(str::to_string);
+                matches.predict_game_result = predict.map(str::to_string);
+                odds_manager.create_match_info(matches).await.unwrap();
+            }
+
+            let query = ScorecardQuery {
+                league_id: None,
+                group_by: ScorecardGroup::League,
+                window: Some(2),
+            };
+            let scorecard = odds_manager.query_prediction_scorecard(query).await.unwrap();
+            assert_eq!(scorecard.overall.settled, 3);
+            assert_eq!(scorecard.overall.hits, 2);
+            assert_eq!(scorecard.overall.pending, 1);
+            assert_eq!(scorecard.overall.confusion.count(Outcome::HomeWin, Outcome::Draw), 1);
+            let keys: Vec<_> = scorecard.groups.iter().map(|group| group.key.clone()).collect();
+            assert_eq!(keys, [Some("英超".to_string()), Some("西甲".to_string())]);
+            assert_eq!(scorecard.groups[0].hit_rate, "0.5".parse::<BigDecimal>().unwrap());
+            let hits: Vec<bool> = scorecard.trend.iter().map(|point| point.hit).collect();
+            assert_eq!(hits, [true, false, true]);
+
+            let query = ScorecardQuery {
+                league_id: Some(2),
+                group_by: ScorecardGroup::Month,
+                window: None,
+            };
+            let scorecard = odds_manager.query_prediction_scorecard(query).await.unwrap();
+            assert_eq!(scorecard.overall.settled, 1);
+            assert_eq!(scorecard.groups[0].key.as_deref(), Some("2023-05"));
+        }
+
+        #[tokio::test]
+        async fn invalid_rows_should_not_be_written() {
+            let (_guard, odds_manager) = $setup().await;
+            let fields = |err: OddsError| -> Vec<String> {
+                err.fields().iter().map(|field| field.field.clone()).collect()
+            };
+            let league = |name: &str| LeagueBuilder::default().name(name).build().unwrap();
+            odds_manager.create_league(league("英超")).await.unwrap();
+            let leagues = odds_manager.create_league(league("西甲")).await.unwrap();
+            let team = |name: &str, league_id: i32| {
+                TeamBuilder::default().name(name).league_id(league_id).build().unwrap()
+            };
+            odds_manager.create_team(team("曼联", leagues[0].id)).await.unwrap();
+            let teams = odds_manager.create_team(team("皇马", leagues[1].id)).await.unwrap();
+            let (home, away) = (&teams[0], &teams[1]);
+
+            let mut matches = MatchesBuilder::default()
+                .league_id(home.league_id)
+                .league_name("英超")
+                .home_team_id(home.id)
+                .home_team("曼联")
+                .away_team_id(home.id)
+                .away_team("曼联")
+                .home_goals(1)
+                .half_home_goals(2)
+                .build()
+                .unwrap();
+            let err = odds_manager.create_match_info(matches.clone()).await.unwrap_err();
+            assert_eq!(fields(err), ["away_team_id", "game_time", "half_home_goals"]);
+
+            matches.away_team_id = away.id;
+            matches.away_team = Some("皇马".into());
+            matches.game_time = Some(NaiveDateTime::default());
+            matches.half_home_goals = Some(0);
+            let err = odds_manager.create_match_info(matches.clone()).await.unwrap_err();
+            assert_eq!(fields(err), ["away_team_id"]);
+
+            let page = odds_manager.query_match_info(MatchFilter::default()).await.unwrap();
+            assert!(page.items.is_empty());
+
+            let odd = OddsBuilder::default()
+                .bookmaker_id(1)
+                .bookmaker_name("威廉希尔".into())
+                .home_win_start_setter("1.00")
+                .draw_start_setter("3.40")
+                .away_win_start_setter("3.30")
+                .home_win_end_setter("2.05")
+                .draw_end_setter("3.50")
+                .away_win_end_setter("1.00")
+                .build()
+                .unwrap();
+            let err = odds_manager.create_odd_info(1, odd).await.unwrap_err();
+            assert_eq!(fields(err), ["home_win_start", "away_win_end"]);
+        }
+    };
+}
+
+pub(crate) use europe_odds_test_suite;